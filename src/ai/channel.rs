@@ -1,9 +1,13 @@
 use crate::{Data, Error};
 use poise::serenity_prelude::{self as serenity, CreateMessage};
 use serde::Deserialize;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+use crate::utils::ratelimit::{cooldown_message, AI_BUCKET};
 
 const PENDING_TIMEOUT_SECS: u64 = 120;
+/// 쿨다운 안내 메시지를 보여주는 시간
+const COOLDOWN_NOTICE_TTL: Duration = Duration::from_secs(5);
 
 pub async fn handle(
     ctx: &serenity::Context,
@@ -39,6 +43,17 @@ pub async fn handle(
         has_image,
     )?;
 
+    // Gemini를 호출하는 경로(이미지/멘션)는 쿨다운 적용
+    if has_image || mentioned {
+        if let Err(remaining) = data
+            .rate_limiter
+            .check(AI_BUCKET, msg.author.id, msg.guild_id)
+        {
+            reply_cooldown(ctx, msg, remaining).await?;
+            return Ok(());
+        }
+    }
+
     // 1. 이미지 첨부 시 Gemini 이미지 분석
     if has_image {
         handle_image(ctx, msg, data, api_key, &channel_id_str, db).await?;
@@ -58,6 +73,21 @@ pub async fn handle(
     Ok(())
 }
 
+/// 메시지에는 ephemeral 응답이 없으므로 잠시 보여준 뒤 삭제
+async fn reply_cooldown(
+    ctx: &serenity::Context,
+    msg: &serenity::Message,
+    remaining: Duration,
+) -> Result<(), Error> {
+    let reply = msg.reply(ctx, cooldown_message(remaining)).await?;
+    let http = ctx.http.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(COOLDOWN_NOTICE_TTL).await;
+        let _ = reply.delete(&http).await;
+    });
+    Ok(())
+}
+
 fn is_image_attachment(a: &serenity::Attachment) -> bool {
     a.content_type
         .as_ref()
@@ -0,0 +1,671 @@
+//! 로컬 HTTP 제어 API (스트림덱, 간단한 웹 페이지용)
+//!
+//! 모든 요청은 `Authorization: Bearer <토큰>` 또는 `?token=<토큰>`(EventSource용)으로
+//! 인증합니다. 재생 제어는 슬래시 커맨드와 같은 `music::queue`/`music::player` 함수를 씁니다.
+//!
+//! | 메서드 | 경로 | 설명 |
+//! |--------|------|------|
+//! | GET | `/guilds` | 큐가 있는 서버 목록 |
+//! | GET | `/guilds/{id}` | 현재 곡, 대기열, 볼륨, 일시정지 여부 |
+//! | GET | `/guilds/{id}/events` | 곡 전환 알림 (server-sent events) |
+//! | POST | `/guilds/{id}/play` | 재개 (멈춰 있으면 대기열 첫 곡 재생) |
+//! | POST | `/guilds/{id}/pause` | 일시정지 |
+//! | POST | `/guilds/{id}/skip` | 다음 곡 |
+//! | POST | `/guilds/{id}/volume` | `{"level": "70"}` 또는 `{"level": "+10"}` |
+//! | POST | `/guilds/{id}/enqueue` | `{"query": "URL 또는 검색어", "requester": "이름"}` |
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyper::body::{Bytes, HttpBody};
+use hyper::header::{AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde::{Deserialize, Serialize};
+use serenity::model::id::GuildId;
+use songbird::tracks::PlayMode;
+use songbird::Songbird;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info};
+
+use crate::music::feed::TrackChange;
+use crate::music::settings::GuildSettings;
+use crate::music::{player, queue, radio, LoopMode, QueueManager, Song, VolumeChange};
+
+/// 요청 본문 최대 크기
+const MAX_BODY: u64 = 16 * 1024;
+/// 이벤트 스트림이 끊기지 않도록 보내는 주석 간격
+const KEEPALIVE: Duration = Duration::from_secs(15);
+
+pub struct ApiState {
+    pub queue_manager: QueueManager,
+    pub services: player::Services,
+    pub songbird: Arc<Songbird>,
+    pub guild_settings: Option<Arc<GuildSettings>>,
+    pub token: String,
+}
+
+/// `addr`에서 API 서버를 실행합니다 (종료되지 않음)
+pub async fn serve(addr: SocketAddr, state: Arc<ApiState>) -> Result<(), hyper::Error> {
+    let make_service = make_service_fn(move |_| {
+        let state = state.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                let state = state.clone();
+                async move { Ok::<_, Infallible>(handle(&state, req).await) }
+            }))
+        }
+    });
+
+    let server = Server::try_bind(&addr)?.serve(make_service);
+    info!("HTTP 제어 API 시작: http://{addr}");
+    server.await
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Action {
+    Play,
+    Pause,
+    Skip,
+    Volume,
+    Enqueue,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Route {
+    Guilds,
+    State(GuildId),
+    Events(GuildId),
+    Action(GuildId, Action),
+}
+
+impl Route {
+    fn parse(method: &Method, path: &str) -> Option<Self> {
+        let mut parts = path.trim_matches('/').split('/');
+        if parts.next()? != "guilds" {
+            return None;
+        }
+        let Some(id) = parts.next() else {
+            return (method == Method::GET).then_some(Self::Guilds);
+        };
+        let guild_id = id.parse().ok().filter(|&id| id != 0).map(GuildId::new)?;
+
+        let route = match (method, parts.next()) {
+            (&Method::GET, None) => Self::State(guild_id),
+            (&Method::GET, Some("events")) => Self::Events(guild_id),
+            (&Method::POST, Some(action)) => {
+                let action = match action {
+                    "play" => Action::Play,
+                    "pause" => Action::Pause,
+                    "skip" => Action::Skip,
+                    "volume" => Action::Volume,
+                    "enqueue" => Action::Enqueue,
+                    _ => return None,
+                };
+                Self::Action(guild_id, action)
+            }
+            _ => return None,
+        };
+        parts.next().is_none().then_some(route)
+    }
+}
+
+/// 길이가 같으면 끝까지 비교해 토큰 추측에 응답 시간이 단서가 되지 않게 합니다
+fn token_matches(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn authorized(req: &Request<Body>, token: &str) -> bool {
+    let header = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    // 브라우저 EventSource는 헤더를 붙일 수 없으므로 쿼리도 허용
+    let query = req
+        .uri()
+        .query()
+        .and_then(|q| q.split('&').find_map(|pair| pair.strip_prefix("token=")));
+    header
+        .into_iter()
+        .chain(query)
+        .any(|given| token_matches(given.trim(), token))
+}
+
+fn json<T: Serialize>(status: StatusCode, value: &T) -> Response<Body> {
+    let body = serde_json::to_vec(value).unwrap_or_default();
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json; charset=utf-8")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+fn error(status: StatusCode, message: &str) -> Response<Body> {
+    json(status, &serde_json::json!({ "error": message }))
+}
+
+/// 요청 하나를 처리합니다
+pub async fn handle(state: &ApiState, req: Request<Body>) -> Response<Body> {
+    if !authorized(&req, &state.token) {
+        return error(StatusCode::UNAUTHORIZED, "인증 토큰이 올바르지 않습니다.");
+    }
+    let Some(route) = Route::parse(req.method(), req.uri().path()) else {
+        return error(StatusCode::NOT_FOUND, "없는 경로입니다.");
+    };
+
+    match route {
+        Route::Guilds => guilds(state).await,
+        Route::State(guild_id) => json(StatusCode::OK, &guild_state(state, guild_id).await),
+        Route::Events(guild_id) => events(state, guild_id),
+        Route::Action(guild_id, action) => {
+            let body = match read_body(req).await {
+                Ok(body) => body,
+                Err((status, message)) => return error(status, &message),
+            };
+            match action {
+                Action::Play => play(state, guild_id).await,
+                Action::Pause => pause(state, guild_id).await,
+                Action::Skip => skip(state, guild_id).await,
+                Action::Volume => volume(state, guild_id, &body).await,
+                Action::Enqueue => enqueue(state, guild_id, &body).await,
+            }
+        }
+    }
+}
+
+/// 요청을 거절할 때의 상태 코드와 메시지
+type Rejection = (StatusCode, String);
+
+async fn read_body(req: Request<Body>) -> Result<Bytes, Rejection> {
+    let too_large = || {
+        (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "요청 본문이 너무 큽니다.".to_string(),
+        )
+    };
+    if req.body().size_hint().lower() > MAX_BODY {
+        return Err(too_large());
+    }
+    match hyper::body::to_bytes(req.into_body()).await {
+        Ok(body) if body.len() as u64 > MAX_BODY => Err(too_large()),
+        Ok(body) => Ok(body),
+        Err(e) => Err((StatusCode::BAD_REQUEST, format!("본문 읽기 실패: {e}"))),
+    }
+}
+
+fn parse_body<'a, T: Deserialize<'a>>(body: &'a [u8]) -> Result<T, Rejection> {
+    serde_json::from_slice(body).map_err(|e| (StatusCode::BAD_REQUEST, format!("잘못된 JSON: {e}")))
+}
+
+#[derive(Serialize)]
+struct GuildSummary {
+    guild_id: GuildId,
+    current: Option<Song>,
+    queued: usize,
+}
+
+async fn guilds(state: &ApiState) -> Response<Body> {
+    let mut guilds = Vec::new();
+    for guild_id in state.queue_manager.guild_ids() {
+        let (current, songs) = queue::get_queue_list(&state.queue_manager, guild_id).await;
+        guilds.push(GuildSummary {
+            guild_id,
+            current,
+            queued: songs.len(),
+        });
+    }
+    json(StatusCode::OK, &guilds)
+}
+
+#[derive(Serialize)]
+struct GuildState {
+    guild_id: GuildId,
+    /// 봇이 음성 채널에 있는지
+    connected: bool,
+    paused: bool,
+    /// 0~100
+    volume: u32,
+    loop_mode: &'static str,
+    current: Option<Song>,
+    queue: Vec<Song>,
+}
+
+async fn guild_state(state: &ApiState, guild_id: GuildId) -> GuildState {
+    let qm = &state.queue_manager;
+    let (current, queue) = queue::get_queue_list(qm, guild_id).await;
+    let paused = match queue::get_track_handle(qm, guild_id).await {
+        Some(handle) => handle
+            .get_info()
+            .await
+            .is_ok_and(|info| info.playing == PlayMode::Pause),
+        None => false,
+    };
+    let loop_mode = match queue::get_loop_mode(qm, guild_id).await {
+        LoopMode::Off => "off",
+        LoopMode::Song => "song",
+        LoopMode::Queue => "queue",
+    };
+
+    GuildState {
+        guild_id,
+        connected: state.songbird.get(guild_id).is_some(),
+        paused,
+        volume: (queue::get_volume(qm, guild_id).await * 100.0).round() as u32,
+        loop_mode,
+        current,
+        queue,
+    }
+}
+
+/// 현재 곡을 먼저 보낸 뒤, 이 서버의 곡이 바뀔 때마다 `track` 이벤트를 보냅니다
+fn events(state: &ApiState, guild_id: GuildId) -> Response<Body> {
+    // 현재 곡을 읽는 사이의 전환을 놓치지 않도록 먼저 구독
+    let mut changes = state.queue_manager.feed().subscribe();
+    let queue_manager = state.queue_manager.clone();
+    let (mut sender, body) = Body::channel();
+
+    tokio::spawn(async move {
+        let song = queue::get_current(&queue_manager, guild_id).await;
+        if sender
+            .send_data(sse_event(&TrackChange { guild_id, song }))
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        let mut keepalive = tokio::time::interval(KEEPALIVE);
+        keepalive.tick().await;
+        loop {
+            let chunk = tokio::select! {
+                change = changes.recv() => match change {
+                    Ok(change) if change.guild_id == guild_id => sse_event(&change),
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                },
+                _ = keepalive.tick() => Bytes::from_static(b": keepalive\n\n"),
+            };
+            // 클라이언트가 연결을 끊으면 종료
+            if sender.send_data(chunk).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    Response::builder()
+        .header(CONTENT_TYPE, "text/event-stream")
+        .header(CACHE_CONTROL, "no-cache")
+        .body(body)
+        .unwrap()
+}
+
+fn sse_event(change: &TrackChange) -> Bytes {
+    let data = serde_json::to_string(change).unwrap_or_default();
+    Bytes::from(format!("event: track\ndata: {data}\n\n"))
+}
+
+async fn play(state: &ApiState, guild_id: GuildId) -> Response<Body> {
+    let qm = &state.queue_manager;
+    if let Err(msg) = state.services.quiz_games.check_music(guild_id) {
+        return error(StatusCode::CONFLICT, msg);
+    }
+    if let Some(handle) = queue::get_track_handle(qm, guild_id).await {
+        let _ = handle.play();
+        return json(StatusCode::OK, &guild_state(state, guild_id).await);
+    }
+
+    let Some(call) = state.songbird.get(guild_id) else {
+        return error(StatusCode::CONFLICT, "봇이 음성 채널에 없습니다.");
+    };
+    match player::start_playback(
+        guild_id,
+        qm,
+        &state.services,
+        state.guild_settings.as_deref(),
+        &call,
+    )
+    .await
+    {
+        Ok(Some(_)) => json(StatusCode::OK, &guild_state(state, guild_id).await),
+        Ok(None) => error(StatusCode::CONFLICT, "대기열이 비어 있습니다."),
+        Err(e) => error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("재생 실패: {e}"),
+        ),
+    }
+}
+
+async fn pause(state: &ApiState, guild_id: GuildId) -> Response<Body> {
+    match queue::get_track_handle(&state.queue_manager, guild_id).await {
+        Some(handle) => {
+            let _ = handle.pause();
+            json(StatusCode::OK, &guild_state(state, guild_id).await)
+        }
+        None => error(StatusCode::CONFLICT, "재생 중인 곡이 없습니다."),
+    }
+}
+
+async fn skip(state: &ApiState, guild_id: GuildId) -> Response<Body> {
+    let qm = &state.queue_manager;
+    let (Some(call), Some(_)) = (
+        state.songbird.get(guild_id),
+        queue::get_current(qm, guild_id).await,
+    ) else {
+        return error(StatusCode::CONFLICT, "재생 중인 곡이 없습니다.");
+    };
+    if let Err(msg) = radio::check_skippable(qm, guild_id).await {
+        return error(StatusCode::CONFLICT, msg);
+    }
+
+    match player::play_next(guild_id, qm, &state.services, &call, true).await {
+        Ok(()) => json(StatusCode::OK, &guild_state(state, guild_id).await),
+        Err(e) => error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("스킵 실패: {e}"),
+        ),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VolumeLevel {
+    Number(u32),
+    Text(String),
+}
+
+#[derive(Deserialize)]
+struct VolumeRequest {
+    level: VolumeLevel,
+}
+
+async fn volume(state: &ApiState, guild_id: GuildId, body: &[u8]) -> Response<Body> {
+    let request: VolumeRequest = match parse_body(body) {
+        Ok(request) => request,
+        Err((status, message)) => return error(status, &message),
+    };
+    let change = match request.level {
+        VolumeLevel::Number(level) => Some(VolumeChange::Set(level)),
+        VolumeLevel::Text(text) => VolumeChange::parse(&text),
+    };
+
+    let current = (queue::get_volume(&state.queue_manager, guild_id).await * 100.0).round() as u32;
+    let Some(level) = change.and_then(|change| change.apply(current)) else {
+        return error(
+            StatusCode::BAD_REQUEST,
+            "볼륨은 0~100 사이 또는 `+10`, `-10`처럼 입력해주세요.",
+        );
+    };
+
+    // /volume과 같이 재생 중인 트랙에 바로 적용하고 서버별로 저장
+    let volume = level as f32 / 100.0;
+    queue::set_volume(&state.queue_manager, guild_id, volume).await;
+    if let Some(settings) = &state.guild_settings {
+        settings.set_volume(guild_id, volume);
+    }
+    json(StatusCode::OK, &guild_state(state, guild_id).await)
+}
+
+#[derive(Deserialize)]
+struct EnqueueRequest {
+    query: String,
+    requester: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Enqueued {
+    position: usize,
+    /// 중복 정책이 경고일 때 같은 곡의 위치 (0이면 재생 중)
+    duplicate_of: Option<usize>,
+    song: Song,
+}
+
+async fn enqueue(state: &ApiState, guild_id: GuildId, body: &[u8]) -> Response<Body> {
+    let request: EnqueueRequest = match parse_body(body) {
+        Ok(request) => request,
+        Err((status, message)) => return error(status, &message),
+    };
+    if request.query.trim().is_empty() {
+        return error(StatusCode::BAD_REQUEST, "검색어를 입력해주세요.");
+    }
+    // 봇이 없는 서버에는 대기열을 만들지 않음
+    let Some(call) = state.songbird.get(guild_id) else {
+        return error(StatusCode::CONFLICT, "봇이 음성 채널에 없습니다.");
+    };
+
+    let mut song = match state.services.ytdlp.song_info(request.query.trim()).await {
+        Ok(song) => song,
+        Err(e) => {
+            return error(
+                StatusCode::NOT_FOUND,
+                &format!("노래를 찾을 수 없습니다: {e}"),
+            )
+        }
+    };
+    song.requester = request.requester.unwrap_or_else(|| "HTTP API".to_string());
+
+    let qm = &state.queue_manager;
+    let added = match queue::add_song(qm, guild_id, song.clone()).await {
+        Ok(added) => added,
+        Err(e) => return error(StatusCode::UNPROCESSABLE_ENTITY, &e.to_string()),
+    };
+
+    // 재생 중인 곡이 없으면 바로 재생
+    if queue::get_current(qm, guild_id).await.is_none() {
+        if let Err(e) = player::start_playback(
+            guild_id,
+            qm,
+            &state.services,
+            state.guild_settings.as_deref(),
+            &call,
+        )
+        .await
+        {
+            error!("HTTP API 재생 시작 실패: {e}");
+        }
+    }
+
+    json(
+        StatusCode::CREATED,
+        &Enqueued {
+            position: added.position,
+            duplicate_of: added.duplicate_of,
+            song,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::music;
+
+    fn state(token: &str) -> ApiState {
+        ApiState {
+            queue_manager: music::new_queue_manager(),
+            services: player::Services {
+                http_client: reqwest::Client::new(),
+                ytdlp: Arc::new(music::source::YtDlp::new(Default::default())),
+                stats: None,
+                quiz_games: Default::default(),
+            },
+            songbird: Songbird::serenity(),
+            guild_settings: None,
+            token: token.to_string(),
+        }
+    }
+
+    fn request(method: Method, uri: &str, token: Option<&str>, body: &str) -> Request<Body> {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    async fn body_json(response: Response<Body>) -> serde_json::Value {
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[test]
+    fn test_route_parse() {
+        let gid = GuildId::new(42);
+        assert_eq!(Route::parse(&Method::GET, "/guilds"), Some(Route::Guilds));
+        assert_eq!(
+            Route::parse(&Method::GET, "/guilds/42/"),
+            Some(Route::State(gid))
+        );
+        assert_eq!(
+            Route::parse(&Method::GET, "/guilds/42/events"),
+            Some(Route::Events(gid))
+        );
+        assert_eq!(
+            Route::parse(&Method::POST, "/guilds/42/skip"),
+            Some(Route::Action(gid, Action::Skip))
+        );
+        assert_eq!(Route::parse(&Method::GET, "/guilds/42/skip"), None);
+        assert_eq!(Route::parse(&Method::POST, "/guilds/42/explode"), None);
+        assert_eq!(Route::parse(&Method::GET, "/guilds/abc"), None);
+        assert_eq!(Route::parse(&Method::GET, "/guilds/0"), None);
+        assert_eq!(Route::parse(&Method::GET, "/guilds/42/events/x"), None);
+        assert_eq!(Route::parse(&Method::GET, "/"), None);
+    }
+
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches("secret", "secret"));
+        assert!(!token_matches("secreT", "secret"));
+        assert!(!token_matches("secret2", "secret"));
+        assert!(!token_matches("", "secret"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_or_wrong_token() {
+        let state = state("secret");
+        let response = handle(&state, request(Method::GET, "/guilds", None, "")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = handle(&state, request(Method::GET, "/guilds", Some("nope"), "")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = handle(
+            &state,
+            request(Method::GET, "/guilds?token=secret", None, ""),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_guild_state_and_volume() {
+        let state = state("secret");
+        let gid = GuildId::new(5);
+        let song = Song {
+            title: "Queued".to_string(),
+            url: "https://youtube.com/watch?v=queued".to_string(),
+            ..Default::default()
+        };
+        queue::add_song(&state.queue_manager, gid, song)
+            .await
+            .unwrap();
+
+        let response = handle(
+            &state,
+            request(Method::GET, "/guilds/5", Some("secret"), ""),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_json(response).await;
+        assert_eq!(body["queue"][0]["title"], "Queued");
+        assert_eq!(body["connected"], false);
+        assert_eq!(body["volume"], 50);
+
+        let response = handle(
+            &state,
+            request(
+                Method::POST,
+                "/guilds/5/volume",
+                Some("secret"),
+                r#"{"level":"+20"}"#,
+            ),
+        )
+        .await;
+        assert_eq!(body_json(response).await["volume"], 70);
+
+        let response = handle(
+            &state,
+            request(
+                Method::POST,
+                "/guilds/5/volume",
+                Some("secret"),
+                r#"{"level":150}"#,
+            ),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_controls_need_playback() {
+        let state = state("secret");
+        for action in ["pause", "skip", "play"] {
+            let uri = format!("/guilds/5/{action}");
+            let response = handle(&state, request(Method::POST, &uri, Some("secret"), "")).await;
+            assert_eq!(response.status(), StatusCode::CONFLICT, "{action}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_needs_voice_connection() {
+        let state = state("secret");
+        let response = handle(
+            &state,
+            request(
+                Method::POST,
+                "/guilds/7/enqueue",
+                Some("secret"),
+                r#"{"query":"anything"}"#,
+            ),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert!(state.queue_manager.get(GuildId::new(7)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_events_stream_track_changes() {
+        let state = state("secret");
+        let gid = GuildId::new(6);
+        let response = handle(
+            &state,
+            request(Method::GET, "/guilds/6/events", Some("secret"), ""),
+        )
+        .await;
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/event-stream");
+        let mut body = response.into_body();
+
+        // 접속 직후에는 현재 상태(재생 중인 곡 없음)
+        let first = body.data().await.unwrap().unwrap();
+        assert!(first.starts_with(b"event: track\ndata: "));
+        assert!(String::from_utf8_lossy(&first).contains(r#""song":null"#));
+
+        // 다른 서버의 전환은 보내지 않음
+        let feed = state.queue_manager.feed();
+        feed.publish(GuildId::new(99), None);
+        let song = Song {
+            title: "Live Change".to_string(),
+            ..Default::default()
+        };
+        feed.publish(gid, Some(song));
+        let next = body.data().await.unwrap().unwrap();
+        assert!(String::from_utf8_lossy(&next).contains("Live Change"));
+    }
+}
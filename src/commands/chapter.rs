@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use poise::CreateReply;
+
+use crate::music::chapters::{self, ChapterTarget};
+use crate::music::queue;
+use crate::utils::embed;
+use crate::{Context, Error};
+
+async fn chapter_impl(ctx: Context<'_>, target: String) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let target = match ChapterTarget::parse(&target) {
+        Some(t) => t,
+        None => {
+            ctx.send(CreateReply::default().embed(embed::error(
+                "`next`, `prev` 또는 챕터 번호를 입력해주세요.",
+            )))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let current = queue::get_current(&ctx.data().queue_manager, guild_id).await;
+    let handle = queue::get_track_handle(&ctx.data().queue_manager, guild_id).await;
+
+    let (song, handle) = match (current, handle) {
+        (Some(song), Some(handle)) => (song, handle),
+        _ => {
+            ctx.send(CreateReply::default().embed(embed::error("재생 중인 곡이 없습니다.")))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    if song.is_live {
+        ctx.send(
+            CreateReply::default().embed(embed::error("라이브 스트림에서는 탐색할 수 없습니다.")),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if song.chapters.is_empty() {
+        ctx.send(CreateReply::default().embed(embed::error("현재 곡에 챕터 정보가 없습니다.")))
+            .await?;
+        return Ok(());
+    }
+
+    let position = handle
+        .get_info()
+        .await
+        .map(|info| info.position.as_secs_f64())
+        .unwrap_or(0.0);
+
+    let index = match chapters::resolve(&song.chapters, position, target) {
+        Some(i) => i,
+        None => {
+            ctx.send(CreateReply::default().embed(embed::error("이동할 챕터가 없습니다.")))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let chapter = &song.chapters[index];
+    if let Err(e) = handle
+        .seek_async(Duration::from_secs_f64(chapter.start_secs))
+        .await
+    {
+        ctx.send(CreateReply::default().embed(embed::error(&format!("챕터 이동 실패: {e}"))))
+            .await?;
+        return Ok(());
+    }
+
+    ctx.say(format!(
+        "⏩ 챕터 {}. **{}** (`{}`)",
+        index + 1,
+        chapter.title,
+        chapters::format_timestamp(chapter.start_secs)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// 현재 곡의 챕터로 이동합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn chapter(
+    ctx: Context<'_>,
+    #[description = "next / prev / 챕터 번호"] target: String,
+) -> Result<(), Error> {
+    chapter_impl(ctx, target).await
+}
+
+/// 현재 곡의 챕터로 이동합니다 (/chapter 단축)
+#[poise::command(slash_command, guild_only)]
+pub async fn ch(
+    ctx: Context<'_>,
+    #[description = "next / prev / 챕터 번호"] target: String,
+) -> Result<(), Error> {
+    chapter_impl(ctx, target).await
+}
@@ -0,0 +1,134 @@
+use poise::serenity_prelude::GuildId;
+use poise::CreateReply;
+
+use crate::music::eq::{self, EqBands};
+use crate::music::queue;
+use crate::utils::{components, embed};
+use crate::{Context, Error};
+
+/// 재생 중인 곡에 바로 적용하고, 다음 재생을 위해 서버별로 저장
+async fn apply(ctx: Context<'_>, guild_id: GuildId, bands: EqBands) -> Result<(), Error> {
+    queue::set_equalizer(&ctx.data().queue_manager, guild_id, bands).await;
+    if let Some(settings) = &ctx.data().guild_settings {
+        settings.set_equalizer(guild_id, &bands);
+    }
+    ctx.send(
+        CreateReply::default()
+            .embed(embed::equalizer(&bands))
+            .components(vec![components::equalizer_row(&bands)]),
+    )
+    .await?;
+    Ok(())
+}
+
+/// 저장된 설정 (재생 전이라 아직 복원되지 않았을 수 있으므로 저장소를 먼저 봅니다)
+async fn current_bands(ctx: Context<'_>, guild_id: GuildId) -> EqBands {
+    let data = ctx.data();
+    match data
+        .guild_settings
+        .as_ref()
+        .and_then(|settings| settings.equalizer(guild_id))
+    {
+        Some(bands) => bands,
+        None => queue::get_equalizer(&data.queue_manager, guild_id).await,
+    }
+}
+
+/// 이퀄라이저를 조절합니다
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("show", "set", "preset", "reset"),
+    subcommand_required
+)]
+pub async fn equalizer(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 이퀄라이저를 조절합니다 (/equalizer 단축)
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("show", "set", "preset", "reset"),
+    subcommand_required
+)]
+pub async fn eq(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 현재 이퀄라이저 설정을 표시합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn show(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let bands = current_bands(ctx, guild_id).await;
+    ctx.send(
+        CreateReply::default()
+            .embed(embed::equalizer(&bands))
+            .components(vec![components::equalizer_row(&bands)]),
+    )
+    .await?;
+    Ok(())
+}
+
+/// 한 밴드의 게인을 설정합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn set(
+    ctx: Context<'_>,
+    #[description = "밴드 (31, 62, 125, 250, 500, 1k, 2k, 4k, 8k, 16k)"] band: String,
+    #[description = "게인 (dB, -12 ~ 12)"] gain: f32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    if !super::ensure_same_channel(ctx, guild_id).await? {
+        return Ok(());
+    }
+
+    let Some(index) = EqBands::parse_band(&band) else {
+        ctx.send(CreateReply::default().embed(embed::error(&format!(
+            "밴드는 {} 중 하나로 입력해주세요.",
+            eq::BAND_LABELS.join(", ")
+        ))))
+        .await?;
+        return Ok(());
+    };
+    if !gain.is_finite() {
+        ctx.send(CreateReply::default().embed(embed::error("게인은 숫자로 입력해주세요.")))
+            .await?;
+        return Ok(());
+    }
+
+    let mut bands = current_bands(ctx, guild_id).await;
+    bands.set_gain(index, gain);
+    apply(ctx, guild_id, bands).await
+}
+
+/// 프리셋을 적용합니다 (flat, rock, pop)
+#[poise::command(slash_command, guild_only)]
+pub async fn preset(
+    ctx: Context<'_>,
+    #[description = "프리셋 (flat, rock, pop)"] name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    if !super::ensure_same_channel(ctx, guild_id).await? {
+        return Ok(());
+    }
+
+    let Some(bands) = EqBands::preset(&name) else {
+        ctx.send(CreateReply::default().embed(embed::error(&format!(
+            "프리셋은 {} 중 하나로 입력해주세요.",
+            eq::PRESETS.join(", ")
+        ))))
+        .await?;
+        return Ok(());
+    };
+    apply(ctx, guild_id, bands).await
+}
+
+/// 이퀄라이저를 끕니다 (모든 밴드 0dB)
+#[poise::command(slash_command, guild_only)]
+pub async fn reset(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    if !super::ensure_same_channel(ctx, guild_id).await? {
+        return Ok(());
+    }
+    apply(ctx, guild_id, EqBands::default()).await
+}
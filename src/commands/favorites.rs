@@ -0,0 +1,212 @@
+use poise::CreateReply;
+use serenity::builder::CreateEmbed;
+
+use crate::music::queue;
+use crate::utils::components::{self, ControllerState};
+use crate::utils::{embed, voice};
+use crate::{Context, Error};
+
+async fn list_impl(ctx: Context<'_>, page: Option<usize>) -> Result<(), Error> {
+    let Some(favorites) = &ctx.data().favorites else {
+        ctx.send(
+            CreateReply::default()
+                .embed(embed::error("즐겨찾기를 사용할 수 없습니다."))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let songs = favorites.list(ctx.author().id);
+    ctx.send(
+        CreateReply::default()
+            .embed(embed::favorites_list(&songs, page.unwrap_or(1)))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+async fn play_impl(ctx: Context<'_>, number: Option<usize>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let Some(favorites) = &ctx.data().favorites else {
+        ctx.send(CreateReply::default().embed(embed::error("즐겨찾기를 사용할 수 없습니다.")))
+            .await?;
+        return Ok(());
+    };
+
+    let all = favorites.list(ctx.author().id);
+    let songs = match number {
+        None if all.is_empty() => {
+            ctx.send(CreateReply::default().embed(embed::error("즐겨찾기가 비어있습니다.")))
+                .await?;
+            return Ok(());
+        }
+        None => all,
+        Some(n) => match n.checked_sub(1).and_then(|i| all.get(i)) {
+            Some(song) => vec![song.clone()],
+            None => {
+                ctx.send(
+                    CreateReply::default()
+                        .embed(embed::error(&format!("#{n} 즐겨찾기가 없습니다."))),
+                )
+                .await?;
+                return Ok(());
+            }
+        },
+    };
+
+    let Some(channel_id) = voice::user_channel(ctx.serenity_context(), guild_id, ctx.author().id)
+    else {
+        ctx.send(CreateReply::default().embed(embed::error("음성 채널에 먼저 접속해주세요!")))
+            .await?;
+        return Ok(());
+    };
+
+    ctx.defer().await?;
+
+    let call = match voice::join(
+        ctx.serenity_context(),
+        &ctx.data().queue_manager,
+        guild_id,
+        channel_id,
+    )
+    .await
+    {
+        Ok(call) => call,
+        Err(e) => {
+            ctx.send(
+                CreateReply::default().embed(embed::error(&format!("음성 채널 접속 실패: {e}"))),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let is_first = queue::get_current(&ctx.data().queue_manager, guild_id)
+        .await
+        .is_none();
+    let mut count = 0;
+    let mut rejected = 0;
+    let mut first_title = None;
+    for mut song in songs {
+        song.requester = ctx.author().name.clone();
+        song.requester_id = Some(ctx.author().id);
+        let title = song.title.clone();
+        match queue::add_song(&ctx.data().queue_manager, guild_id, song).await {
+            Ok(_) => {
+                count += 1;
+                first_title.get_or_insert(title);
+            }
+            Err(_) => rejected += 1,
+        }
+    }
+
+    let Some(first_title) = first_title else {
+        ctx.send(CreateReply::default().embed(embed::error(&format!(
+            "서버의 곡 추가 정책에 걸려 즐겨찾기 {rejected}곡을 모두 추가하지 못했습니다."
+        ))))
+        .await?;
+        return Ok(());
+    };
+
+    if is_first {
+        super::play::start_playback(ctx, guild_id, &call).await?;
+    }
+
+    let description = if count == 1 {
+        format!("**{first_title}** 을(를) 대기열에 추가했습니다.")
+    } else {
+        format!("즐겨찾기 **{count}곡**을 대기열에 추가했습니다.")
+    };
+    let description = if rejected > 0 {
+        format!("{description}\n⚠️ 곡 추가 정책에 걸린 {rejected}곡은 건너뛰었습니다.")
+    } else {
+        description
+    };
+    let state = ControllerState::load(&ctx.data().queue_manager, guild_id).await;
+    let (upcoming, version) = queue::get_upcoming(&ctx.data().queue_manager, guild_id).await;
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::new()
+                    .title("❤️ 즐겨찾기 재생")
+                    .description(description)
+                    .color(0xED4245),
+            )
+            .components(components::music_components(&state, &upcoming, version)),
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn remove_impl(ctx: Context<'_>, number: usize) -> Result<(), Error> {
+    let removed = ctx
+        .data()
+        .favorites
+        .as_ref()
+        .and_then(|favorites| favorites.remove(ctx.author().id, number));
+
+    let reply = match removed {
+        Some(song) => CreateReply::default().content(format!(
+            "🗑️ **{}** 즐겨찾기에서 제거됨 (#{number})",
+            song.title
+        )),
+        None => {
+            CreateReply::default().embed(embed::error(&format!("#{number} 즐겨찾기가 없습니다.")))
+        }
+    };
+    ctx.send(reply.ephemeral(true)).await?;
+    Ok(())
+}
+
+/// 즐겨찾기한 곡을 관리합니다
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("list", "play", "remove"),
+    subcommand_required
+)]
+pub async fn favorites(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 즐겨찾기한 곡을 관리합니다 (/favorites 단축)
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("list", "play", "remove"),
+    subcommand_required
+)]
+pub async fn fav(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 내 즐겨찾기 목록을 표시합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn list(
+    ctx: Context<'_>,
+    #[description = "페이지 번호"] page: Option<usize>,
+) -> Result<(), Error> {
+    list_impl(ctx, page).await
+}
+
+/// 즐겨찾기를 대기열에 추가합니다 (번호를 생략하면 전체)
+#[poise::command(slash_command, guild_only)]
+pub async fn play(
+    ctx: Context<'_>,
+    #[description = "즐겨찾기 번호 (생략 시 전체)"] number: Option<usize>,
+) -> Result<(), Error> {
+    play_impl(ctx, number).await
+}
+
+/// 즐겨찾기에서 곡을 제거합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "제거할 즐겨찾기 번호"] number: usize,
+) -> Result<(), Error> {
+    remove_impl(ctx, number).await
+}
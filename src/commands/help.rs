@@ -5,17 +5,34 @@ use crate::{Context, Error};
 
 async fn help_impl(ctx: Context<'_>) -> Result<(), Error> {
     let music_cmds = "\
-`/play` (`/p`) — 음악 재생 또는 큐에 추가
+`/play` (`/p`) — 음악 재생 또는 큐에 추가 (.txt/.csv 첨부나 검색어 없이 실행하면 목록 한꺼번에 추가)
 `/skip` (`/s`) — 현재 곡 건너뛰기
+`/previous` (`/prev`) — 이전 곡 다시 재생
 `/stop` (`/st`) — 재생 중지 및 퇴장
+`/join` (`/j`) — 음성 채널 접속 (채널 지정 가능)
+`/leave` (`/dc`) — 대기열을 유지한 채 퇴장
+`/summon` (`/sm`) — 재생을 유지한 채 내 채널로 이동
+`/247` (`/stay`) — 24/7 모드 (자동 퇴장 끔, 재시작 시 재접속, 자동 재생)
+`/queuepolicy` (`/qp`) — 곡 추가 정책 (중복, 최대 길이, 라이브, 차단 목록)
 `/queue` (`/q`) — 재생 목록 표시
 `/pause` (`/pa`) — 일시정지
 `/resume` (`/r`) — 재개
 `/nowplaying` (`/np`) — 현재 재생 중인 곡
 `/loop` (`/l`) — 반복 모드 (off/song/queue)
-`/shuffle` (`/sh`) — 큐 셔플
+`/shuffle` (`/sh`) — 셔플 모드 켜기 (반복 시마다 다시 셔플)
+`/unshuffle` (`/ush`) — 셔플 끄기 및 원래 순서 복원
+`/undo` (`/u`) — 마지막 셔플/제거/이동/정지 되돌리기
 `/remove` (`/rm`) — 큐에서 곡 제거
-`/volume` (`/v`) — 볼륨 조절 (0-100)";
+`/myqueue` (`/myq`) — 내가 신청한 대기 곡과 위치
+`/volume` (`/v`) — 볼륨 조절 (0-100, +10/-10), 서버별 저장
+`/equalizer` (`/eq`) — 이퀄라이저 보기/밴드 설정/프리셋(flat, rock, pop)/초기화
+`/chapter` (`/ch`) — 챕터 이동 (next/prev/번호)
+`/sponsorskip` (`/sb`) — 스폰서/인트로 구간 자동 건너뛰기
+`/favorites` (`/fav`) — 즐겨찾기 목록/재생/삭제 (❤️ 버튼으로 저장)
+`/radio` (`/rd`) — 인터넷 라디오 재생/끄기, 서버별 방송국 목록
+`/lyrics` (`/ly`) — 가사 보기 (현재 곡 기본, 싱크 모드 지원)
+`/musicquiz` (`/mq`) — 노래 맞히기 퀴즈 (start/stop, 빨리 맞힐수록 고득점)
+`/stats music` — 인기 곡, 신청 순위, 재생 시간 통계 (7d/30d/all)";
 
     let tarkov_cmds = "\
 `/item` (`/아이템`) — 아이템 검색 (정보/가격 탭)
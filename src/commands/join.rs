@@ -0,0 +1,98 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+
+use crate::music::{player, queue};
+use crate::utils::{embed, voice};
+use crate::{Context, Error};
+
+async fn join_impl(ctx: Context<'_>, channel: Option<serenity::GuildChannel>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let target = channel
+        .map(|c| c.id)
+        .or_else(|| voice::user_channel(ctx.serenity_context(), guild_id, ctx.author().id));
+    let target = match target {
+        Some(id) => id,
+        None => {
+            ctx.send(CreateReply::default().embed(embed::error(
+                "음성 채널에 먼저 접속하거나 채널을 지정해주세요.",
+            )))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // 다른 채널에서 재생 중이면 다른 사람의 재생을 빼앗지 않도록 /summon을 안내
+    let current_channel = queue::get_voice_channel(&ctx.data().queue_manager, guild_id).await;
+    let is_playing = queue::get_current(&ctx.data().queue_manager, guild_id)
+        .await
+        .is_some();
+    if let Some(current) = current_channel.filter(|&c| c != target && is_playing) {
+        ctx.send(CreateReply::default().embed(embed::error(&format!(
+            "이미 <#{current}>에서 재생 중입니다. 옮기려면 `/summon`을 사용해주세요."
+        ))))
+        .await?;
+        return Ok(());
+    }
+
+    let call = match voice::join(
+        ctx.serenity_context(),
+        &ctx.data().queue_manager,
+        guild_id,
+        target,
+    )
+    .await
+    {
+        Ok(call) => call,
+        Err(e) => {
+            ctx.send(
+                CreateReply::default().embed(embed::error(&format!("음성 채널 접속 실패: {e}"))),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // /leave로 남겨둔 대기열이 있으면 이어서 재생
+    let resume = !is_playing && !queue::is_empty(&ctx.data().queue_manager, guild_id).await;
+    if resume {
+        player::play_next(
+            guild_id,
+            &ctx.data().queue_manager,
+            &ctx.data().services,
+            &call,
+            true,
+        )
+        .await?;
+    }
+
+    let msg = match queue::get_current(&ctx.data().queue_manager, guild_id).await {
+        Some(song) if resume => format!("🔊 <#{target}> 접속 — **{}** 이어서 재생", song.title),
+        _ => format!("🔊 <#{target}>에 접속했습니다."),
+    };
+    ctx.say(msg).await?;
+
+    Ok(())
+}
+
+/// 음성 채널에 접속합니다 (채널을 지정하지 않으면 내가 있는 채널)
+#[poise::command(slash_command, guild_only)]
+pub async fn join(
+    ctx: Context<'_>,
+    #[description = "접속할 음성 채널"]
+    #[channel_types("Voice", "Stage")]
+    channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    join_impl(ctx, channel).await
+}
+
+/// 음성 채널에 접속합니다 (/join 단축)
+#[poise::command(slash_command, guild_only)]
+pub async fn j(
+    ctx: Context<'_>,
+    #[description = "접속할 음성 채널"]
+    #[channel_types("Voice", "Stage")]
+    channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    join_impl(ctx, channel).await
+}
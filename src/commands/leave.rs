@@ -0,0 +1,52 @@
+use poise::CreateReply;
+
+use crate::music::{player, queue};
+use crate::utils::embed;
+use crate::{Context, Error};
+
+async fn leave_impl(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    if !super::ensure_same_channel(ctx, guild_id).await? {
+        return Ok(());
+    }
+
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird 미등록");
+
+    if manager.get(guild_id).is_none() {
+        ctx.send(CreateReply::default().embed(embed::error("봇이 음성 채널에 없습니다.")))
+            .await?;
+        return Ok(());
+    }
+
+    // 채널 정보를 먼저 지워야 퇴장하면서 끝나는 트랙이 다음 곡으로 넘어가지 않음
+    player::record_skipped(guild_id, &ctx.data().queue_manager, &ctx.data().services).await;
+    queue::detach(&ctx.data().queue_manager, guild_id).await;
+    let _ = manager.remove(guild_id).await;
+
+    let (_, songs) = queue::get_queue_list(&ctx.data().queue_manager, guild_id).await;
+    let msg = if songs.is_empty() {
+        "👋 음성 채널에서 나갑니다.".to_string()
+    } else {
+        format!(
+            "👋 음성 채널에서 나갑니다. 대기열 {}곡은 유지되며 `/join`이나 `/play`로 이어서 재생할 수 있습니다.",
+            songs.len()
+        )
+    };
+    ctx.say(msg).await?;
+
+    Ok(())
+}
+
+/// 대기열을 유지한 채 음성 채널에서 나갑니다
+#[poise::command(slash_command, guild_only)]
+pub async fn leave(ctx: Context<'_>) -> Result<(), Error> {
+    leave_impl(ctx).await
+}
+
+/// 대기열을 유지한 채 음성 채널에서 나갑니다 (/leave 단축)
+#[poise::command(slash_command, guild_only)]
+pub async fn dc(ctx: Context<'_>) -> Result<(), Error> {
+    leave_impl(ctx).await
+}
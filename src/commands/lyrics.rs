@@ -0,0 +1,234 @@
+use std::time::{Duration, Instant};
+
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+
+use crate::music::lyrics::{self, Lyrics};
+use crate::music::{queue, Song};
+use crate::utils::{components, embed};
+use crate::{Context, Error};
+
+/// 버튼 입력이 없을 때 화면을 유지하는 시간
+const IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+/// 싱크 모드에서 현재 줄을 다시 확인하는 주기
+const SYNC_INTERVAL_SECS: u64 = 2;
+
+#[derive(Clone, Copy, PartialEq)]
+enum View {
+    Pages(usize),
+    Synced,
+}
+
+/// 싱크 모드를 켜고 끄는 버튼
+///
+/// Custom ID: `lyrics_sync`, `lyrics_pages`
+fn mode_row(view: View) -> serenity::CreateActionRow {
+    let button = match view {
+        View::Pages(_) => serenity::CreateButton::new("lyrics_sync")
+            .label("싱크 모드")
+            .emoji('🎤')
+            .style(serenity::ButtonStyle::Success),
+        View::Synced => serenity::CreateButton::new("lyrics_pages")
+            .label("전체 가사")
+            .emoji('📝')
+            .style(serenity::ButtonStyle::Secondary),
+    };
+    serenity::CreateActionRow::Buttons(vec![button])
+}
+
+/// 지금 재생 중인 트랙이 이 곡일 때의 재생 위치
+async fn playing_position(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    song: &Song,
+) -> Option<f64> {
+    let current = queue::get_current(&ctx.data().queue_manager, guild_id).await?;
+    if current.url != song.url {
+        return None;
+    }
+    let handle = queue::get_track_handle(&ctx.data().queue_manager, guild_id).await?;
+    let info = handle.get_info().await.ok()?;
+    Some(info.position.as_secs_f64())
+}
+
+async fn render(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    song: &Song,
+    lyrics: &Lyrics,
+    pages: &[String],
+    view: View,
+) -> (serenity::CreateEmbed, Vec<serenity::CreateActionRow>) {
+    match view {
+        View::Pages(page) => {
+            let e = embed::lyrics_page(song, lyrics, &pages[page]);
+            let mut rows = Vec::new();
+            if pages.len() > 1 {
+                rows.push(components::pagination_row("lyrics", page, pages.len()));
+            }
+            // 지금 재생 중인 곡일 때만 싱크 모드 제공
+            if lyrics.is_synced() && playing_position(ctx, guild_id, song).await.is_some() {
+                rows.push(mode_row(view));
+            }
+            (e, rows)
+        }
+        View::Synced => {
+            let position = playing_position(ctx, guild_id, song).await.unwrap_or(0.0);
+            (
+                embed::lyrics_synced(song, lyrics, position),
+                vec![mode_row(view)],
+            )
+        }
+    }
+}
+
+async fn lyrics_impl(ctx: Context<'_>, query: Option<String>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let song = match query {
+        Some(query) => Song {
+            title: query,
+            ..Default::default()
+        },
+        None => match queue::get_current(&ctx.data().queue_manager, guild_id).await {
+            Some(song) => song,
+            None => {
+                ctx.send(CreateReply::default().embed(embed::error(
+                    "재생 중인 곡이 없습니다. 검색어를 입력해주세요.",
+                )))
+                .await?;
+                return Ok(());
+            }
+        },
+    };
+
+    ctx.defer().await?;
+
+    let lyrics = match ctx.data().lyrics_provider.lyrics(&song).await {
+        Ok(Some(lyrics)) if !lyrics.plain.trim().is_empty() => lyrics,
+        Ok(_) => {
+            ctx.send(CreateReply::default().embed(embed::error(&format!(
+                "가사를 찾을 수 없습니다: {}",
+                song.title
+            ))))
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            ctx.send(CreateReply::default().embed(embed::error(&format!("가사 조회 실패: {e}"))))
+                .await?;
+            return Ok(());
+        }
+    };
+    let pages = lyrics::paginate(&lyrics.plain, lyrics::PAGE_CHARS);
+
+    let mut view = View::Pages(0);
+    let (e, rows) = render(ctx, guild_id, &song, &lyrics, &pages, view).await;
+    let has_components = !rows.is_empty();
+    let reply = ctx
+        .send(CreateReply::default().embed(e).components(rows))
+        .await?;
+    if !has_components {
+        return Ok(());
+    }
+    let mut msg = reply.message().await?.into_owned();
+
+    let mut idle_deadline = Instant::now() + IDLE_TIMEOUT;
+    let mut last_line = None;
+    loop {
+        let remaining = idle_deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        let wait = match view {
+            View::Synced => SYNC_INTERVAL_SECS,
+            View::Pages(_) => remaining.as_secs().max(1),
+        };
+
+        match components::await_component_interaction(ctx, &msg, wait).await {
+            Some(interaction) => {
+                let custom_id = interaction.data.custom_id.as_str();
+                let page = custom_id
+                    .strip_prefix("tarkov_lyrics_prev_")
+                    .or_else(|| custom_id.strip_prefix("tarkov_lyrics_next_"))
+                    .and_then(|p| p.parse::<usize>().ok());
+
+                view = match (custom_id, page) {
+                    (_, Some(p)) => View::Pages(p.min(pages.len() - 1)),
+                    ("lyrics_sync", _) => View::Synced,
+                    ("lyrics_pages", _) => View::Pages(0),
+                    _ => view,
+                };
+                last_line = None;
+                idle_deadline = Instant::now() + IDLE_TIMEOUT;
+
+                let (e, rows) = render(ctx, guild_id, &song, &lyrics, &pages, view).await;
+                interaction
+                    .create_response(
+                        ctx.serenity_context(),
+                        serenity::CreateInteractionResponse::UpdateMessage(
+                            serenity::CreateInteractionResponseMessage::new()
+                                .embed(e)
+                                .components(rows),
+                        ),
+                    )
+                    .await?;
+            }
+            None if view == View::Synced => {
+                // 곡이 바뀌거나 멈추면 전체 가사로 돌아감
+                let line = match playing_position(ctx, guild_id, &song).await {
+                    Some(position) => lyrics::current_line(&lyrics.synced, position),
+                    None => {
+                        view = View::Pages(0);
+                        let (e, rows) = render(ctx, guild_id, &song, &lyrics, &pages, view).await;
+                        msg.edit(
+                            ctx.serenity_context(),
+                            serenity::EditMessage::new().embed(e).components(rows),
+                        )
+                        .await?;
+                        continue;
+                    }
+                };
+                // 줄이 바뀔 때만 메시지를 수정
+                if last_line != Some(line) {
+                    last_line = Some(line);
+                    let (e, rows) = render(ctx, guild_id, &song, &lyrics, &pages, view).await;
+                    msg.edit(
+                        ctx.serenity_context(),
+                        serenity::EditMessage::new().embed(e).components(rows),
+                    )
+                    .await?;
+                }
+            }
+            None => break,
+        }
+    }
+
+    // Timeout: remove components
+    msg.edit(
+        ctx.serenity_context(),
+        serenity::EditMessage::new().components(vec![]),
+    )
+    .await
+    .ok();
+
+    Ok(())
+}
+
+/// 가사를 표시합니다 (기본: 현재 재생 중인 곡)
+#[poise::command(slash_command, guild_only)]
+pub async fn lyrics(
+    ctx: Context<'_>,
+    #[description = "곡 제목 (생략 시 현재 곡)"] query: Option<String>,
+) -> Result<(), Error> {
+    lyrics_impl(ctx, query).await
+}
+
+/// 가사를 표시합니다 (/lyrics 단축)
+#[poise::command(slash_command, guild_only)]
+pub async fn ly(
+    ctx: Context<'_>,
+    #[description = "곡 제목 (생략 시 현재 곡)"] query: Option<String>,
+) -> Result<(), Error> {
+    lyrics_impl(ctx, query).await
+}
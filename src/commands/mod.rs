@@ -1,17 +1,35 @@
+mod chapter;
+mod equalizer;
+mod favorites;
 mod help;
+mod join;
+mod leave;
 mod loop_cmd;
+mod lyrics;
+mod musicquiz;
+mod myqueue;
 mod nowplaying;
 mod pause;
 mod play;
+mod previous;
 mod queue;
+mod queuepolicy;
+mod radio;
 mod remove;
 mod resume;
 mod shuffle;
 mod skip;
+mod sponsorskip;
+mod stats;
+mod stay;
 mod stop;
+mod summon;
 pub mod tarkov;
+mod undo;
+mod unshuffle;
 mod volume;
 
+use crate::utils::ratelimit;
 use crate::{Data, Error};
 
 pub fn all() -> Vec<poise::Command<Data, Error>> {
@@ -37,9 +55,101 @@ pub fn all() -> Vec<poise::Command<Data, Error>> {
         shuffle::sh(),
         remove::remove(),
         remove::rm(),
+        myqueue::myqueue(),
+        myqueue::myq(),
         volume::volume(),
         volume::v(),
+        equalizer::equalizer(),
+        equalizer::eq(),
+        chapter::chapter(),
+        chapter::ch(),
+        sponsorskip::sponsorskip(),
+        sponsorskip::sb(),
+        unshuffle::unshuffle(),
+        unshuffle::ush(),
+        undo::undo(),
+        undo::u(),
+        previous::previous(),
+        previous::prev(),
+        join::join(),
+        join::j(),
+        leave::leave(),
+        leave::dc(),
+        summon::summon(),
+        summon::sm(),
+        stay::stay_247(),
+        stay::stay(),
+        queuepolicy::queuepolicy(),
+        queuepolicy::qp(),
+        favorites::favorites(),
+        favorites::fav(),
+        radio::radio(),
+        radio::rd(),
+        lyrics::lyrics(),
+        lyrics::ly(),
+        musicquiz::musicquiz(),
+        musicquiz::mq(),
+        stats::stats(),
     ];
     cmds.extend(tarkov::all());
     cmds
 }
+
+/// 재생 제어 커맨드 공통 검사: 사용자가 봇과 같은 음성 채널에 있어야 합니다.
+///
+/// 컨트롤러 버튼과 같은 조건을 사용합니다. 실패하면 ephemeral 오류를 보내고 `false`를 반환합니다.
+async fn ensure_same_channel(
+    ctx: crate::Context<'_>,
+    guild_id: poise::serenity_prelude::GuildId,
+) -> Result<bool, Error> {
+    match crate::utils::voice::check_same_channel(ctx.serenity_context(), guild_id, ctx.author().id)
+        .await
+    {
+        Ok(()) => Ok(true),
+        Err(msg) => {
+            ctx.send(
+                poise::CreateReply::default()
+                    .embed(crate::utils::embed::error(msg))
+                    .ephemeral(true),
+            )
+            .await?;
+            Ok(false)
+        }
+    }
+}
+
+/// 모든 슬래시 커맨드 실행 전 쿨다운을 확인합니다. 걸리면 남은 시간을 ephemeral로 알립니다.
+pub async fn check_cooldown(ctx: crate::Context<'_>) -> Result<bool, Error> {
+    let limiter = &ctx.data().rate_limiter;
+    let bucket = match limiter.bucket_for(&ctx.command().name) {
+        Some(b) => b,
+        None => return Ok(true),
+    };
+
+    match limiter.check(bucket, ctx.author().id, ctx.guild_id()) {
+        Ok(()) => Ok(true),
+        Err(remaining) => {
+            ctx.send(
+                poise::CreateReply::default()
+                    .embed(crate::utils::embed::error(&ratelimit::cooldown_message(
+                        remaining,
+                    )))
+                    .ephemeral(true),
+            )
+            .await?;
+            Ok(false)
+        }
+    }
+}
+
+/// 쿨다운으로 막힌 경우는 이미 안내했으므로 조용히 넘기고, 나머지는 기본 처리
+pub async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
+    match error {
+        poise::FrameworkError::CommandCheckFailed { error: None, .. } => {}
+        other => {
+            if let Err(e) = poise::builtins::on_error(other).await {
+                tracing::error!("오류 처리 중 오류: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use rand::seq::SliceRandom;
+use serenity::builder::CreateEmbed;
+use serenity::collector::MessageCollector;
+use serenity::futures::StreamExt;
+use songbird::tracks::{PlayMode, Track, TrackHandle};
+use songbird::Call;
+use tokio::sync::{Mutex, Notify};
+
+use crate::music::quiz::{self, Scoreboard};
+use crate::music::source::{self, YtDlp};
+use crate::music::{player, queue, Song};
+use crate::utils::{embed, voice};
+use crate::{Context, Error};
+
+/// 재생목록/검색에서 가져올 후보 곡 수
+const POOL_SIZE: usize = 50;
+/// 라운드 사이 쉬는 시간
+const ROUND_GAP: Duration = Duration::from_secs(3);
+/// 발췌 위치로 이동을 기다리는 최대 시간
+const SEEK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 퀴즈에 낼 곡을 고릅니다 (길이를 알 수 없는 라이브 등은 제외)
+async fn pick_songs(ytdlp: &YtDlp, seed: &str, rounds: usize) -> Result<Vec<Song>, Error> {
+    let url = if source::is_url(seed) {
+        seed.to_string()
+    } else {
+        format!("ytsearch{POOL_SIZE}:{seed}")
+    };
+    let mut songs: Vec<Song> = ytdlp
+        .playlist_songs(&url, POOL_SIZE)
+        .await?
+        .into_iter()
+        .filter(|s| {
+            s.duration
+                .as_deref()
+                .and_then(quiz::duration_secs)
+                .is_some()
+        })
+        .collect();
+    songs.shuffle(&mut rand::thread_rng());
+    songs.truncate(rounds);
+    Ok(songs)
+}
+
+/// 대기열과 별개의 트랙으로 곡의 일부를 재생합니다.
+///
+/// `play_only`가 아닌 `play`를 사용하므로 일시정지해 둔 음악 트랙은 그대로 남고,
+/// 대기열의 TrackEnd 이벤트나 재생 기록도 붙지 않습니다.
+async fn play_excerpt(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    call: &Arc<Mutex<Call>>,
+    song: &Song,
+    length: Duration,
+) -> Result<TrackHandle, Error> {
+    let services = &ctx.data().services;
+    let stream = services.ytdlp.stream_source(&song.url).await?;
+    let volume = queue::get_volume(&ctx.data().queue_manager, guild_id).await;
+    let track = Track::from(stream.input(&services.http_client))
+        .volume(volume)
+        .pause();
+    let handle = call.lock().await.play(track);
+
+    if let Some(total) = song.duration.as_deref().and_then(quiz::duration_secs) {
+        let start = quiz::excerpt_start(total, length, rand::random());
+        if !start.is_zero() {
+            if let Err(e) = tokio::time::timeout(SEEK_TIMEOUT, handle.seek_async(start)).await {
+                tracing::warn!("퀴즈 발췌 위치 이동 실패: {e}");
+            }
+        }
+    }
+    let _ = handle.play();
+    Ok(handle)
+}
+
+/// 퀴즈가 끝난 뒤 대기열 음악을 되돌립니다.
+///
+/// 퀴즈 때문에 멈춘 곡은 이어서 재생하고, 퀴즈 중에 끝난 곡은 다음 곡으로 넘어갑니다.
+/// 재생 중인 곡이 없었는데 퀴즈 중에 대기열이 바뀌었으면(`/play` 등) 재생을 시작합니다.
+async fn resume_music(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    call: &Arc<Mutex<Call>>,
+    paused: Option<TrackHandle>,
+    idle_version: Option<u64>,
+) -> Result<(), Error> {
+    let data = ctx.data();
+    let qm = &data.queue_manager;
+
+    let version = queue::get_upcoming(qm, guild_id).await.1;
+    let handle = queue::get_track_handle(qm, guild_id).await;
+    let alive = match &handle {
+        Some(h) => h.get_info().await.is_ok_and(|i| !i.playing.is_done()),
+        None => false,
+    };
+    if let (true, Some(h)) = (alive, handle) {
+        // 퀴즈 중에 사람이 돌아와 미뤄 둔 자동 재개도 여기서 처리
+        if paused.is_some() || queue::take_auto_paused(qm, guild_id).await {
+            let _ = h.play();
+        }
+    } else if queue::get_current(qm, guild_id).await.is_some() {
+        player::play_next(guild_id, qm, &data.services, call, false).await?;
+    } else if idle_version.is_some_and(|v| v != version) {
+        player::start_playback(
+            guild_id,
+            qm,
+            &data.services,
+            data.guild_settings.as_deref(),
+            call,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+enum RoundEnd {
+    Answered(serenity::UserId, u32),
+    TimedOut,
+    Stopped,
+}
+
+/// 채팅에서 첫 정답을 기다립니다
+async fn collect_answer(ctx: Context<'_>, song: &Song, limit: Duration, stop: &Notify) -> RoundEnd {
+    let started = Instant::now();
+    let mut messages = MessageCollector::new(ctx.serenity_context())
+        .channel_id(ctx.channel_id())
+        .timeout(limit)
+        .stream();
+
+    loop {
+        tokio::select! {
+            _ = stop.notified() => return RoundEnd::Stopped,
+            msg = messages.next() => match msg {
+                Some(msg) if !msg.author.bot && quiz::is_correct(&msg.content, &song.title) => {
+                    let points = quiz::points_for(started.elapsed(), limit);
+                    let _ = msg.react(ctx.serenity_context(), '✅').await;
+                    return RoundEnd::Answered(msg.author.id, points);
+                }
+                Some(_) => {}
+                None => return RoundEnd::TimedOut,
+            },
+        }
+    }
+}
+
+async fn run_rounds(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    call: &Arc<Mutex<Call>>,
+    songs: &[Song],
+    limit: Duration,
+    stop: &Notify,
+) -> Result<(Scoreboard, usize), Error> {
+    let mut board = Scoreboard::default();
+
+    for (i, song) in songs.iter().enumerate() {
+        ctx.send(
+            CreateReply::default().embed(
+                CreateEmbed::new()
+                    .title(format!("🎧 라운드 {}/{}", i + 1, songs.len()))
+                    .description(format!(
+                        "채팅으로 곡 제목을 입력하세요! (**{}초**, 빨리 맞힐수록 높은 점수)",
+                        limit.as_secs()
+                    ))
+                    .color(0x5865F2),
+            ),
+        )
+        .await?;
+
+        let handle = match play_excerpt(ctx, guild_id, call, song, limit).await {
+            Ok(handle) => handle,
+            Err(e) => {
+                tracing::warn!("퀴즈 곡 재생 실패 ({}): {e}", song.title);
+                ctx.send(
+                    CreateReply::default()
+                        .embed(embed::error("곡을 불러오지 못해 이번 라운드는 건너뜁니다.")),
+                )
+                .await?;
+                continue;
+            }
+        };
+        let end = collect_answer(ctx, song, limit, stop).await;
+        let _ = handle.stop();
+
+        let winner = match end {
+            RoundEnd::Answered(user_id, points) => {
+                board.award(user_id, points);
+                Some((user_id, points))
+            }
+            RoundEnd::TimedOut => None,
+            RoundEnd::Stopped => return Ok((board, i)),
+        };
+        ctx.send(CreateReply::default().embed(embed::quiz_reveal(song, winner, &board.ranking())))
+            .await?;
+
+        if i + 1 < songs.len() {
+            tokio::select! {
+                _ = tokio::time::sleep(ROUND_GAP) => {}
+                _ = stop.notified() => return Ok((board, i + 1)),
+            }
+        }
+    }
+
+    Ok((board, songs.len()))
+}
+
+async fn start_impl(
+    ctx: Context<'_>,
+    seed: String,
+    rounds: Option<usize>,
+    seconds: Option<u64>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let rounds = rounds
+        .unwrap_or(quiz::DEFAULT_ROUNDS)
+        .clamp(1, quiz::MAX_ROUNDS);
+    let limit = Duration::from_secs(
+        seconds
+            .unwrap_or(quiz::DEFAULT_ROUND_SECS)
+            .clamp(quiz::MIN_ROUND_SECS, quiz::MAX_ROUND_SECS),
+    );
+
+    let Some(channel_id) = voice::user_channel(ctx.serenity_context(), guild_id, ctx.author().id)
+    else {
+        ctx.send(CreateReply::default().embed(embed::error("음성 채널에 먼저 접속해주세요!")))
+            .await?;
+        return Ok(());
+    };
+
+    // 이미 다른 채널에서 음악을 틀고 있으면 옮기지 않음
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird 미등록");
+    if manager.get(guild_id).is_some() {
+        if let Err(msg) =
+            voice::check_same_channel(ctx.serenity_context(), guild_id, ctx.author().id).await
+        {
+            ctx.send(CreateReply::default().embed(embed::error(msg)))
+                .await?;
+            return Ok(());
+        }
+    }
+
+    let Some(stop) = ctx.data().services.quiz_games.start(guild_id) else {
+        ctx.send(CreateReply::default().embed(embed::error(
+            "이미 퀴즈가 진행 중입니다. `/musicquiz stop`으로 끝낼 수 있습니다.",
+        )))
+        .await?;
+        return Ok(());
+    };
+
+    ctx.defer().await?;
+    let result = run_game(ctx, guild_id, channel_id, &seed, rounds, limit, &stop).await;
+    ctx.data().services.quiz_games.finish(guild_id);
+    result
+}
+
+async fn run_game(
+    ctx: Context<'_>,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+    seed: &str,
+    rounds: usize,
+    limit: Duration,
+    stop: &Notify,
+) -> Result<(), Error> {
+    let songs = match pick_songs(&ctx.data().services.ytdlp, seed, rounds).await {
+        Ok(songs) if !songs.is_empty() => songs,
+        Ok(_) => {
+            ctx.send(
+                CreateReply::default().embed(embed::error("퀴즈에 사용할 곡을 찾지 못했습니다.")),
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            ctx.send(
+                CreateReply::default()
+                    .embed(embed::error(&format!("곡 목록을 가져오지 못했습니다: {e}"))),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird 미등록");
+    let call = match manager.get(guild_id) {
+        Some(call) => call,
+        None => match voice::join(
+            ctx.serenity_context(),
+            &ctx.data().queue_manager,
+            guild_id,
+            channel_id,
+        )
+        .await
+        {
+            Ok(call) => call,
+            Err(e) => {
+                ctx.send(
+                    CreateReply::default()
+                        .embed(embed::error(&format!("음성 채널 접속 실패: {e}"))),
+                )
+                .await?;
+                return Ok(());
+            }
+        },
+    };
+
+    // 재생 중이던 음악은 퀴즈 동안 일시정지했다가 끝나면 이어서 재생
+    let idle_version = match queue::get_current(&ctx.data().queue_manager, guild_id).await {
+        Some(_) => None,
+        None => Some(
+            queue::get_upcoming(&ctx.data().queue_manager, guild_id)
+                .await
+                .1,
+        ),
+    };
+    let music = match queue::get_track_handle(&ctx.data().queue_manager, guild_id).await {
+        Some(h)
+            if h.get_info()
+                .await
+                .is_ok_and(|i| i.playing == PlayMode::Play) =>
+        {
+            let _ = h.pause();
+            Some(h)
+        }
+        _ => None,
+    };
+
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::new()
+                .title("🎵 음악 퀴즈 시작!")
+                .description(format!(
+                    "**{}라운드**, 라운드당 **{}초**\n노래를 듣고 채팅으로 제목을 맞혀보세요.",
+                    songs.len(),
+                    limit.as_secs()
+                ))
+                .color(0x5865F2),
+        ),
+    )
+    .await?;
+
+    let result = run_rounds(ctx, guild_id, &call, &songs, limit, stop).await;
+
+    // 퀴즈를 먼저 끝내야 대기열 음악을 다시 틀 수 있음
+    ctx.data().services.quiz_games.finish(guild_id);
+    if let Err(e) = resume_music(ctx, guild_id, &call, music, idle_version).await {
+        tracing::warn!("퀴즈 후 음악 재개 실패: {e}");
+    }
+
+    let (board, played) = result?;
+    ctx.send(CreateReply::default().embed(embed::quiz_final(&board.ranking(), played)))
+        .await?;
+    Ok(())
+}
+
+async fn stop_impl(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    if ctx.data().services.quiz_games.stop(guild_id) {
+        ctx.say("🛑 퀴즈를 종료합니다.").await?;
+    } else {
+        ctx.send(CreateReply::default().embed(embed::error("진행 중인 퀴즈가 없습니다.")))
+            .await?;
+    }
+    Ok(())
+}
+
+/// 노래 맞히기 퀴즈
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("start", "stop"),
+    subcommand_required
+)]
+pub async fn musicquiz(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 노래 맞히기 퀴즈 (/musicquiz 단축)
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("start", "stop"),
+    subcommand_required
+)]
+pub async fn mq(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 재생목록이나 검색어로 퀴즈를 시작합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn start(
+    ctx: Context<'_>,
+    #[description = "재생목록 URL 또는 검색어"] seed: String,
+    #[description = "라운드 수 (기본 5, 최대 20)"] rounds: Option<usize>,
+    #[description = "라운드당 제한 시간(초) (10-60, 기본 20)"] seconds: Option<u64>,
+) -> Result<(), Error> {
+    start_impl(ctx, seed, rounds, seconds).await
+}
+
+/// 진행 중인 퀴즈를 끝냅니다
+#[poise::command(slash_command, guild_only)]
+pub async fn stop(ctx: Context<'_>) -> Result<(), Error> {
+    stop_impl(ctx).await
+}
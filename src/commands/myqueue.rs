@@ -0,0 +1,32 @@
+use poise::CreateReply;
+
+use crate::music::queue;
+use crate::utils::embed;
+use crate::{Context, Error};
+
+async fn myqueue_impl(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let songs =
+        queue::songs_requested_by(&ctx.data().queue_manager, guild_id, ctx.author().id).await;
+    ctx.send(
+        CreateReply::default()
+            .embed(embed::my_queue(&songs))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// 내가 신청해 대기 중인 곡과 위치를 표시합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn myqueue(ctx: Context<'_>) -> Result<(), Error> {
+    myqueue_impl(ctx).await
+}
+
+/// 내가 신청해 대기 중인 곡을 표시합니다 (/myqueue 단축)
+#[poise::command(slash_command, guild_only)]
+pub async fn myq(ctx: Context<'_>) -> Result<(), Error> {
+    myqueue_impl(ctx).await
+}
@@ -1,7 +1,8 @@
 use poise::CreateReply;
 
-use crate::music::queue;
-use crate::utils::{components, embed};
+use crate::music::{chapters, queue};
+use crate::utils::components::{self, ControllerState};
+use crate::utils::embed;
 use crate::{Context, Error};
 
 async fn nowplaying_impl(ctx: Context<'_>) -> Result<(), Error> {
@@ -16,29 +17,35 @@ async fn nowplaying_impl(ctx: Context<'_>) -> Result<(), Error> {
 
             let mut e = embed::now_playing(&song);
             e = e.field("반복", format!("{loop_mode}"), true);
-            e = e.field("볼륨", format!("{}%", (vol * 100.0) as u32), true);
+            e = e.field("볼륨", embed::format_volume(vol), true);
+            if queue::is_shuffled(&ctx.data().queue_manager, guild_id).await {
+                e = e.field("셔플", "켬", true);
+            }
 
-            let is_paused = {
-                let handle = {
-                    let queues = ctx.data().queue_manager.read().await;
-                    queues.get(&guild_id).and_then(|q| q.track_handle.clone())
-                };
-                match handle {
-                    Some(h) => h
-                        .get_info()
-                        .await
-                        .map(|info| info.playing == songbird::tracks::PlayMode::Pause)
-                        .unwrap_or(false),
-                    None => false,
-                }
+            let info = match queue::get_track_handle(&ctx.data().queue_manager, guild_id).await {
+                Some(h) => h.get_info().await.ok(),
+                None => None,
             };
 
-            let (_, upcoming) = queue::get_queue_list(&ctx.data().queue_manager, guild_id).await;
+            if !song.chapters.is_empty() {
+                let current = info.as_ref().and_then(|info| {
+                    chapters::current_index(&song.chapters, info.position.as_secs_f64())
+                });
+                e = e.field(
+                    format!("챕터 ({}개)", song.chapters.len()),
+                    chapters::format_list(&song.chapters, current),
+                    false,
+                );
+            }
+
+            let state = ControllerState::load(&ctx.data().queue_manager, guild_id).await;
+            let (upcoming, version) =
+                queue::get_upcoming(&ctx.data().queue_manager, guild_id).await;
 
             ctx.send(
                 CreateReply::default()
                     .embed(e)
-                    .components(components::music_components(is_paused, &upcoming)),
+                    .components(components::music_components(&state, &upcoming, version)),
             )
             .await?;
         }
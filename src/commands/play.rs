@@ -1,19 +1,39 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use poise::serenity_prelude::{Attachment, GuildId};
 use poise::CreateReply;
+use songbird::Call;
+use tokio::sync::{watch, Mutex};
+use tracing::warn;
 
-use crate::music::{player, queue, source};
-use crate::utils::{components, embed};
+use crate::music::import::{self, ListFormat};
+use crate::music::{player, queue, Song};
+use crate::utils::components::{self, ControllerState};
+use crate::utils::{embed, voice};
 use crate::{Context, Error};
 
+/// 재생 중인 곡이 없을 때 대기열의 첫 곡을 재생합니다 (이 서버의 마지막 볼륨과 EQ 복원)
+pub(super) async fn start_playback(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    call: &Arc<Mutex<Call>>,
+) -> Result<Option<Song>, Error> {
+    let data = ctx.data();
+    player::start_playback(
+        guild_id,
+        &data.queue_manager,
+        &data.services,
+        data.guild_settings.as_deref(),
+        call,
+    )
+    .await
+}
+
 async fn play_impl(ctx: Context<'_>, query: String) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
 
-    let channel_id = {
-        let guild = ctx.guild().ok_or("서버 정보를 가져올 수 없습니다")?;
-        guild
-            .voice_states
-            .get(&ctx.author().id)
-            .and_then(|vs| vs.channel_id)
-    };
+    let channel_id = voice::user_channel(ctx.serenity_context(), guild_id, ctx.author().id);
 
     let channel_id = match channel_id {
         Some(id) => id,
@@ -26,7 +46,7 @@ async fn play_impl(ctx: Context<'_>, query: String) -> Result<(), Error> {
 
     ctx.defer().await?;
 
-    let mut song = match source::get_song_info(&query).await {
+    let mut song = match ctx.data().services.ytdlp.song_info(&query).await {
         Ok(s) => s,
         Err(e) => {
             ctx.send(
@@ -39,63 +59,296 @@ async fn play_impl(ctx: Context<'_>, query: String) -> Result<(), Error> {
     };
 
     song.requester = ctx.author().name.clone();
+    song.requester_id = Some(ctx.author().id);
 
-    let manager = songbird::get(ctx.serenity_context())
-        .await
-        .expect("Songbird 미등록");
-
-    let call = manager.join(guild_id, channel_id).await?;
-
-    let is_first = queue::is_empty(&ctx.data().queue_manager, guild_id).await;
-    let position = queue::add_song(&ctx.data().queue_manager, guild_id, song.clone()).await;
-
-    if is_first {
-        let next = queue::get_next_song(&ctx.data().queue_manager, guild_id, false).await;
-        if let Some(song) = next {
-            player::play_song(
-                guild_id,
-                &ctx.data().queue_manager,
-                &ctx.data().http_client,
-                &call,
-                &song,
-            )
+    // 정책에 걸리는 곡이면 음성 채널에 들어가기 전에 알림
+    if let Err(e) = queue::check_policy(&ctx.data().queue_manager, guild_id, &song).await {
+        ctx.send(CreateReply::default().embed(embed::error(&e.to_string())))
             .await?;
+        return Ok(());
+    }
 
-            let (_, upcoming) = queue::get_queue_list(&ctx.data().queue_manager, guild_id).await;
+    let call = match voice::join(
+        ctx.serenity_context(),
+        &ctx.data().queue_manager,
+        guild_id,
+        channel_id,
+    )
+    .await
+    {
+        Ok(call) => call,
+        Err(e) => {
             ctx.send(
-                CreateReply::default()
-                    .embed(embed::now_playing(&song))
-                    .components(components::music_components(false, &upcoming)),
+                CreateReply::default().embed(embed::error(&format!("음성 채널 접속 실패: {e}"))),
             )
             .await?;
+            return Ok(());
+        }
+    };
+
+    // /leave 후에는 대기열이 남아 있어도 재생 중인 곡이 없으므로 새로 시작
+    let is_first = queue::get_current(&ctx.data().queue_manager, guild_id)
+        .await
+        .is_none();
+    let added = match queue::add_song(&ctx.data().queue_manager, guild_id, song.clone()).await {
+        Ok(added) => added,
+        Err(e) => {
+            ctx.send(CreateReply::default().embed(embed::error(&e.to_string())))
+                .await?;
+            return Ok(());
         }
+    };
+
+    // 음악 퀴즈 중에는 재생이 미뤄지므로 대기열에 추가된 것으로 안내
+    let playing = if is_first {
+        start_playback(ctx, guild_id, &call).await?
     } else {
-        let (_, upcoming) = queue::get_queue_list(&ctx.data().queue_manager, guild_id).await;
+        None
+    };
+    if let Some(playing) = playing {
+        let state = ControllerState::load(&ctx.data().queue_manager, guild_id).await;
+        let volume = queue::get_volume(&ctx.data().queue_manager, guild_id).await;
+        let (upcoming, version) = queue::get_upcoming(&ctx.data().queue_manager, guild_id).await;
+        let mut now_playing =
+            embed::now_playing(&playing).field("볼륨", embed::format_volume(volume), true);
+        if let Some(position) = added.duplicate_of {
+            now_playing = embed::duplicate_warning(now_playing, position);
+        }
         ctx.send(
             CreateReply::default()
-                .embed(embed::added_to_queue(&song, position))
-                .components(components::music_components(false, &upcoming)),
+                .embed(now_playing)
+                .components(components::music_components(&state, &upcoming, version)),
         )
         .await?;
+    } else {
+        let state = ControllerState::load(&ctx.data().queue_manager, guild_id).await;
+        let (upcoming, version) = queue::get_upcoming(&ctx.data().queue_manager, guild_id).await;
+        let mut added_embed = embed::added_to_queue(&song, added.position);
+        if let Some(position) = added.duplicate_of {
+            added_embed = embed::duplicate_warning(added_embed, position);
+        }
+        ctx.send(
+            CreateReply::default()
+                .embed(added_embed)
+                .components(components::music_components(&state, &upcoming, version)),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// 목록을 붙여 넣는 창을 닫지 않고 기다릴 시간
+const MODAL_TIMEOUT: Duration = Duration::from_secs(600);
+/// 진행 상황 메시지를 고치는 최소 간격 (Discord 속도 제한)
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(1500);
+
+/// 여러 곡을 한 번에 붙여 넣는 창
+#[derive(Debug, poise::Modal)]
+#[name = "노래 목록 가져오기"]
+struct ImportModal {
+    #[name = "한 줄에 한 곡 (제목 또는 URL)"]
+    #[placeholder = "아티스트 - 제목\nhttps://youtu.be/..."]
+    #[paragraph]
+    #[max_length = 4000]
+    songs: String,
+}
+
+/// 검색어, 첨부 파일, 붙여 넣기 창 중 하나로 /play를 처리합니다
+async fn play_entry(
+    ctx: Context<'_>,
+    query: Option<String>,
+    file: Option<Attachment>,
+) -> Result<(), Error> {
+    if let Some(file) = file {
+        return import_attachment(ctx, file).await;
+    }
+    if let Some(query) = query.filter(|q| !q.trim().is_empty()) {
+        return play_impl(ctx, query).await;
+    }
+
+    // 검색어 없이 부르면 목록을 붙여 넣을 창을 띄움 (창을 띄운 뒤에는 오류를 먼저 보낼 수 없으므로 미리 검사)
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let poise::Context::Application(app_ctx) = ctx else {
+        ctx.send(CreateReply::default().embed(embed::error("노래 제목 또는 URL을 입력해주세요.")))
+            .await?;
+        return Ok(());
+    };
+    if voice::user_channel(ctx.serenity_context(), guild_id, ctx.author().id).is_none() {
+        ctx.send(CreateReply::default().embed(embed::error("음성 채널에 먼저 접속해주세요!")))
+            .await?;
+        return Ok(());
+    }
+
+    match poise::execute_modal(app_ctx, None::<ImportModal>, Some(MODAL_TIMEOUT)).await? {
+        Some(modal) => import_impl(ctx, import::parse_list(&modal.songs, ListFormat::Text)).await,
+        None => Ok(()),
+    }
+}
+
+async fn import_attachment(ctx: Context<'_>, file: Attachment) -> Result<(), Error> {
+    let Some(format) = ListFormat::from_filename(&file.filename) else {
+        ctx.send(CreateReply::default().embed(embed::error(
+            "`.txt` 또는 `.csv` 파일만 가져올 수 있습니다.",
+        )))
+        .await?;
+        return Ok(());
+    };
+    if file.size > import::MAX_FILE_SIZE {
+        ctx.send(CreateReply::default().embed(embed::error(&format!(
+            "파일이 너무 큽니다 (최대 {}KB).",
+            import::MAX_FILE_SIZE / 1024
+        ))))
+        .await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+    let bytes = match file.download().await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            ctx.send(
+                CreateReply::default().embed(embed::error(&format!("파일을 받지 못했습니다: {e}"))),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+    let text = String::from_utf8_lossy(&bytes);
+    import_impl(ctx, import::parse_list(&text, format)).await
+}
+
+/// 검색어 목록을 동시에 조회해 진행 상황을 보여주고, 찾은 곡을 목록 순서대로 대기열에 추가합니다
+async fn import_impl(ctx: Context<'_>, queries: Vec<String>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let error = if queries.is_empty() {
+        Some("가져올 곡이 없습니다. 한 줄에 한 곡씩 적어주세요.".to_string())
+    } else if queries.len() > import::MAX_LINES {
+        Some(format!(
+            "한 번에 최대 {}곡까지 가져올 수 있습니다 ({}줄).",
+            import::MAX_LINES,
+            queries.len()
+        ))
+    } else {
+        None
+    };
+    if let Some(error) = error {
+        ctx.send(CreateReply::default().embed(embed::error(&error)))
+            .await?;
+        return Ok(());
     }
 
+    let Some(channel_id) = voice::user_channel(ctx.serenity_context(), guild_id, ctx.author().id)
+    else {
+        ctx.send(CreateReply::default().embed(embed::error("음성 채널에 먼저 접속해주세요!")))
+            .await?;
+        return Ok(());
+    };
+
+    ctx.defer().await?;
+
+    let total = queries.len();
+    let reply = ctx
+        .send(CreateReply::default().embed(embed::import_progress(0, total)))
+        .await?;
+
+    let (progress, mut done) = watch::channel(0);
+    let resolving = import::resolve_all(&ctx.data().services.ytdlp, &queries, &progress);
+    tokio::pin!(resolving);
+    let results = loop {
+        tokio::select! {
+            results = &mut resolving => break results,
+            Ok(()) = done.changed() => {
+                let count = *done.borrow_and_update();
+                let _ = reply
+                    .edit(ctx, CreateReply::default().embed(embed::import_progress(count, total)))
+                    .await;
+                tokio::time::sleep(PROGRESS_INTERVAL).await;
+            }
+        }
+    };
+
+    let call = match voice::join(
+        ctx.serenity_context(),
+        &ctx.data().queue_manager,
+        guild_id,
+        channel_id,
+    )
+    .await
+    {
+        Ok(call) => call,
+        Err(e) => {
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .embed(embed::error(&format!("음성 채널 접속 실패: {e}"))),
+                )
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let is_first = queue::get_current(&ctx.data().queue_manager, guild_id)
+        .await
+        .is_none();
+    let mut added = 0;
+    let mut failed = Vec::new();
+    for (number, (query, result)) in (1..).zip(queries.iter().zip(results)) {
+        let reason = match result {
+            Ok(mut song) => {
+                song.requester = ctx.author().name.clone();
+                song.requester_id = Some(ctx.author().id);
+                match queue::add_song(&ctx.data().queue_manager, guild_id, song).await {
+                    Ok(_) => {
+                        added += 1;
+                        continue;
+                    }
+                    Err(e) => e.to_string(),
+                }
+            }
+            Err(e) => {
+                warn!("목록 가져오기 중 곡 조회 실패 ({query}): {e}");
+                "노래를 찾을 수 없습니다".to_string()
+            }
+        };
+        failed.push((number, query.clone(), reason));
+    }
+
+    if is_first && added > 0 {
+        start_playback(ctx, guild_id, &call).await?;
+    }
+
+    let state = ControllerState::load(&ctx.data().queue_manager, guild_id).await;
+    let (upcoming, version) = queue::get_upcoming(&ctx.data().queue_manager, guild_id).await;
+    reply
+        .edit(
+            ctx,
+            CreateReply::default()
+                .embed(embed::import_result(added, total, &failed))
+                .components(components::music_components(&state, &upcoming, version)),
+        )
+        .await?;
     Ok(())
 }
 
-/// 음악을 재생합니다
+/// 음악을 재생합니다 (검색어를 비우면 목록 붙여 넣기)
 #[poise::command(slash_command, guild_only)]
 pub async fn play(
     ctx: Context<'_>,
-    #[description = "노래 제목 또는 URL"] query: String,
+    #[description = "노래 제목 또는 URL (비우면 목록 붙여 넣기)"] query: Option<String>,
+    #[description = "노래 목록 파일 (.txt/.csv, 한 줄에 한 곡)"] file: Option<Attachment>,
 ) -> Result<(), Error> {
-    play_impl(ctx, query).await
+    play_entry(ctx, query, file).await
 }
 
 /// 음악을 재생합니다 (/play 단축)
 #[poise::command(slash_command, guild_only)]
 pub async fn p(
     ctx: Context<'_>,
-    #[description = "노래 제목 또는 URL"] query: String,
+    #[description = "노래 제목 또는 URL (비우면 목록 붙여 넣기)"] query: Option<String>,
+    #[description = "노래 목록 파일 (.txt/.csv, 한 줄에 한 곡)"] file: Option<Attachment>,
 ) -> Result<(), Error> {
-    play_impl(ctx, query).await
+    play_entry(ctx, query, file).await
 }
@@ -0,0 +1,62 @@
+use poise::CreateReply;
+
+use crate::music::player;
+use crate::utils::embed;
+use crate::{Context, Error};
+
+async fn previous_impl(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    if !super::ensure_same_channel(ctx, guild_id).await? {
+        return Ok(());
+    }
+
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird 미등록");
+
+    let call = match manager.get(guild_id) {
+        Some(c) => c,
+        None => {
+            ctx.send(CreateReply::default().embed(embed::error("재생 중인 곡이 없습니다.")))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    match player::play_previous(
+        guild_id,
+        &ctx.data().queue_manager,
+        &ctx.data().services,
+        &call,
+    )
+    .await
+    {
+        Ok(Some(song)) => {
+            ctx.say(format!("⏮️ 이전 곡: **{}**", song.title)).await?;
+        }
+        Ok(None) => {
+            ctx.send(CreateReply::default().embed(embed::error("이전 곡이 없습니다.")))
+                .await?;
+        }
+        Err(e) => {
+            ctx.send(
+                CreateReply::default().embed(embed::error(&format!("이전 곡 재생 실패: {e}"))),
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 이전 곡을 다시 재생합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn previous(ctx: Context<'_>) -> Result<(), Error> {
+    previous_impl(ctx).await
+}
+
+/// 이전 곡을 다시 재생합니다 (/previous 단축)
+#[poise::command(slash_command, guild_only)]
+pub async fn prev(ctx: Context<'_>) -> Result<(), Error> {
+    previous_impl(ctx).await
+}
@@ -0,0 +1,135 @@
+use poise::serenity_prelude::GuildId;
+use poise::CreateReply;
+
+use crate::music::policy::{DuplicateMode, EnqueuePolicy};
+use crate::music::queue;
+use crate::utils::embed;
+use crate::{Context, Error};
+
+/// 정책을 대기열에 바로 적용하고, 재시작 후에도 유지되도록 서버별로 저장
+async fn apply(ctx: Context<'_>, guild_id: GuildId, policy: EnqueuePolicy) -> Result<(), Error> {
+    if let Some(settings) = &ctx.data().guild_settings {
+        settings.set_enqueue_policy(guild_id, &policy);
+    }
+    let reply = CreateReply::default().embed(embed::enqueue_policy(&policy));
+    queue::set_policy(&ctx.data().queue_manager, guild_id, policy).await;
+    ctx.send(reply).await?;
+    Ok(())
+}
+
+/// 곡 추가 정책(중복, 길이, 라이브, 차단 목록)을 설정합니다
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD",
+    subcommands("show", "duplicates", "maxlength", "live", "block", "unblock"),
+    subcommand_required
+)]
+pub async fn queuepolicy(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 곡 추가 정책을 설정합니다 (/queuepolicy 단축)
+#[poise::command(
+    slash_command,
+    guild_only,
+    default_member_permissions = "MANAGE_GUILD",
+    subcommands("show", "duplicates", "maxlength", "live", "block", "unblock"),
+    subcommand_required
+)]
+pub async fn qp(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 현재 곡 추가 정책을 표시합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn show(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let policy = queue::get_policy(&ctx.data().queue_manager, guild_id).await;
+    ctx.send(CreateReply::default().embed(embed::enqueue_policy(&policy)))
+        .await?;
+    Ok(())
+}
+
+/// 이미 대기열에 있는 곡을 다시 추가할 때의 처리
+#[poise::command(slash_command, guild_only)]
+pub async fn duplicates(
+    ctx: Context<'_>,
+    #[description = "allow(허용), warn(경고 후 추가), reject(거부)"] mode: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let Some(mode) = DuplicateMode::parse(&mode) else {
+        ctx.send(CreateReply::default().embed(embed::error(
+            "중복 처리는 `allow`, `warn`, `reject` 중 하나로 입력해주세요.",
+        )))
+        .await?;
+        return Ok(());
+    };
+
+    let mut policy = queue::get_policy(&ctx.data().queue_manager, guild_id).await;
+    policy.duplicates = mode;
+    apply(ctx, guild_id, policy).await
+}
+
+/// 추가할 수 있는 곡의 최대 길이를 정합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn maxlength(
+    ctx: Context<'_>,
+    #[description = "최대 길이 (분, 0이면 제한 없음)"]
+    #[max = 1440]
+    minutes: u32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let mut policy = queue::get_policy(&ctx.data().queue_manager, guild_id).await;
+    policy.max_duration_secs = (minutes > 0).then_some(u64::from(minutes) * 60);
+    apply(ctx, guild_id, policy).await
+}
+
+/// 라이브 스트림을 추가할 수 있는지 정합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn live(
+    ctx: Context<'_>,
+    #[description = "허용/거부"] allowed: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let mut policy = queue::get_policy(&ctx.data().queue_manager, guild_id).await;
+    policy.allow_live = allowed;
+    apply(ctx, guild_id, policy).await
+}
+
+/// URL이나 제목에 이 문자열이 들어간 곡을 차단합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn block(
+    ctx: Context<'_>,
+    #[description = "차단할 URL 일부 또는 키워드"] pattern: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let mut policy = queue::get_policy(&ctx.data().queue_manager, guild_id).await;
+    if !policy.block(&pattern) {
+        ctx.send(CreateReply::default().embed(embed::error(
+            "비어 있거나 이미 차단 목록에 있는 항목입니다.",
+        )))
+        .await?;
+        return Ok(());
+    }
+    apply(ctx, guild_id, policy).await
+}
+
+/// 차단 목록에서 항목을 뺍니다
+#[poise::command(slash_command, guild_only)]
+pub async fn unblock(
+    ctx: Context<'_>,
+    #[description = "차단 해제할 URL 일부 또는 키워드"] pattern: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let mut policy = queue::get_policy(&ctx.data().queue_manager, guild_id).await;
+    if !policy.unblock(&pattern) {
+        ctx.send(CreateReply::default().embed(embed::error(&format!(
+            "차단 목록에 `{}` 이(가) 없습니다.",
+            pattern.trim()
+        ))))
+        .await?;
+        return Ok(());
+    }
+    apply(ctx, guild_id, policy).await
+}
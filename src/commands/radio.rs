@@ -0,0 +1,290 @@
+use poise::CreateReply;
+
+use crate::music::radio::{self, RadioPreset};
+use crate::music::{player, queue};
+use crate::utils::components::{self, ControllerState};
+use crate::utils::{embed, voice};
+use crate::{Context, Error};
+
+const NO_SETTINGS: &str = "설정 DB를 사용할 수 없어 라디오 프리셋을 쓸 수 없습니다.";
+
+async fn play_impl(ctx: Context<'_>, name: String) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let Some(settings) = &ctx.data().guild_settings else {
+        ctx.send(CreateReply::default().embed(embed::error(NO_SETTINGS)))
+            .await?;
+        return Ok(());
+    };
+    let Some(preset) = settings.radio_preset(guild_id, &name) else {
+        ctx.send(CreateReply::default().embed(embed::error(&format!(
+            "`{}` 방송국이 없습니다. `/radio list`로 목록을 확인해주세요.",
+            name.trim()
+        ))))
+        .await?;
+        return Ok(());
+    };
+
+    let Some(channel_id) = voice::user_channel(ctx.serenity_context(), guild_id, ctx.author().id)
+    else {
+        ctx.send(CreateReply::default().embed(embed::error("음성 채널에 먼저 접속해주세요!")))
+            .await?;
+        return Ok(());
+    };
+
+    let mut song = preset.to_song();
+    song.requester = ctx.author().name.clone();
+    song.requester_id = Some(ctx.author().id);
+
+    if let Err(e) = queue::check_policy(&ctx.data().queue_manager, guild_id, &song).await {
+        ctx.send(CreateReply::default().embed(embed::error(&e.to_string())))
+            .await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+
+    let call = match voice::join(
+        ctx.serenity_context(),
+        &ctx.data().queue_manager,
+        guild_id,
+        channel_id,
+    )
+    .await
+    {
+        Ok(call) => call,
+        Err(e) => {
+            ctx.send(
+                CreateReply::default().embed(embed::error(&format!("음성 채널 접속 실패: {e}"))),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let is_first = queue::get_current(&ctx.data().queue_manager, guild_id)
+        .await
+        .is_none();
+    let added = match queue::add_song(&ctx.data().queue_manager, guild_id, song.clone()).await {
+        Ok(added) => added,
+        Err(e) => {
+            ctx.send(CreateReply::default().embed(embed::error(&e.to_string())))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    // 음악 퀴즈 중에는 재생이 미뤄지므로 대기열에 추가된 것으로 안내
+    let playing = if is_first {
+        super::play::start_playback(ctx, guild_id, &call).await?
+    } else {
+        None
+    };
+    let reply_embed = match playing {
+        Some(song) => embed::now_playing(&song),
+        None => embed::added_to_queue(&song, added.position),
+    };
+    let state = ControllerState::load(&ctx.data().queue_manager, guild_id).await;
+    let (upcoming, version) = queue::get_upcoming(&ctx.data().queue_manager, guild_id).await;
+    ctx.send(
+        CreateReply::default()
+            .embed(reply_embed)
+            .components(components::music_components(&state, &upcoming, version)),
+    )
+    .await?;
+    Ok(())
+}
+
+async fn stop_impl(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let Some(station) = queue::get_current(&ctx.data().queue_manager, guild_id)
+        .await
+        .filter(|song| song.is_radio)
+    else {
+        ctx.send(CreateReply::default().embed(embed::error("재생 중인 라디오가 없습니다.")))
+            .await?;
+        return Ok(());
+    };
+
+    if !super::ensure_same_channel(ctx, guild_id).await? {
+        return Ok(());
+    }
+
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird 미등록");
+    let Some(call) = manager.get(guild_id) else {
+        ctx.send(CreateReply::default().embed(embed::error("재생 중인 라디오가 없습니다.")))
+            .await?;
+        return Ok(());
+    };
+
+    if let Err(e) = player::play_next(
+        guild_id,
+        &ctx.data().queue_manager,
+        &ctx.data().services,
+        &call,
+        true,
+    )
+    .await
+    {
+        ctx.send(CreateReply::default().embed(embed::error(&format!("라디오 끄기 실패: {e}"))))
+            .await?;
+        return Ok(());
+    }
+
+    let msg = match queue::get_current(&ctx.data().queue_manager, guild_id).await {
+        Some(next) => format!(
+            "📻 **{}** 라디오를 껐습니다 → **{}**",
+            station.title, next.title
+        ),
+        None => format!("📻 **{}** 라디오를 껐습니다 (큐 비어있음)", station.title),
+    };
+    ctx.say(msg).await?;
+    Ok(())
+}
+
+async fn list_impl(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let Some(settings) = &ctx.data().guild_settings else {
+        ctx.send(CreateReply::default().embed(embed::error(NO_SETTINGS)))
+            .await?;
+        return Ok(());
+    };
+    ctx.send(CreateReply::default().embed(embed::radio_presets(&settings.radio_presets(guild_id))))
+        .await?;
+    Ok(())
+}
+
+async fn add_impl(ctx: Context<'_>, name: String, url: String) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let Some(settings) = &ctx.data().guild_settings else {
+        ctx.send(CreateReply::default().embed(embed::error(NO_SETTINGS)))
+            .await?;
+        return Ok(());
+    };
+
+    let name = name.trim();
+    let url = url.trim();
+    let error = if name.is_empty() || name.chars().count() > radio::MAX_NAME_LEN {
+        Some(format!(
+            "이름은 1~{}자로 입력해주세요.",
+            radio::MAX_NAME_LEN
+        ))
+    } else if !radio::is_stream_url(url) {
+        Some("스트림 주소는 `http://` 또는 `https://`로 시작해야 합니다.".to_string())
+    } else if settings.radio_presets(guild_id).len() >= radio::MAX_PRESETS {
+        Some(format!(
+            "방송국은 서버마다 {}개까지 저장할 수 있습니다.",
+            radio::MAX_PRESETS
+        ))
+    } else {
+        None
+    };
+    if let Some(error) = error {
+        ctx.send(CreateReply::default().embed(embed::error(&error)))
+            .await?;
+        return Ok(());
+    }
+
+    let preset = RadioPreset {
+        name: name.to_string(),
+        url: url.to_string(),
+    };
+    if !settings.add_radio_preset(guild_id, &preset) {
+        ctx.send(
+            CreateReply::default()
+                .embed(embed::error(&format!("`{name}` 방송국이 이미 있습니다."))),
+        )
+        .await?;
+        return Ok(());
+    }
+    ctx.say(format!(
+        "📻 **{name}** 방송국을 추가했습니다. `/radio play {name}`"
+    ))
+    .await?;
+    Ok(())
+}
+
+async fn remove_impl(ctx: Context<'_>, name: String) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    let Some(settings) = &ctx.data().guild_settings else {
+        ctx.send(CreateReply::default().embed(embed::error(NO_SETTINGS)))
+            .await?;
+        return Ok(());
+    };
+
+    let reply = if settings.remove_radio_preset(guild_id, &name) {
+        CreateReply::default().content(format!("🗑️ **{}** 방송국을 삭제했습니다.", name.trim()))
+    } else {
+        CreateReply::default().embed(embed::error(&format!(
+            "`{}` 방송국이 없습니다.",
+            name.trim()
+        )))
+    };
+    ctx.send(reply).await?;
+    Ok(())
+}
+
+/// 인터넷 라디오를 재생하거나 방송국 목록을 관리합니다
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("play", "stop", "list", "add", "remove"),
+    subcommand_required
+)]
+pub async fn radio(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 인터넷 라디오를 재생하거나 방송국 목록을 관리합니다 (/radio 단축)
+#[poise::command(
+    slash_command,
+    guild_only,
+    subcommands("play", "stop", "list", "add", "remove"),
+    subcommand_required
+)]
+pub async fn rd(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 저장된 방송국을 대기열에 추가합니다 (끄기 전까지 계속 재생)
+#[poise::command(slash_command, guild_only)]
+pub async fn play(
+    ctx: Context<'_>,
+    #[description = "방송국 이름"] name: String,
+) -> Result<(), Error> {
+    play_impl(ctx, name).await
+}
+
+/// 재생 중인 라디오를 끄고 다음 곡으로 넘어갑니다
+#[poise::command(slash_command, guild_only)]
+pub async fn stop(ctx: Context<'_>) -> Result<(), Error> {
+    stop_impl(ctx).await
+}
+
+/// 이 서버에 저장된 방송국 목록을 표시합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn list(ctx: Context<'_>) -> Result<(), Error> {
+    list_impl(ctx).await
+}
+
+/// 방송국을 추가합니다 (Icecast/SHOUTcast MP3·AAC 스트림 주소)
+#[poise::command(slash_command, guild_only, required_permissions = "MANAGE_GUILD")]
+pub async fn add(
+    ctx: Context<'_>,
+    #[description = "방송국 이름"] name: String,
+    #[description = "스트림 주소 (http/https)"] url: String,
+) -> Result<(), Error> {
+    add_impl(ctx, name, url).await
+}
+
+/// 방송국을 삭제합니다
+#[poise::command(slash_command, guild_only, required_permissions = "MANAGE_GUILD")]
+pub async fn remove(
+    ctx: Context<'_>,
+    #[description = "방송국 이름"] name: String,
+) -> Result<(), Error> {
+    remove_impl(ctx, name).await
+}
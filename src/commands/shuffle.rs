@@ -13,19 +13,22 @@ async fn shuffle_impl(ctx: Context<'_>) -> Result<(), Error> {
         ctx.send(CreateReply::default().embed(embed::error("큐가 비어있습니다.")))
             .await?;
     } else {
-        ctx.say(format!("🔀 {count}곡을 셔플했습니다.")).await?;
+        ctx.say(format!(
+            "🔀 {count}곡을 셔플했습니다. 셔플 모드가 켜졌습니다 (`/unshuffle`로 복원)."
+        ))
+        .await?;
     }
 
     Ok(())
 }
 
-/// 큐를 셔플합니다
+/// 셔플 모드를 켜고 큐를 섞습니다
 #[poise::command(slash_command, guild_only)]
 pub async fn shuffle(ctx: Context<'_>) -> Result<(), Error> {
     shuffle_impl(ctx).await
 }
 
-/// 큐를 셔플합니다 (/shuffle 단축)
+/// 셔플 모드를 켜고 큐를 섞습니다 (/shuffle 단축)
 #[poise::command(slash_command, guild_only)]
 pub async fn sh(ctx: Context<'_>) -> Result<(), Error> {
     shuffle_impl(ctx).await
@@ -1,11 +1,16 @@
 use poise::CreateReply;
 
-use crate::music::{player, queue};
+use crate::music::{player, queue, radio};
 use crate::utils::embed;
 use crate::{Context, Error};
 
 async fn skip_impl(ctx: Context<'_>) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+    if let Err(msg) = radio::check_skippable(&ctx.data().queue_manager, guild_id).await {
+        ctx.send(CreateReply::default().embed(embed::error(msg)))
+            .await?;
+        return Ok(());
+    }
 
     let manager = songbird::get(ctx.serenity_context())
         .await
@@ -28,7 +33,7 @@ async fn skip_impl(ctx: Context<'_>) -> Result<(), Error> {
             match player::play_next(
                 guild_id,
                 &ctx.data().queue_manager,
-                &ctx.data().http_client,
+                &ctx.data().services,
                 &call,
                 true,
             )
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::music::queue;
+use crate::music::segments::{SegmentProvider, SponsorBlockProvider};
+use crate::{Context, Error};
+
+async fn sponsorskip_impl(ctx: Context<'_>, enabled: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let provider: Option<Arc<dyn SegmentProvider>> = if enabled {
+        Some(Arc::new(SponsorBlockProvider::new(
+            ctx.data().http_client.clone(),
+        )))
+    } else {
+        None
+    };
+    queue::set_segment_provider(&ctx.data().queue_manager, guild_id, provider).await;
+
+    if enabled {
+        ctx.say("⏩ 스폰서/인트로 구간 자동 건너뛰기: **켬** (다음 곡부터 적용)")
+            .await?;
+    } else {
+        ctx.say("▶️ 스폰서/인트로 구간 자동 건너뛰기: **끔**")
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// 스폰서/인트로 구간 자동 건너뛰기를 설정합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn sponsorskip(
+    ctx: Context<'_>,
+    #[description = "자동 건너뛰기 사용 여부"] enabled: bool,
+) -> Result<(), Error> {
+    sponsorskip_impl(ctx, enabled).await
+}
+
+/// 스폰서/인트로 구간 자동 건너뛰기를 설정합니다 (/sponsorskip 단축)
+#[poise::command(slash_command, guild_only)]
+pub async fn sb(
+    ctx: Context<'_>,
+    #[description = "자동 건너뛰기 사용 여부"] enabled: bool,
+) -> Result<(), Error> {
+    sponsorskip_impl(ctx, enabled).await
+}
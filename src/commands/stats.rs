@@ -0,0 +1,226 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+
+use crate::music::stats::{self, PlayStats, StatsWindow};
+use crate::utils::components;
+use crate::utils::embed::{self, STATS_PAGE_SIZE};
+use crate::{Context, Error};
+
+/// 순위표 종류
+#[derive(Clone, Copy, PartialEq)]
+enum Board {
+    Songs,
+    Requesters,
+}
+
+impl Board {
+    fn id(self) -> &'static str {
+        match self {
+            Board::Songs => "songs",
+            Board::Requesters => "requesters",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "songs" => Some(Board::Songs),
+            "requesters" => Some(Board::Requesters),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Board::Songs => "🎵 인기 곡",
+            Board::Requesters => "🙋 신청 순위",
+        }
+    }
+}
+
+const WINDOWS: [(StatsWindow, &str, &str); 3] = [
+    (StatsWindow::Week, "7d", "7일"),
+    (StatsWindow::Month, "30d", "30일"),
+    (StatsWindow::All, "all", "전체"),
+];
+
+/// 현재 선택으로 통계 화면을 만듭니다
+fn render(
+    stats: &PlayStats,
+    guild_id: serenity::GuildId,
+    window: StatsWindow,
+    board: Board,
+    page: usize,
+) -> (serenity::CreateEmbed, Vec<serenity::CreateActionRow>, usize) {
+    let since = window.since(stats::now_secs());
+    let summary = stats.summary(guild_id, since);
+    let (songs, requesters) = stats.distinct_counts(guild_id, since);
+    let total = match board {
+        Board::Songs => songs,
+        Board::Requesters => requesters,
+    };
+    let total_pages = total.div_ceil(STATS_PAGE_SIZE).max(1);
+    let page = page.min(total_pages - 1);
+
+    let offset = page * STATS_PAGE_SIZE;
+    let entries = match board {
+        Board::Songs => stats.top_songs(guild_id, since, STATS_PAGE_SIZE, offset),
+        Board::Requesters => stats.top_requesters(guild_id, since, STATS_PAGE_SIZE, offset),
+    };
+
+    let e = embed::music_stats(
+        window.label(),
+        &summary,
+        board.label(),
+        &entries,
+        page,
+        total_pages,
+    );
+    (e, build_components(window, board, page, total_pages), page)
+}
+
+/// 기간/순위표 선택 버튼과 페이지 버튼
+///
+/// Custom ID: `stats_music_window_{id}`, `stats_music_board_{id}`,
+/// `stats_music_prev_{page}`, `stats_music_next_{page}`
+fn build_components(
+    window: StatsWindow,
+    board: Board,
+    page: usize,
+    total_pages: usize,
+) -> Vec<serenity::CreateActionRow> {
+    let style = |active: bool| {
+        if active {
+            serenity::ButtonStyle::Primary
+        } else {
+            serenity::ButtonStyle::Secondary
+        }
+    };
+
+    let window_buttons = WINDOWS
+        .iter()
+        .map(|(w, id, label)| {
+            serenity::CreateButton::new(format!("stats_music_window_{id}"))
+                .label(*label)
+                .style(style(*w == window))
+        })
+        .collect();
+
+    let board_buttons = [Board::Songs, Board::Requesters]
+        .into_iter()
+        .map(|b| {
+            serenity::CreateButton::new(format!("stats_music_board_{}", b.id()))
+                .label(b.label())
+                .style(style(b == board))
+        })
+        .collect();
+
+    let page_buttons = vec![
+        serenity::CreateButton::new(format!("stats_music_prev_{}", page.saturating_sub(1)))
+            .label("<< 이전")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(page == 0),
+        serenity::CreateButton::new("stats_music_page")
+            .label(format!("{}/{total_pages}", page + 1))
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(true),
+        serenity::CreateButton::new(format!("stats_music_next_{}", page + 1))
+            .label("다음 >>")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(page + 1 >= total_pages),
+    ];
+
+    vec![
+        serenity::CreateActionRow::Buttons(window_buttons),
+        serenity::CreateActionRow::Buttons(board_buttons),
+        serenity::CreateActionRow::Buttons(page_buttons),
+    ]
+}
+
+async fn music_impl(ctx: Context<'_>, period: Option<String>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let Some(stats) = ctx.data().services.stats.clone() else {
+        ctx.send(CreateReply::default().embed(embed::error("재생 기록을 사용할 수 없습니다.")))
+            .await?;
+        return Ok(());
+    };
+
+    let mut window = match period.as_deref().map(StatsWindow::parse) {
+        None => StatsWindow::default(),
+        Some(Some(w)) => w,
+        Some(None) => {
+            ctx.send(CreateReply::default().embed(embed::error(
+                "올바른 기간을 선택해주세요: `7d`, `30d`, `all`",
+            )))
+            .await?;
+            return Ok(());
+        }
+    };
+    let mut board = Board::Songs;
+
+    let (e, rows, mut page) = render(&stats, guild_id, window, board, 0);
+    let reply = ctx
+        .send(CreateReply::default().embed(e).components(rows))
+        .await?;
+    let mut msg = reply.message().await?.into_owned();
+
+    while let Some(interaction) = components::await_component_interaction(ctx, &msg, 300).await {
+        let custom_id = interaction.data.custom_id.as_str();
+
+        if let Some(id) = custom_id.strip_prefix("stats_music_window_") {
+            if let Some((w, _, _)) = WINDOWS.iter().find(|(_, wid, _)| *wid == id) {
+                window = *w;
+                page = 0;
+            }
+        } else if let Some(id) = custom_id.strip_prefix("stats_music_board_") {
+            if let Some(b) = Board::from_id(id) {
+                board = b;
+                page = 0;
+            }
+        } else if let Some(p) = custom_id
+            .strip_prefix("stats_music_prev_")
+            .or_else(|| custom_id.strip_prefix("stats_music_next_"))
+            .and_then(|p| p.parse().ok())
+        {
+            page = p;
+        }
+
+        let (e, rows, shown) = render(&stats, guild_id, window, board, page);
+        page = shown;
+        interaction
+            .create_response(
+                ctx.serenity_context(),
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .embed(e)
+                        .components(rows),
+                ),
+            )
+            .await?;
+    }
+
+    // Timeout: remove components
+    msg.edit(
+        ctx.serenity_context(),
+        serenity::EditMessage::new().components(vec![]),
+    )
+    .await
+    .ok();
+
+    Ok(())
+}
+
+/// 서버 통계를 표시합니다
+#[poise::command(slash_command, guild_only, subcommands("music"), subcommand_required)]
+pub async fn stats(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// 인기 곡, 신청 순위, 재생 시간을 표시합니다
+#[poise::command(slash_command, guild_only)]
+pub async fn music(
+    ctx: Context<'_>,
+    #[description = "기간 (7d, 30d, all)"] period: Option<String>,
+) -> Result<(), Error> {
+    music_impl(ctx, period).await
+}
@@ -0,0 +1,156 @@
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use serenity::builder::CreateEmbed;
+
+use crate::music::{player, queue, IdleFallback};
+use crate::utils::{embed, voice};
+use crate::{Context, Error};
+
+fn fallback_label(fallback: Option<&IdleFallback>) -> String {
+    match fallback {
+        None => "없음".to_string(),
+        Some(IdleFallback::Autoplay) => "자동 재생 (마지막 곡 기반 믹스)".to_string(),
+        Some(IdleFallback::Playlist(url)) => format!("[저장된 재생목록]({url})"),
+    }
+}
+
+async fn stay_impl(
+    ctx: Context<'_>,
+    enabled: bool,
+    channel: Option<serenity::GuildChannel>,
+    fallback: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let settings = match &ctx.data().guild_settings {
+        Some(s) => s,
+        None => {
+            ctx.send(CreateReply::default().embed(embed::error(
+                "설정 DB를 사용할 수 없어 24/7 모드를 바꿀 수 없습니다.",
+            )))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    if !enabled {
+        settings.set_stay(guild_id, None, None);
+        queue::set_idle_fallback(&ctx.data().queue_manager, guild_id, None).await;
+        let minutes = ctx.data().auto_leave.as_secs().div_ceil(60);
+        ctx.say(format!(
+            "📌 24/7 모드를 껐습니다. 듣는 사람이 없으면 일시정지하고, {minutes}분 동안 돌아오지 않으면 자동으로 퇴장합니다."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let target = channel
+        .map(|c| c.id)
+        .or_else(|| voice::user_channel(ctx.serenity_context(), guild_id, ctx.author().id));
+    let target = match target {
+        Some(id) => id,
+        None => {
+            ctx.send(CreateReply::default().embed(embed::error(
+                "음성 채널에 먼저 접속하거나 채널을 지정해주세요.",
+            )))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let fallback = match fallback.as_deref().map(str::trim) {
+        None | Some("off") | Some("끔") => None,
+        Some(input) => match IdleFallback::parse(input) {
+            Some(f) => Some(f),
+            None => {
+                ctx.send(CreateReply::default().embed(embed::error(
+                    "대체 재생은 `autoplay`, `off` 또는 재생목록 URL로 입력해주세요.",
+                )))
+                .await?;
+                return Ok(());
+            }
+        },
+    };
+
+    let call = match voice::join(
+        ctx.serenity_context(),
+        &ctx.data().queue_manager,
+        guild_id,
+        target,
+    )
+    .await
+    {
+        Ok(call) => call,
+        Err(e) => {
+            ctx.send(
+                CreateReply::default().embed(embed::error(&format!("음성 채널 접속 실패: {e}"))),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    settings.set_stay(guild_id, Some(target), fallback.as_ref());
+    queue::set_idle_fallback(&ctx.data().queue_manager, guild_id, fallback.clone()).await;
+
+    // 재생 중인 곡이 없으면 남은 대기열이나 저장된 재생목록으로 바로 시작
+    if queue::get_current(&ctx.data().queue_manager, guild_id)
+        .await
+        .is_none()
+    {
+        ctx.defer().await?;
+        if let Err(e) = player::play_next(
+            guild_id,
+            &ctx.data().queue_manager,
+            &ctx.data().services,
+            &call,
+            true,
+        )
+        .await
+        {
+            tracing::warn!("24/7 재생 시작 실패: {e}");
+        }
+    }
+
+    let e = CreateEmbed::new()
+        .title("📌 24/7 모드 켜짐")
+        .description(format!(
+            "<#{target}>에 계속 머무르며 자동 퇴장하지 않습니다. 봇이 재시작되면 이 채널로 다시 접속합니다."
+        ))
+        .field("대기열이 비면", fallback_label(fallback.as_ref()), false)
+        .color(0x5865F2);
+    ctx.send(CreateReply::default().embed(e)).await?;
+
+    Ok(())
+}
+
+/// 24/7 모드: 음성 채널에 계속 머무르고 대기열이 비면 자동 재생합니다
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "247",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn stay_247(
+    ctx: Context<'_>,
+    #[description = "켜기/끄기"] enabled: bool,
+    #[description = "머무를 음성 채널 (기본: 내가 있는 채널)"]
+    #[channel_types("Voice", "Stage")]
+    channel: Option<serenity::GuildChannel>,
+    #[description = "대기열이 비면: autoplay, off 또는 재생목록 URL"] fallback: Option<String>,
+) -> Result<(), Error> {
+    stay_impl(ctx, enabled, channel, fallback).await
+}
+
+/// 24/7 모드를 켜거나 끕니다 (/247 단축)
+#[poise::command(slash_command, guild_only, default_member_permissions = "MANAGE_GUILD")]
+pub async fn stay(
+    ctx: Context<'_>,
+    #[description = "켜기/끄기"] enabled: bool,
+    #[description = "머무를 음성 채널 (기본: 내가 있는 채널)"]
+    #[channel_types("Voice", "Stage")]
+    channel: Option<serenity::GuildChannel>,
+    #[description = "대기열이 비면: autoplay, off 또는 재생목록 URL"] fallback: Option<String>,
+) -> Result<(), Error> {
+    stay_impl(ctx, enabled, channel, fallback).await
+}
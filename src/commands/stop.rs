@@ -1,6 +1,6 @@
 use poise::CreateReply;
 
-use crate::music::queue;
+use crate::music::{player, queue};
 use crate::utils::embed;
 use crate::{Context, Error};
 
@@ -17,10 +17,12 @@ async fn stop_impl(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     }
 
+    player::record_skipped(guild_id, &ctx.data().queue_manager, &ctx.data().services).await;
     queue::clear(&ctx.data().queue_manager, guild_id).await;
     let _ = manager.remove(guild_id).await;
 
-    ctx.say("⏹️ 재생을 중지하고 퇴장합니다.").await?;
+    ctx.say("⏹️ 재생을 중지하고 퇴장합니다. (`/undo`로 되돌리기)")
+        .await?;
 
     Ok(())
 }
@@ -0,0 +1,68 @@
+use poise::CreateReply;
+
+use crate::music::queue;
+use crate::utils::{embed, voice};
+use crate::{Context, Error};
+
+async fn summon_impl(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let target = match voice::user_channel(ctx.serenity_context(), guild_id, ctx.author().id) {
+        Some(id) => id,
+        None => {
+            ctx.send(CreateReply::default().embed(embed::error("음성 채널에 먼저 접속해주세요!")))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let current = queue::get_voice_channel(&ctx.data().queue_manager, guild_id).await;
+    match current {
+        None => {
+            ctx.send(CreateReply::default().embed(embed::error(
+                "봇이 음성 채널에 없습니다. `/join`이나 `/play`를 사용해주세요.",
+            )))
+            .await?;
+        }
+        Some(current) if current == target => {
+            ctx.say("🔊 이미 같은 음성 채널에 있습니다.").await?;
+        }
+        Some(_) => {
+            // 연결을 유지한 채 이동하므로 재생 중인 곡은 끊기지 않음
+            match voice::join(
+                ctx.serenity_context(),
+                &ctx.data().queue_manager,
+                guild_id,
+                target,
+            )
+            .await
+            {
+                Ok(_) => {
+                    ctx.say(format!("🔊 <#{target}>(으)로 이동했습니다."))
+                        .await?;
+                }
+                Err(e) => {
+                    ctx.send(
+                        CreateReply::default()
+                            .embed(embed::error(&format!("음성 채널 이동 실패: {e}"))),
+                    )
+                    .await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 재생을 유지한 채 봇을 내 음성 채널로 옮깁니다
+#[poise::command(slash_command, guild_only)]
+pub async fn summon(ctx: Context<'_>) -> Result<(), Error> {
+    summon_impl(ctx).await
+}
+
+/// 재생을 유지한 채 봇을 내 음성 채널로 옮깁니다 (/summon 단축)
+#[poise::command(slash_command, guild_only)]
+pub async fn sm(ctx: Context<'_>) -> Result<(), Error> {
+    summon_impl(ctx).await
+}
@@ -1,3 +1,5 @@
+use std::cmp::Reverse;
+
 use poise::serenity_prelude as serenity;
 use poise::CreateReply;
 use serde::Deserialize;
@@ -48,11 +50,9 @@ impl AmmoSort {
 
     fn sort(self, ammo_list: &mut [Ammo]) {
         match self {
-            AmmoSort::Penetration => {
-                ammo_list.sort_by(|a, b| b.penetration_power.cmp(&a.penetration_power))
-            }
-            AmmoSort::Damage => ammo_list.sort_by(|a, b| b.damage.cmp(&a.damage)),
-            AmmoSort::ArmorDamage => ammo_list.sort_by(|a, b| b.armor_damage.cmp(&a.armor_damage)),
+            AmmoSort::Penetration => ammo_list.sort_by_key(|a| Reverse(a.penetration_power)),
+            AmmoSort::Damage => ammo_list.sort_by_key(|a| Reverse(a.damage)),
+            AmmoSort::ArmorDamage => ammo_list.sort_by_key(|a| Reverse(a.armor_damage)),
         }
     }
 }
@@ -0,0 +1,98 @@
+use poise::CreateReply;
+
+use crate::music::{queue, QueueEdit};
+use crate::utils::{embed, voice};
+use crate::{Context, Error};
+
+async fn undo_impl(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    let Some(edit) = queue::last_edit(&ctx.data().queue_manager, guild_id).await else {
+        ctx.send(CreateReply::default().embed(embed::error("되돌릴 작업이 없습니다.")))
+            .await?;
+        return Ok(());
+    };
+
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Songbird 미등록");
+
+    let call = match manager.get(guild_id) {
+        Some(call) => {
+            if !super::ensure_same_channel(ctx, guild_id).await? {
+                return Ok(());
+            }
+            call
+        }
+        // 정지하면서 퇴장했으므로 사용자가 있는 채널로 다시 접속
+        None if edit == QueueEdit::Stop => {
+            let Some(channel_id) =
+                voice::user_channel(ctx.serenity_context(), guild_id, ctx.author().id)
+            else {
+                ctx.send(
+                    CreateReply::default().embed(embed::error("음성 채널에 먼저 접속해주세요!")),
+                )
+                .await?;
+                return Ok(());
+            };
+
+            ctx.defer().await?;
+            match voice::join(
+                ctx.serenity_context(),
+                &ctx.data().queue_manager,
+                guild_id,
+                channel_id,
+            )
+            .await
+            {
+                Ok(call) => call,
+                Err(e) => {
+                    ctx.send(
+                        CreateReply::default()
+                            .embed(embed::error(&format!("음성 채널 접속 실패: {e}"))),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            }
+        }
+        None => {
+            ctx.send(CreateReply::default().embed(embed::error("봇이 음성 채널에 없습니다.")))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let Some(edit) = queue::undo(&ctx.data().queue_manager, guild_id).await else {
+        ctx.send(CreateReply::default().embed(embed::error("되돌릴 작업이 없습니다.")))
+            .await?;
+        return Ok(());
+    };
+
+    let mut message = format!("↩️ **{edit}** 작업을 되돌렸습니다.");
+    // 정지를 되돌린 경우에만 재생을 다시 시작 (그 외에는 재생 중인 곡을 그대로 둠)
+    if edit == QueueEdit::Stop
+        && queue::get_current(&ctx.data().queue_manager, guild_id)
+            .await
+            .is_none()
+    {
+        if let Some(song) = super::play::start_playback(ctx, guild_id, &call).await? {
+            message.push_str(&format!("\n▶️ **{}** 부터 다시 재생합니다.", song.title));
+        }
+    }
+
+    ctx.say(message).await?;
+    Ok(())
+}
+
+/// 마지막 대기열 변경(셔플, 제거, 이동, 정지)을 되돌립니다
+#[poise::command(slash_command, guild_only)]
+pub async fn undo(ctx: Context<'_>) -> Result<(), Error> {
+    undo_impl(ctx).await
+}
+
+/// 마지막 대기열 변경을 되돌립니다 (/undo 단축)
+#[poise::command(slash_command, guild_only)]
+pub async fn u(ctx: Context<'_>) -> Result<(), Error> {
+    undo_impl(ctx).await
+}
@@ -0,0 +1,30 @@
+use poise::CreateReply;
+
+use crate::music::queue;
+use crate::utils::embed;
+use crate::{Context, Error};
+
+async fn unshuffle_impl(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
+
+    if queue::unshuffle(&ctx.data().queue_manager, guild_id).await {
+        ctx.say("↩️ 셔플을 끄고 원래 순서로 되돌렸습니다.").await?;
+    } else {
+        ctx.send(CreateReply::default().embed(embed::error("셔플 모드가 아닙니다.")))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// 셔플을 끄고 원래 순서로 되돌립니다
+#[poise::command(slash_command, guild_only)]
+pub async fn unshuffle(ctx: Context<'_>) -> Result<(), Error> {
+    unshuffle_impl(ctx).await
+}
+
+/// 셔플을 끄고 원래 순서로 되돌립니다 (/unshuffle 단축)
+#[poise::command(slash_command, guild_only)]
+pub async fn ush(ctx: Context<'_>) -> Result<(), Error> {
+    unshuffle_impl(ctx).await
+}
@@ -1,31 +1,44 @@
 use poise::CreateReply;
 
-use crate::music::queue;
+use crate::music::{queue, VolumeChange};
 use crate::utils::embed;
 use crate::{Context, Error};
 
-async fn volume_impl(ctx: Context<'_>, level: u32) -> Result<(), Error> {
+async fn volume_impl(ctx: Context<'_>, level: String) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("서버에서만 사용할 수 있습니다")?;
 
-    if level > 100 {
-        ctx.send(CreateReply::default().embed(embed::error("볼륨은 0~100 사이로 설정해주세요.")))
+    let before = queue::get_volume(&ctx.data().queue_manager, guild_id).await;
+    let current = (before * 100.0).round() as u32;
+
+    let level = match VolumeChange::parse(&level).and_then(|change| change.apply(current)) {
+        Some(level) => level,
+        None => {
+            ctx.send(CreateReply::default().embed(embed::error(
+                "볼륨은 0~100 사이 또는 `+10`, `-10`처럼 입력해주세요.",
+            )))
             .await?;
-        return Ok(());
-    }
+            return Ok(());
+        }
+    };
 
+    // 재생 중인 트랙에도 즉시 적용되고, 다음 재생을 위해 서버별로 저장
     let volume = level as f32 / 100.0;
     queue::set_volume(&ctx.data().queue_manager, guild_id, volume).await;
+    if let Some(settings) = &ctx.data().guild_settings {
+        settings.set_volume(guild_id, volume);
+    }
 
-    ctx.say(format!("🔊 볼륨: **{level}%**")).await?;
+    ctx.say(format!("🔊 볼륨: {current}% → **{level}%**"))
+        .await?;
 
     Ok(())
 }
 
-/// 볼륨을 조절합니다
+/// 볼륨을 조절합니다 (0-100, +10/-10으로 상대 조절)
 #[poise::command(slash_command, guild_only)]
 pub async fn volume(
     ctx: Context<'_>,
-    #[description = "볼륨 (0-100)"] level: u32,
+    #[description = "볼륨 (0-100 또는 +10/-10)"] level: String,
 ) -> Result<(), Error> {
     volume_impl(ctx, level).await
 }
@@ -33,7 +46,8 @@ pub async fn volume(
 /// 볼륨을 조절합니다 (/volume 단축)
 #[poise::command(slash_command, guild_only)]
 pub async fn v(
-    ctx: Context<'_>, #[description = "볼륨 (0-100)"] level: u32
+    ctx: Context<'_>,
+    #[description = "볼륨 (0-100 또는 +10/-10)"] level: String,
 ) -> Result<(), Error> {
     volume_impl(ctx, level).await
 }
@@ -3,6 +3,30 @@ pub struct Config {
     pub gemini_api_key: Option<String>,
     pub tv_channel_id: Option<u64>,
     pub db_path: String,
+    /// 명령어별 쿨다운 덮어쓰기 (예: `play:user=3/15,guild=10/30;ai:user=1/10`)
+    pub cooldowns: Option<String>,
+    /// yt-dlp 실행 파일 경로
+    pub ytdlp_path: String,
+    /// yt-dlp에 항상 붙일 추가 인자 (공백으로 구분)
+    pub ytdlp_args: Vec<String>,
+    /// YouTube 로그인 쿠키 파일 (`--cookies`)
+    pub ytdlp_cookies: Option<String>,
+    /// yt-dlp 프록시 (`--proxy`)
+    pub ytdlp_proxy: Option<String>,
+    /// 동시에 실행할 yt-dlp 프로세스 수
+    pub ytdlp_max_concurrent: usize,
+    /// yt-dlp 호출 제한 시간 (초)
+    pub ytdlp_timeout_secs: u64,
+    /// 곡 정보 캐시 유지 시간 (초)
+    pub song_cache_ttl_secs: u64,
+    /// 로컬 가사 파일(`.lrc`/`.txt`) 디렉터리
+    pub lyrics_dir: Option<String>,
+    /// 듣는 사람이 없을 때 일시정지한 뒤 퇴장까지 기다릴 시간 (초)
+    pub auto_leave_secs: u64,
+    /// HTTP 제어 API 토큰 (없으면 API 서버를 띄우지 않음)
+    pub api_token: Option<String>,
+    /// HTTP 제어 API 주소
+    pub api_addr: String,
 }
 
 impl Config {
@@ -16,6 +40,36 @@ impl Config {
                 .and_then(|v| v.parse().ok()),
             db_path: std::env::var("EVERYBOT_DB_PATH")
                 .unwrap_or_else(|_| "everybot.db".to_string()),
+            cooldowns: std::env::var("EVERYBOT_COOLDOWNS").ok(),
+            ytdlp_path: std::env::var("EVERYBOT_YTDLP_PATH")
+                .unwrap_or_else(|_| "yt-dlp".to_string()),
+            ytdlp_args: std::env::var("EVERYBOT_YTDLP_ARGS")
+                .map(|v| v.split_whitespace().map(String::from).collect())
+                .unwrap_or_default(),
+            ytdlp_cookies: std::env::var("EVERYBOT_YTDLP_COOKIES").ok(),
+            ytdlp_proxy: std::env::var("EVERYBOT_YTDLP_PROXY").ok(),
+            ytdlp_max_concurrent: std::env::var("EVERYBOT_YTDLP_MAX_CONCURRENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(4),
+            ytdlp_timeout_secs: std::env::var("EVERYBOT_YTDLP_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            song_cache_ttl_secs: std::env::var("EVERYBOT_SONG_CACHE_TTL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(24 * 3600),
+            lyrics_dir: std::env::var("EVERYBOT_LYRICS_DIR").ok(),
+            auto_leave_secs: std::env::var("EVERYBOT_AUTO_LEAVE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            api_token: std::env::var("EVERYBOT_API_TOKEN")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            api_addr: std::env::var("EVERYBOT_API_ADDR")
+                .unwrap_or_else(|_| "127.0.0.1:8787".to_string()),
         }
     }
 }
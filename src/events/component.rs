@@ -2,27 +2,45 @@ use poise::serenity_prelude as serenity;
 use serenity::builder::{
     CreateActionRow, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
 };
-use serenity::model::application::ComponentInteraction;
+use serenity::model::application::{ComponentInteraction, ComponentInteractionDataKind};
 use serenity::model::id::GuildId;
+use songbird::Songbird;
 
-use crate::music::{player, queue};
-use crate::utils::{components, embed};
+use crate::music::eq::EqBands;
+use crate::music::{player, queue, radio};
+use crate::utils::components::{self, ControllerState, QueueAction};
+use crate::utils::{embed, voice};
 use crate::{Data, Error};
 
-async fn respond_ephemeral(
+/// 볼륨 버튼 한 번에 바뀌는 양
+const VOLUME_STEP: f32 = 0.1;
+
+const STALE_QUEUE_MESSAGE: &str = "대기열이 변경되었습니다. 대기열 메뉴에서 다시 선택해주세요.";
+
+async fn send_ephemeral(
     ctx: &serenity::Context,
     interaction: &ComponentInteraction,
-    message: &str,
+    embed: CreateEmbed,
+    components: Vec<CreateActionRow>,
 ) -> Result<(), Error> {
     let response = CreateInteractionResponse::Message(
         CreateInteractionResponseMessage::new()
-            .embed(embed::error(message))
+            .embed(embed)
+            .components(components)
             .ephemeral(true),
     );
     interaction.create_response(&ctx.http, response).await?;
     Ok(())
 }
 
+async fn respond_ephemeral(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    message: &str,
+) -> Result<(), Error> {
+    send_ephemeral(ctx, interaction, embed::error(message), vec![]).await
+}
+
 async fn update_message(
     ctx: &serenity::Context,
     interaction: &ComponentInteraction,
@@ -38,19 +56,48 @@ async fn update_message(
     Ok(())
 }
 
-async fn is_track_paused(data: &Data, guild_id: GuildId) -> bool {
-    let handle = {
-        let queues = data.queue_manager.read().await;
-        queues.get(&guild_id).and_then(|q| q.track_handle.clone())
-    };
-    match handle {
-        Some(h) => h
-            .get_info()
-            .await
-            .map(|info| info.playing == songbird::tracks::PlayMode::Pause)
-            .unwrap_or(false),
-        None => false,
+/// 컨트롤러 메시지를 현재 재생 상태로 갱신합니다.
+///
+/// `paused`가 주어지면 트랙 상태 대신 그 값을 사용합니다 (일시정지 직후에는
+/// 드라이버에 아직 반영되지 않았을 수 있으므로).
+async fn refresh_controller(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    guild_id: GuildId,
+    paused: Option<bool>,
+) -> Result<(), Error> {
+    let mut state = ControllerState::load(&data.queue_manager, guild_id).await;
+    if let Some(paused) = paused {
+        state.is_paused = paused;
     }
+
+    let current = queue::get_current(&data.queue_manager, guild_id).await;
+    let volume = queue::get_volume(&data.queue_manager, guild_id).await;
+    let (upcoming, version) = queue::get_upcoming(&data.queue_manager, guild_id).await;
+
+    let e = match current {
+        Some(song) => {
+            let mut e = embed::now_playing(&song);
+            if state.is_paused {
+                e = e.title("⏸️ 일시정지");
+            }
+            e.field(
+                "볼륨",
+                format!("{}%", (volume * 100.0).round() as u32),
+                true,
+            )
+        }
+        None => embed::error("재생 중인 곡이 없습니다."),
+    };
+
+    update_message(
+        ctx,
+        interaction,
+        e,
+        components::music_components(&state, &upcoming, version),
+    )
+    .await
 }
 
 pub async fn handle(
@@ -62,7 +109,11 @@ pub async fn handle(
 
     // Tarkov interactions are handled by each command's ComponentInteractionCollector.
     // Do NOT respond here — the collector will pick them up.
-    if custom_id.starts_with("tarkov_") {
+    // /stats, /lyrics 화면도 명령어 안의 collector가 처리합니다.
+    if ["tarkov_", "stats_", "lyrics_"]
+        .iter()
+        .any(|prefix| custom_id.starts_with(prefix))
+    {
         return Ok(());
     }
 
@@ -71,86 +122,96 @@ pub async fn handle(
         .guild_id
         .ok_or("서버에서만 사용할 수 있습니다")?;
 
+    // 즐겨찾기는 재생을 바꾸지 않으므로 음성 채널에 없어도 저장할 수 있음
+    if custom_id == "music_favorite" {
+        return save_favorite(ctx, interaction, data, guild_id).await;
+    }
+
+    // 대기열 보기도 읽기 전용이라 /queue처럼 누구나 볼 수 있음
+    if custom_id == "music_queue_view" {
+        let (current, songs) = queue::get_queue_list(&data.queue_manager, guild_id).await;
+        let total_pages = embed::queue_page_count(songs.len());
+        return send_ephemeral(
+            ctx,
+            interaction,
+            embed::queue_list(current.as_ref(), &songs, 1),
+            vec![components::queue_page_row(1, total_pages)],
+        )
+        .await;
+    }
+    if let Some(page) = components::parse_queue_page(custom_id) {
+        let (current, songs) = queue::get_queue_list(&data.queue_manager, guild_id).await;
+        let total_pages = embed::queue_page_count(songs.len());
+        let page = page.min(total_pages);
+        return update_message(
+            ctx,
+            interaction,
+            embed::queue_list(current.as_ref(), &songs, page),
+            vec![components::queue_page_row(page, total_pages)],
+        )
+        .await;
+    }
+
     let manager = songbird::get(ctx).await.expect("Songbird 미등록");
 
-    // Check bot is in a voice channel
-    let bot_channel = {
-        let handler_lock = match manager.get(guild_id) {
-            Some(h) => h,
-            None => {
-                respond_ephemeral(ctx, interaction, "봇이 음성 채널에 없습니다.").await?;
-                return Ok(());
-            }
-        };
-        let handler = handler_lock.lock().await;
-        handler.current_channel()
-    };
+    // Same check as the playback slash commands
+    if let Err(msg) = voice::check_same_channel(ctx, guild_id, interaction.user.id).await {
+        respond_ephemeral(ctx, interaction, msg).await?;
+        return Ok(());
+    }
 
-    // Check user is in the same voice channel
-    let user_in_bot_channel = {
-        let guild = ctx
-            .cache
-            .guild(guild_id)
-            .ok_or("서버 정보를 가져올 수 없습니다")?;
-        match bot_channel {
-            Some(bot_ch) => guild
-                .voice_states
-                .get(&interaction.user.id)
-                .and_then(|vs| vs.channel_id)
-                .is_some_and(|ch| ch.get() == bot_ch.0.get()),
-            None => false,
+    if let Some(name) = components::parse_eq_preset(custom_id) {
+        let Some(bands) = EqBands::preset(name) else {
+            return Ok(());
+        };
+        queue::set_equalizer(&data.queue_manager, guild_id, bands).await;
+        if let Some(settings) = &data.guild_settings {
+            settings.set_equalizer(guild_id, &bands);
         }
-    };
-
-    if !user_in_bot_channel {
-        respond_ephemeral(ctx, interaction, "봇과 같은 음성 채널에 있어야 합니다.").await?;
+        update_message(
+            ctx,
+            interaction,
+            embed::equalizer(&bands),
+            vec![components::equalizer_row(&bands)],
+        )
+        .await?;
         return Ok(());
     }
 
+    if let Some((action, version, index)) = QueueAction::parse(custom_id) {
+        return handle_queue_action(
+            ctx,
+            interaction,
+            data,
+            &manager,
+            guild_id,
+            (action, version, index),
+        )
+        .await;
+    }
+
     match custom_id {
         "music_pause" => {
-            let queues = data.queue_manager.read().await;
-            if let Some(h) = queues.get(&guild_id).and_then(|q| q.track_handle.as_ref()) {
+            if let Some(h) = queue::get_track_handle(&data.queue_manager, guild_id).await {
                 let _ = h.pause();
             }
-            drop(queues);
-
-            let current = queue::get_current(&data.queue_manager, guild_id).await;
-            let (_, upcoming) = queue::get_queue_list(&data.queue_manager, guild_id).await;
-            let e = match current {
-                Some(song) => embed::now_playing(&song).title("⏸️ 일시정지"),
-                None => embed::error("재생 중인 곡이 없습니다."),
-            };
-            update_message(
-                ctx,
-                interaction,
-                e,
-                components::music_components(true, &upcoming),
-            )
-            .await?;
+            refresh_controller(ctx, interaction, data, guild_id, Some(true)).await?;
         }
         "music_resume" => {
-            let queues = data.queue_manager.read().await;
-            if let Some(h) = queues.get(&guild_id).and_then(|q| q.track_handle.as_ref()) {
+            if let Err(msg) = data.services.quiz_games.check_music(guild_id) {
+                respond_ephemeral(ctx, interaction, msg).await?;
+                return Ok(());
+            }
+            if let Some(h) = queue::get_track_handle(&data.queue_manager, guild_id).await {
                 let _ = h.play();
             }
-            drop(queues);
-
-            let current = queue::get_current(&data.queue_manager, guild_id).await;
-            let (_, upcoming) = queue::get_queue_list(&data.queue_manager, guild_id).await;
-            let e = match current {
-                Some(song) => embed::now_playing(&song),
-                None => embed::error("재생 중인 곡이 없습니다."),
-            };
-            update_message(
-                ctx,
-                interaction,
-                e,
-                components::music_components(false, &upcoming),
-            )
-            .await?;
+            refresh_controller(ctx, interaction, data, guild_id, Some(false)).await?;
         }
         "music_skip" => {
+            if let Err(msg) = radio::check_skippable(&data.queue_manager, guild_id).await {
+                respond_ephemeral(ctx, interaction, msg).await?;
+                return Ok(());
+            }
             let call = match manager.get(guild_id) {
                 Some(c) => c,
                 None => {
@@ -161,32 +222,28 @@ pub async fn handle(
                 }
             };
 
-            match player::play_next(
-                guild_id,
-                &data.queue_manager,
-                &data.http_client,
-                &call,
-                true,
-            )
-            .await
+            match player::play_next(guild_id, &data.queue_manager, &data.services, &call, true)
+                .await
             {
                 Ok(()) => {
-                    let next = queue::get_current(&data.queue_manager, guild_id).await;
-                    let (_, upcoming) = queue::get_queue_list(&data.queue_manager, guild_id).await;
-                    let (e, comps) = match next {
-                        Some(song) => (
-                            embed::now_playing(&song),
-                            components::music_components(false, &upcoming),
-                        ),
-                        None => (
-                            CreateEmbed::new()
-                                .title("⏭️ 스킵 완료")
-                                .description("큐가 비어있습니다.")
-                                .color(0x5865F2),
+                    if queue::get_current(&data.queue_manager, guild_id)
+                        .await
+                        .is_some()
+                    {
+                        refresh_controller(ctx, interaction, data, guild_id, Some(false)).await?;
+                    } else {
+                        let e = CreateEmbed::new()
+                            .title("⏭️ 스킵 완료")
+                            .description("큐가 비어있습니다.")
+                            .color(0x5865F2);
+                        update_message(
+                            ctx,
+                            interaction,
+                            e,
                             components::music_components_disabled(),
-                        ),
-                    };
-                    update_message(ctx, interaction, e, comps).await?;
+                        )
+                        .await?;
+                    }
                 }
                 Err(e) => {
                     respond_ephemeral(ctx, interaction, &format!("스킵 실패: {e}")).await?;
@@ -194,6 +251,7 @@ pub async fn handle(
             }
         }
         "music_stop" => {
+            player::record_skipped(guild_id, &data.queue_manager, &data.services).await;
             queue::clear(&data.queue_manager, guild_id).await;
             let _ = manager.remove(guild_id).await;
 
@@ -203,26 +261,82 @@ pub async fn handle(
                 .color(0xED4245);
             update_message(ctx, interaction, e, components::music_components_disabled()).await?;
         }
+        "music_previous" => {
+            let call = match manager.get(guild_id) {
+                Some(c) => c,
+                None => {
+                    respond_ephemeral(ctx, interaction, "봇이 음성 채널에 없습니다.").await?;
+                    return Ok(());
+                }
+            };
+
+            match player::play_previous(guild_id, &data.queue_manager, &data.services, &call).await
+            {
+                Ok(Some(_)) => {
+                    refresh_controller(ctx, interaction, data, guild_id, Some(false)).await?;
+                }
+                Ok(None) => {
+                    respond_ephemeral(ctx, interaction, "이전 곡이 없습니다.").await?;
+                }
+                Err(e) => {
+                    respond_ephemeral(ctx, interaction, &format!("이전 곡 재생 실패: {e}")).await?;
+                }
+            }
+        }
+        "music_loop" => {
+            let mode = queue::get_loop_mode(&data.queue_manager, guild_id).await;
+            queue::set_loop_mode(&data.queue_manager, guild_id, mode.next()).await;
+            refresh_controller(ctx, interaction, data, guild_id, None).await?;
+        }
+        "music_shuffle" => {
+            if !queue::unshuffle(&data.queue_manager, guild_id).await {
+                queue::shuffle(&data.queue_manager, guild_id).await;
+            }
+            refresh_controller(ctx, interaction, data, guild_id, None).await?;
+        }
+        "music_volume_down" | "music_volume_up" => {
+            let step = if custom_id == "music_volume_up" {
+                VOLUME_STEP
+            } else {
+                -VOLUME_STEP
+            };
+            let volume = queue::get_volume(&data.queue_manager, guild_id).await;
+            let volume = ((volume + step).clamp(0.0, 1.0) * 100.0).round() / 100.0;
+            queue::set_volume(&data.queue_manager, guild_id, volume).await;
+            if let Some(settings) = &data.guild_settings {
+                settings.set_volume(guild_id, volume);
+            }
+            refresh_controller(ctx, interaction, data, guild_id, None).await?;
+        }
         "music_queue_select" => {
-            // Informational dropdown - refresh message with current state
-            let is_paused = is_track_paused(data, guild_id).await;
-            let current = queue::get_current(&data.queue_manager, guild_id).await;
-            let (_, upcoming) = queue::get_queue_list(&data.queue_manager, guild_id).await;
-            let e = match current {
-                Some(song) => {
-                    let mut e = embed::now_playing(&song);
-                    if is_paused {
-                        e = e.title("⏸️ 일시정지");
-                    }
-                    e
+            let value = match &interaction.data.kind {
+                ComponentInteractionDataKind::StringSelect { values } => values.first().cloned(),
+                _ => None,
+            };
+            let (version, index) = match value.as_deref().and_then(components::parse_queue_option) {
+                Some(v) => v,
+                None => return Ok(()),
+            };
+
+            let (upcoming, current_version) =
+                queue::get_upcoming(&data.queue_manager, guild_id).await;
+            let song = match upcoming.get(index) {
+                Some(song) if current_version == version => song,
+                _ => {
+                    respond_ephemeral(ctx, interaction, STALE_QUEUE_MESSAGE).await?;
+                    return Ok(());
                 }
-                None => embed::error("재생 중인 곡이 없습니다."),
             };
-            update_message(
+
+            let e = CreateEmbed::new()
+                .title(format!("🎵 대기열 #{} 선택됨", index + 1))
+                .description(format!("[{}]({})", song.title, song.url))
+                .color(0x5865F2);
+            send_ephemeral(
                 ctx,
                 interaction,
                 e,
-                components::music_components(is_paused, &upcoming),
+                vec![components::queue_action_row(version, index)],
             )
             .await?;
         }
@@ -231,3 +345,137 @@ pub async fn handle(
 
     Ok(())
 }
+
+/// ❤️ 버튼: 지금 재생 중인 곡을 누른 사용자의 즐겨찾기에 저장
+async fn save_favorite(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    guild_id: GuildId,
+) -> Result<(), Error> {
+    let Some(favorites) = &data.favorites else {
+        return respond_ephemeral(ctx, interaction, "즐겨찾기를 사용할 수 없습니다.").await;
+    };
+    let Some(song) = queue::get_current(&data.queue_manager, guild_id).await else {
+        return respond_ephemeral(ctx, interaction, "재생 중인 곡이 없습니다.").await;
+    };
+
+    let description = if favorites.add(interaction.user.id, &song) {
+        format!(
+            "[{}]({})\n`/favorites list`로 확인할 수 있습니다.",
+            song.title, song.url
+        )
+    } else {
+        format!(
+            "[{}]({})\n이미 즐겨찾기에 있는 곡입니다.",
+            song.title, song.url
+        )
+    };
+    let e = CreateEmbed::new()
+        .title("❤️ 즐겨찾기에 저장")
+        .description(description)
+        .color(0xED4245);
+    send_ephemeral(ctx, interaction, e, vec![]).await
+}
+
+/// 대기열 선택 후 ephemeral 메시지의 동작 버튼 처리
+async fn handle_queue_action(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+    manager: &Songbird,
+    guild_id: GuildId,
+    (action, version, index): (QueueAction, u64, usize),
+) -> Result<(), Error> {
+    let position = index + 1;
+    let stale = || update_message(ctx, interaction, embed::error(STALE_QUEUE_MESSAGE), vec![]);
+
+    // 버전 확인과 변경은 같은 잠금 안에서 (queue::*_version)
+    let e = match action {
+        QueueAction::Details => {
+            let (upcoming, current_version) =
+                queue::get_upcoming(&data.queue_manager, guild_id).await;
+            let Some(song) = upcoming.get(index).filter(|_| current_version == version) else {
+                return stale().await;
+            };
+            update_message(
+                ctx,
+                interaction,
+                embed::queue_entry(song, position),
+                vec![components::queue_action_row(version, index)],
+            )
+            .await?;
+            return Ok(());
+        }
+        QueueAction::Remove => {
+            let Some(song) =
+                queue::remove_at_version(&data.queue_manager, guild_id, version, position).await
+            else {
+                return stale().await;
+            };
+            CreateEmbed::new()
+                .title("🗑️ 제거됨")
+                .description(format!("[{}]({}) (#{position})", song.title, song.url))
+                .color(0x5865F2)
+        }
+        QueueAction::MoveNext => {
+            let Some(song) =
+                queue::move_to_version(&data.queue_manager, guild_id, version, position, 1).await
+            else {
+                return stale().await;
+            };
+            CreateEmbed::new()
+                .title("⏫ 다음 곡으로 이동")
+                .description(format!("[{}]({})", song.title, song.url))
+                .color(0x5865F2)
+        }
+        QueueAction::Jump => {
+            let allowed = match data.services.quiz_games.check_music(guild_id) {
+                Ok(()) => radio::check_skippable(&data.queue_manager, guild_id).await,
+                Err(msg) => Err(msg),
+            };
+            if let Err(msg) = allowed {
+                update_message(ctx, interaction, embed::error(msg), vec![]).await?;
+                return Ok(());
+            }
+            let call = match manager.get(guild_id) {
+                Some(c) => c,
+                None => {
+                    update_message(
+                        ctx,
+                        interaction,
+                        embed::error("봇이 음성 채널에 없습니다."),
+                        vec![],
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            };
+
+            let Some(song) =
+                queue::move_to_version(&data.queue_manager, guild_id, version, position, 1).await
+            else {
+                return stale().await;
+            };
+            if let Err(e) =
+                player::play_next(guild_id, &data.queue_manager, &data.services, &call, true).await
+            {
+                update_message(
+                    ctx,
+                    interaction,
+                    embed::error(&format!("재생 실패: {e}")),
+                    vec![],
+                )
+                .await?;
+                return Ok(());
+            }
+
+            CreateEmbed::new()
+                .title("▶️ 바로 재생")
+                .description(format!("[{}]({})", song.title, song.url))
+                .color(0x1DB954)
+        }
+    };
+
+    update_message(ctx, interaction, e, vec![]).await
+}
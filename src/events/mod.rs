@@ -1,4 +1,5 @@
 pub mod component;
+pub mod ready;
 pub mod voice_state;
 
 use poise::serenity_prelude as serenity;
@@ -12,6 +13,10 @@ pub async fn handler(
     data: &Data,
 ) -> Result<(), Error> {
     match event {
+        serenity::FullEvent::Ready { .. } => {
+            ready::restore_policies(data).await;
+            ready::rejoin_pinned(ctx, data).await;
+        }
         serenity::FullEvent::Message { new_message } => {
             crate::ai::channel::handle(ctx, new_message, data).await?;
         }
@@ -0,0 +1,52 @@
+use poise::serenity_prelude as serenity;
+use tracing::{info, warn};
+
+use crate::music::{player, queue};
+use crate::utils::voice;
+use crate::Data;
+
+/// 저장된 서버별 곡 추가 정책을 대기열에 적용합니다
+pub async fn restore_policies(data: &Data) {
+    let Some(settings) = &data.guild_settings else {
+        return;
+    };
+    for (guild_id, policy) in settings.all_enqueue_policies() {
+        queue::set_policy(&data.queue_manager, guild_id, policy).await;
+    }
+}
+
+/// 24/7 모드로 고정된 채널에 다시 접속합니다
+///
+/// 재연결 시에도 Ready가 다시 오므로, 이미 재생 중인 서버는 건드리지 않습니다.
+pub async fn rejoin_pinned(ctx: &serenity::Context, data: &Data) {
+    let stays = match &data.guild_settings {
+        Some(settings) => settings.all_stays(),
+        None => return,
+    };
+
+    for stay in stays {
+        let guild_id = stay.guild_id;
+        if queue::get_current(&data.queue_manager, guild_id)
+            .await
+            .is_some()
+        {
+            continue;
+        }
+
+        let call = match voice::join(ctx, &data.queue_manager, guild_id, stay.channel_id).await {
+            Ok(call) => call,
+            Err(e) => {
+                warn!("24/7 채널 재접속 실패 (guild: {guild_id}): {e}");
+                continue;
+            }
+        };
+        info!("24/7 채널에 재접속했습니다 (guild: {guild_id})");
+
+        queue::set_idle_fallback(&data.queue_manager, guild_id, stay.fallback).await;
+        if let Err(e) =
+            player::play_next(guild_id, &data.queue_manager, &data.services, &call, true).await
+        {
+            warn!("24/7 재생 시작 실패 (guild: {guild_id}): {e}");
+        }
+    }
+}
@@ -1,9 +1,34 @@
 use poise::serenity_prelude as serenity;
+use songbird::tracks::PlayMode;
 use tracing::info;
 
-use crate::music::queue;
+use crate::music::{player, queue};
 use crate::Data;
 
+/// 봇 채널에서 실제로 듣고 있는 사람 수 (봇과 스피커를 끈 사람은 제외)
+fn listener_count(
+    ctx: &serenity::Context,
+    guild_id: serenity::GuildId,
+    channel_id: serenity::ChannelId,
+) -> Option<usize> {
+    let guild = ctx.cache.guild(guild_id)?;
+    let count = guild
+        .voice_states
+        .values()
+        .filter(|vs| vs.channel_id == Some(channel_id))
+        .filter(|vs| !(vs.deaf || vs.self_deaf))
+        .filter(|vs| {
+            let is_bot = vs
+                .member
+                .as_ref()
+                .or_else(|| guild.members.get(&vs.user_id))
+                .is_some_and(|m| m.user.bot);
+            !is_bot && vs.user_id != ctx.cache.current_user().id
+        })
+        .count();
+    Some(count)
+}
+
 pub async fn handle(
     ctx: &serenity::Context,
     _old: &Option<serenity::VoiceState>,
@@ -15,6 +40,19 @@ pub async fn handle(
         None => return Ok(()),
     };
 
+    // 봇이 직접 옮겨지거나 연결이 끊긴 경우 큐의 채널 정보도 맞춰 갱신
+    if new.user_id == ctx.cache.current_user().id {
+        match new.channel_id {
+            Some(channel_id) => {
+                queue::set_voice_channel(&data.queue_manager, guild_id, Some(channel_id)).await
+            }
+            None => {
+                player::record_skipped(guild_id, &data.queue_manager, &data.services).await;
+                queue::detach(&data.queue_manager, guild_id).await;
+            }
+        }
+    }
+
     let manager = songbird::get(ctx).await.expect("Songbird 미등록");
 
     let handler_lock = match manager.get(guild_id) {
@@ -24,62 +62,79 @@ pub async fn handle(
 
     let handler = handler_lock.lock().await;
     let bot_channel = match handler.current_channel() {
-        Some(ch) => ch,
+        Some(ch) => serenity::ChannelId::new(ch.0.get()),
         None => return Ok(()),
     };
     drop(handler);
 
-    // Count members in the bot's voice channel
-    let member_count = {
-        let guild = match ctx.cache.guild(guild_id) {
-            Some(g) => g,
-            None => return Ok(()),
-        };
-
-        guild
-            .voice_states
-            .values()
-            .filter(|vs| {
-                vs.channel_id
-                    .is_some_and(|ch| ch.get() == bot_channel.0.get())
-            })
-            .count()
+    let Some(listeners) = listener_count(ctx, guild_id, bot_channel) else {
+        return Ok(());
     };
+    let queue_manager = &data.queue_manager;
 
-    // If bot is alone (only bot in channel), start leave timer
-    if member_count <= 1 {
-        let manager = manager.clone();
-        let queue_manager = data.queue_manager.clone();
-
-        tokio::spawn(async move {
-            tokio::time::sleep(std::time::Duration::from_secs(30)).await;
-
-            // Re-check if still alone
-            let still_alone = {
-                let handler_lock = match manager.get(guild_id) {
-                    Some(h) => h,
-                    None => return,
-                };
-                let handler = handler_lock.lock().await;
-                let current_channel = match handler.current_channel() {
-                    Some(ch) => ch,
-                    None => return,
-                };
-                drop(handler);
-
-                // We can't easily re-check member count without cache access here
-                // so we just check if we're still in the channel
-                let _ = current_channel;
-                true // Simplified: always leave after 30s alone
-            };
-
-            if still_alone {
-                info!("음성 채널에 혼자 남아 퇴장합니다 (guild: {guild_id})");
-                queue::clear(&queue_manager, guild_id).await;
-                let _ = manager.remove(guild_id).await;
+    // 누군가 돌아오면 퇴장을 취소하고, 자동으로 멈췄던 곡만 이어서 재생 (퀴즈 중에는 끝난 뒤에)
+    if listeners > 0 {
+        queue::cancel_idle_timer(queue_manager, guild_id).await;
+        if !data.services.quiz_games.is_running(guild_id)
+            && queue::take_auto_paused(queue_manager, guild_id).await
+        {
+            if let Some(track) = queue::get_track_handle(queue_manager, guild_id).await {
+                let _ = track.play();
+                info!("듣는 사람이 돌아와 재생을 재개합니다 (guild: {guild_id})");
             }
-        });
+        }
+        return Ok(());
+    }
+
+    // 아무도 듣지 않으면 재생 중인 곡을 일시정지 (이미 직접 멈춘 곡은 그대로)
+    if let Some(track) = queue::get_track_handle(queue_manager, guild_id).await {
+        if track
+            .get_info()
+            .await
+            .is_ok_and(|info| info.playing == PlayMode::Play)
+        {
+            let _ = track.pause();
+            queue::set_auto_paused(queue_manager, guild_id).await;
+            info!("듣는 사람이 없어 일시정지합니다 (guild: {guild_id})");
+        }
+    }
+
+    // 24/7 모드에서는 아무도 없어도 퇴장하지 않음
+    if data
+        .guild_settings
+        .as_ref()
+        .and_then(|settings| settings.stay(guild_id))
+        .is_some()
+    {
+        return Ok(());
     }
 
+    let Some(timer) = queue::start_idle_timer(queue_manager, guild_id).await else {
+        return Ok(()); // 이미 퇴장 타이머가 돌고 있음
+    };
+    let ctx = ctx.clone();
+    let queue_manager = queue_manager.clone();
+    let services = data.services.clone();
+    let auto_leave = data.auto_leave;
+
+    tokio::spawn(async move {
+        tokio::time::sleep(auto_leave).await;
+
+        // 그사이 누군가 돌아왔으면 타이머가 취소됨
+        if !queue::is_idle_timer(&queue_manager, guild_id, timer).await
+            || listener_count(&ctx, guild_id, bot_channel).is_some_and(|n| n > 0)
+        {
+            return;
+        }
+
+        info!(
+            "{}초 동안 듣는 사람이 없어 퇴장합니다 (guild: {guild_id})",
+            auto_leave.as_secs()
+        );
+        player::record_skipped(guild_id, &queue_manager, &services).await;
+        queue::clear(&queue_manager, guild_id).await;
+        let _ = manager.remove(guild_id).await;
+    });
+
     Ok(())
 }
@@ -1,4 +1,5 @@
 pub mod ai;
+pub mod api;
 pub mod commands;
 pub mod config;
 pub mod events;
@@ -12,10 +13,19 @@ pub type Context<'a> = poise::Context<'a, Data, Error>;
 pub struct Data {
     pub queue_manager: music::QueueManager,
     pub http_client: reqwest::Client,
+    /// 재생에 쓰는 yt-dlp 실행기와 재생 기록 (HTTP API와 공유)
+    pub services: music::player::Services,
     pub tarkov_cache: tarkov::Cache,
     pub gemini_api_key: Option<String>,
     pub tv_channel_id: Option<u64>,
     pub chat_db: Option<ai::db::ChatDb>,
+    /// 서버 설정 DB (HTTP API와 같은 연결을 공유)
+    pub guild_settings: Option<std::sync::Arc<music::settings::GuildSettings>>,
+    pub favorites: Option<music::favorites::Favorites>,
+    pub lyrics_provider: std::sync::Arc<dyn music::lyrics::LyricsProvider>,
     pub pending_queries: ai::PendingQueries,
+    pub rate_limiter: utils::ratelimit::RateLimiter,
+    /// 듣는 사람이 없을 때 퇴장까지 기다릴 시간
+    pub auto_leave: std::time::Duration,
     pub item_catalog: Option<std::sync::Arc<tarkov::catalog::ItemCatalog>>,
 }
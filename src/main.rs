@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use every_discord_bot::{ai, commands, config, events, music, tarkov, Data};
+use every_discord_bot::{ai, api, commands, config, events, music, tarkov, utils, Data};
 use poise::serenity_prelude as serenity;
 use songbird::SerenityInit;
 
@@ -27,6 +27,70 @@ async fn main() {
         None
     };
 
+    let guild_settings = match music::settings::GuildSettings::new(&config.db_path) {
+        Ok(settings) => Some(Arc::new(settings)),
+        Err(e) => {
+            tracing::error!("서버 설정 DB 초기화 실패: {e}");
+            None
+        }
+    };
+
+    let favorites = match music::favorites::Favorites::new(&config.db_path) {
+        Ok(favorites) => Some(favorites),
+        Err(e) => {
+            tracing::error!("즐겨찾기 DB 초기화 실패: {e}");
+            None
+        }
+    };
+
+    // yt-dlp 실행기 및 곡 정보 캐시
+    let ytdlp_config = music::source::YtDlpConfig {
+        binary: config.ytdlp_path.clone(),
+        extra_args: config.ytdlp_args.clone(),
+        max_concurrent: config.ytdlp_max_concurrent,
+        timeout: std::time::Duration::from_secs(config.ytdlp_timeout_secs),
+    }
+    .with_cookies_and_proxy(
+        config.ytdlp_cookies.as_deref(),
+        config.ytdlp_proxy.as_deref(),
+    );
+    let mut ytdlp = music::source::YtDlp::new(ytdlp_config);
+    match music::cache::MetadataCache::new(
+        &config.db_path,
+        std::time::Duration::from_secs(config.song_cache_ttl_secs),
+    ) {
+        Ok(cache) => ytdlp = ytdlp.with_cache(cache),
+        Err(e) => tracing::error!("곡 정보 캐시 초기화 실패: {e}"),
+    }
+
+    // 재생 기록 (/stats music)
+    let play_stats = match music::stats::PlayStats::new(&config.db_path) {
+        Ok(stats) => Some(Arc::new(stats)),
+        Err(e) => {
+            tracing::error!("재생 기록 DB 초기화 실패: {e}");
+            None
+        }
+    };
+
+    let http_client = reqwest::Client::new();
+    let services = music::player::Services {
+        http_client: http_client.clone(),
+        ytdlp: Arc::new(ytdlp),
+        stats: play_stats,
+        quiz_games: Default::default(),
+    };
+
+    // 가사: 로컬 파일을 먼저 찾고 없으면 LRCLIB
+    let mut lyrics_providers: Vec<Arc<dyn music::lyrics::LyricsProvider>> = Vec::new();
+    if let Some(dir) = &config.lyrics_dir {
+        lyrics_providers.push(Arc::new(music::lyrics::LocalLyricsProvider::new(dir)));
+    }
+    lyrics_providers.push(Arc::new(music::lyrics::LrclibProvider::new(
+        reqwest::Client::new(),
+    )));
+    let lyrics_provider: Arc<dyn music::lyrics::LyricsProvider> =
+        Arc::new(music::lyrics::ChainedLyricsProvider::new(lyrics_providers));
+
     let intents = serenity::GatewayIntents::non_privileged()
         | serenity::GatewayIntents::GUILD_MEMBERS
         | serenity::GatewayIntents::MESSAGE_CONTENT;
@@ -34,10 +98,19 @@ async fn main() {
     let gemini_api_key = config.gemini_api_key.clone();
     let tv_channel_id = config.tv_channel_id;
     let db_path = config.db_path.clone();
+    let auto_leave = std::time::Duration::from_secs(config.auto_leave_secs);
+    let api_token = config.api_token.clone();
+    let api_addr = config.api_addr.clone();
+    let rate_limiter = match &config.cooldowns {
+        Some(spec) => utils::ratelimit::RateLimiter::default().with_overrides(spec),
+        None => utils::ratelimit::RateLimiter::default(),
+    };
 
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
             commands: commands::all(),
+            command_check: Some(|ctx| Box::pin(commands::check_cooldown(ctx))),
+            on_error: |error| Box::pin(commands::on_error(error)),
             event_handler: |ctx, event, framework, data| {
                 Box::pin(events::handler(ctx, event, framework, data))
             },
@@ -84,15 +157,44 @@ async fn main() {
                     }
                 };
 
+                let queue_manager = music::new_queue_manager();
+
+                // HTTP 제어 API (토큰이 설정된 경우에만)
+                if let Some(token) = api_token {
+                    match api_addr.parse() {
+                        Ok(addr) => {
+                            let state = Arc::new(api::ApiState {
+                                queue_manager: queue_manager.clone(),
+                                services: services.clone(),
+                                songbird: songbird::get(ctx).await.expect("Songbird 미등록"),
+                                guild_settings: guild_settings.clone(),
+                                token,
+                            });
+                            tokio::spawn(async move {
+                                if let Err(e) = api::serve(addr, state).await {
+                                    tracing::error!("HTTP 제어 API 오류: {e}");
+                                }
+                            });
+                        }
+                        Err(e) => tracing::error!("잘못된 HTTP API 주소 {api_addr}: {e}"),
+                    }
+                }
+
                 tracing::info!("봇이 준비되었습니다!");
                 Ok(Data {
-                    queue_manager: music::new_queue_manager(),
-                    http_client: reqwest::Client::new(),
+                    queue_manager,
+                    http_client,
+                    services,
                     tarkov_cache: tarkov::new_cache(),
                     gemini_api_key,
                     tv_channel_id,
                     chat_db,
+                    guild_settings,
+                    favorites,
+                    lyrics_provider,
                     pending_queries: ai::new_pending_queries(),
+                    rate_limiter,
+                    auto_leave,
                     item_catalog,
                 })
             })
@@ -0,0 +1,156 @@
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::Song;
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// 같은 검색어/URL을 다시 조회하지 않도록 yt-dlp 결과를 저장하는 캐시
+pub struct MetadataCache {
+    conn: Mutex<Connection>,
+    ttl: Duration,
+}
+
+impl MetadataCache {
+    pub fn new(path: &str, ttl: Duration) -> Result<Self, rusqlite::Error> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS song_cache (
+                query TEXT PRIMARY KEY,
+                song_json TEXT NOT NULL,
+                fetched_at INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_song_cache_fetched
+                ON song_cache(fetched_at);",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            ttl,
+        })
+    }
+
+    /// 캐시 키: URL은 그대로, 검색어는 대소문자/공백 차이를 무시
+    pub fn key(query: &str) -> String {
+        let query = query.trim();
+        if super::source::is_url(query) {
+            query.to_string()
+        } else {
+            query
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase()
+        }
+    }
+
+    pub fn get(&self, query: &str) -> Option<Song> {
+        self.get_at(query, now_secs())
+    }
+
+    fn get_at(&self, query: &str, now: i64) -> Option<Song> {
+        let conn = self.conn.lock().unwrap();
+        let json: Option<String> = conn
+            .query_row(
+                "SELECT song_json FROM song_cache WHERE query = ?1 AND fetched_at > ?2",
+                params![Self::key(query), now - self.ttl.as_secs() as i64],
+                |row| row.get(0),
+            )
+            .optional()
+            .unwrap_or_else(|e| {
+                tracing::error!("곡 캐시 조회 실패: {e}");
+                None
+            });
+        json.and_then(|j| serde_json::from_str(&j).ok())
+    }
+
+    pub fn put(&self, query: &str, song: &Song) {
+        self.put_at(query, song, now_secs());
+    }
+
+    fn put_at(&self, query: &str, song: &Song, now: i64) {
+        let json = match serde_json::to_string(song) {
+            Ok(j) => j,
+            Err(e) => {
+                tracing::error!("곡 캐시 직렬화 실패: {e}");
+                return;
+            }
+        };
+
+        let conn = self.conn.lock().unwrap();
+        let result = conn
+            .execute(
+                "INSERT OR REPLACE INTO song_cache (query, song_json, fetched_at)
+                 VALUES (?1, ?2, ?3)",
+                params![Self::key(query), json, now],
+            )
+            .and_then(|_| {
+                // 만료된 항목 정리
+                conn.execute(
+                    "DELETE FROM song_cache WHERE fetched_at <= ?1",
+                    params![now - self.ttl.as_secs() as i64],
+                )
+            });
+        if let Err(e) = result {
+            tracing::error!("곡 캐시 저장 실패: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song() -> Song {
+        Song {
+            title: "Cached".to_string(),
+            url: "https://youtube.com/watch?v=abc".to_string(),
+            duration: Some("3:00".to_string()),
+            view_count: Some(10),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_key_normalizes_search_only() {
+        assert_eq!(MetadataCache::key("  Lofi   Hip Hop "), "lofi hip hop");
+        assert_eq!(
+            MetadataCache::key("https://youtube.com/watch?v=AbC"),
+            "https://youtube.com/watch?v=AbC"
+        );
+    }
+
+    #[test]
+    fn test_put_get_roundtrip() {
+        let cache = MetadataCache::new(":memory:", Duration::from_secs(60)).unwrap();
+        assert!(cache.get("lofi").is_none());
+
+        cache.put("Lofi", &song());
+        let cached = cache.get("lofi").unwrap();
+        assert_eq!(cached.title, "Cached");
+        assert_eq!(cached.view_count, Some(10));
+    }
+
+    #[test]
+    fn test_expired_entries_are_ignored_and_pruned() {
+        let cache = MetadataCache::new(":memory:", Duration::from_secs(60)).unwrap();
+        cache.put_at("old", &song(), 1_000);
+
+        assert!(cache.get_at("old", 1_030).is_some());
+        assert!(cache.get_at("old", 1_061).is_none());
+
+        cache.put_at("new", &song(), 2_000);
+        let count: i64 = cache
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM song_cache", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+}
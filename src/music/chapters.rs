@@ -0,0 +1,156 @@
+use super::Chapter;
+
+/// Discord 임베드 필드 값 최대 길이
+const FIELD_LIMIT: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChapterTarget {
+    Next,
+    Prev,
+    /// 1부터 시작하는 챕터 번호
+    Number(usize),
+}
+
+impl ChapterTarget {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "next" | "다음" => Some(Self::Next),
+            "prev" | "previous" | "이전" => Some(Self::Prev),
+            other => other.parse().ok().filter(|&n| n > 0).map(Self::Number),
+        }
+    }
+}
+
+/// 재생 위치가 속한 챕터의 인덱스 (첫 챕터 이전이면 `None`)
+pub fn current_index(chapters: &[Chapter], position_secs: f64) -> Option<usize> {
+    chapters.iter().rposition(|c| c.start_secs <= position_secs)
+}
+
+/// 이동할 챕터의 인덱스를 계산합니다
+pub fn resolve(chapters: &[Chapter], position_secs: f64, target: ChapterTarget) -> Option<usize> {
+    if chapters.is_empty() {
+        return None;
+    }
+
+    match target {
+        ChapterTarget::Next => match current_index(chapters, position_secs) {
+            Some(i) if i + 1 < chapters.len() => Some(i + 1),
+            Some(_) => None,
+            None => Some(0),
+        },
+        ChapterTarget::Prev => current_index(chapters, position_secs)
+            .and_then(|i| i.checked_sub(1))
+            .or(Some(0)),
+        ChapterTarget::Number(n) => (n <= chapters.len()).then(|| n - 1),
+    }
+}
+
+pub fn format_timestamp(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    let hours = total / 3600;
+    let mins = (total % 3600) / 60;
+    let remaining = total % 60;
+    if hours > 0 {
+        format!("{hours}:{mins:02}:{remaining:02}")
+    } else {
+        format!("{mins}:{remaining:02}")
+    }
+}
+
+/// 임베드 필드용 챕터 목록 (현재 챕터는 굵게 표시)
+pub fn format_list(chapters: &[Chapter], current: Option<usize>) -> String {
+    let mut text = String::new();
+    for (i, chapter) in chapters.iter().enumerate() {
+        let line = if Some(i) == current {
+            format!(
+                "▶ **{}. {}** `{}`\n",
+                i + 1,
+                chapter.title,
+                format_timestamp(chapter.start_secs)
+            )
+        } else {
+            format!(
+                "{}. {} `{}`\n",
+                i + 1,
+                chapter.title,
+                format_timestamp(chapter.start_secs)
+            )
+        };
+
+        if text.chars().count() + line.chars().count() > FIELD_LIMIT - 4 {
+            text.push_str("...");
+            break;
+        }
+        text.push_str(&line);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapters() -> Vec<Chapter> {
+        [
+            ("Intro", 0.0, 30.0),
+            ("Verse", 30.0, 90.0),
+            ("Outro", 90.0, 120.0),
+        ]
+        .iter()
+        .map(|(title, start, end)| Chapter {
+            title: title.to_string(),
+            start_secs: *start,
+            end_secs: *end,
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(ChapterTarget::parse("next"), Some(ChapterTarget::Next));
+        assert_eq!(ChapterTarget::parse("이전"), Some(ChapterTarget::Prev));
+        assert_eq!(ChapterTarget::parse("3"), Some(ChapterTarget::Number(3)));
+        assert_eq!(ChapterTarget::parse("0"), None);
+        assert_eq!(ChapterTarget::parse("abc"), None);
+    }
+
+    #[test]
+    fn test_current_index() {
+        let c = chapters();
+        assert_eq!(current_index(&c, 0.0), Some(0));
+        assert_eq!(current_index(&c, 45.0), Some(1));
+        assert_eq!(current_index(&c, 119.0), Some(2));
+        assert_eq!(current_index(&[], 10.0), None);
+    }
+
+    #[test]
+    fn test_resolve_next_and_prev() {
+        let c = chapters();
+        assert_eq!(resolve(&c, 10.0, ChapterTarget::Next), Some(1));
+        assert_eq!(resolve(&c, 100.0, ChapterTarget::Next), None);
+        assert_eq!(resolve(&c, 45.0, ChapterTarget::Prev), Some(0));
+        assert_eq!(resolve(&c, 5.0, ChapterTarget::Prev), Some(0));
+    }
+
+    #[test]
+    fn test_resolve_number() {
+        let c = chapters();
+        assert_eq!(resolve(&c, 0.0, ChapterTarget::Number(3)), Some(2));
+        assert_eq!(resolve(&c, 0.0, ChapterTarget::Number(4)), None);
+        assert_eq!(resolve(&[], 0.0, ChapterTarget::Number(1)), None);
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0.0), "0:00");
+        assert_eq!(format_timestamp(65.4), "1:05");
+        assert_eq!(format_timestamp(3725.0), "1:02:05");
+    }
+
+    #[test]
+    fn test_format_list_marks_current() {
+        let list = format_list(&chapters(), Some(1));
+        assert!(list.contains("▶ **2. Verse**"));
+        assert!(list.contains("1. Intro `0:00`"));
+    }
+}
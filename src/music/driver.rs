@@ -0,0 +1,35 @@
+use songbird::error::ControlError;
+use songbird::events::{Event, EventHandler, TrackEvent};
+use songbird::tracks::{Track, TrackHandle};
+use songbird::{Call, Driver};
+
+/// 곡을 실제로 재생하는 음성 연결
+///
+/// 봇에서는 songbird `Call`을 쓰고, 오프라인 테스트에서는 Discord 없이 오디오를 디코딩하는
+/// 가짜 드라이버를 끼워 `player`의 곡 전환 흐름을 그대로 검증합니다.
+pub trait VoiceDriver: Send + 'static {
+    /// 재생 중인 트랙을 종료 이벤트 없이 내리고 `track`을 재생합니다
+    fn play_only(&mut self, track: Track) -> TrackHandle;
+
+    /// `track`이 끝나거나 멈추면 `handler`를 실행합니다
+    fn add_end_event<H: EventHandler + 'static>(
+        &mut self,
+        track: &TrackHandle,
+        handler: H,
+    ) -> Result<(), ControlError>;
+}
+
+impl VoiceDriver for Call {
+    fn play_only(&mut self, track: Track) -> TrackHandle {
+        let driver: &mut Driver = self;
+        driver.play_only(track)
+    }
+
+    fn add_end_event<H: EventHandler + 'static>(
+        &mut self,
+        track: &TrackHandle,
+        handler: H,
+    ) -> Result<(), ControlError> {
+        track.add_event(Event::Track(TrackEvent::End), handler)
+    }
+}
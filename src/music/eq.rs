@@ -0,0 +1,407 @@
+use std::f32::consts::PI;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use songbird::input::codecs::{CODEC_REGISTRY, PROBE};
+use songbird::input::{Input, LiveInput};
+use symphonia::core::audio::{AsAudioBufferRef, AudioBuffer, AudioBufferRef, Signal};
+use symphonia::core::codecs::{
+    CodecDescriptor, CodecParameters, Decoder, DecoderOptions, FinalizeResult,
+};
+use symphonia::core::formats::Packet;
+
+/// 밴드 중심 주파수 (Hz)
+pub const BAND_FREQS: [f32; 10] = [
+    31.0, 62.0, 125.0, 250.0, 500.0, 1000.0, 2000.0, 4000.0, 8000.0, 16000.0,
+];
+/// 명령어와 embed에 쓰는 밴드 이름
+pub const BAND_LABELS: [&str; 10] = [
+    "31", "62", "125", "250", "500", "1k", "2k", "4k", "8k", "16k",
+];
+pub const MAX_GAIN_DB: f32 = 12.0;
+pub const PRESETS: [&str; 3] = ["flat", "rock", "pop"];
+
+/// 1옥타브 폭에 해당하는 Q
+const BAND_Q: f32 = 1.41;
+
+/// 밴드별 게인 (dB)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EqBands {
+    pub gains: [f32; 10],
+}
+
+impl EqBands {
+    pub fn preset(name: &str) -> Option<Self> {
+        let gains = match name.trim().to_lowercase().as_str() {
+            "flat" | "reset" | "끔" => [0.0; 10],
+            "rock" | "록" => [5.0, 4.0, 3.0, 1.0, -1.0, -1.0, 1.0, 3.0, 4.0, 5.0],
+            "pop" | "팝" => [-1.0, 0.0, 2.0, 3.0, 4.0, 3.0, 2.0, 0.0, -1.0, -1.0],
+            _ => return None,
+        };
+        Some(Self { gains })
+    }
+
+    /// 이 설정과 같은 프리셋 이름
+    pub fn preset_name(&self) -> Option<&'static str> {
+        PRESETS
+            .into_iter()
+            .find(|name| Self::preset(name).as_ref() == Some(self))
+    }
+
+    /// `1k`, `1000`, `1khz` 등을 밴드 번호로 변환
+    pub fn parse_band(input: &str) -> Option<usize> {
+        let lower = input.trim().to_lowercase();
+        let label = lower.trim_end_matches("hz").trim();
+        if let Some(i) = BAND_LABELS.iter().position(|l| *l == label) {
+            return Some(i);
+        }
+        let hz: f32 = match label.strip_suffix('k') {
+            Some(k) => k.parse::<f32>().ok()? * 1000.0,
+            None => label.parse().ok()?,
+        };
+        BAND_FREQS.iter().position(|f| (f - hz).abs() < 1.0)
+    }
+
+    pub fn set_gain(&mut self, band: usize, gain_db: f32) -> f32 {
+        let gain = gain_db.clamp(-MAX_GAIN_DB, MAX_GAIN_DB);
+        self.gains[band] = gain;
+        gain
+    }
+
+    pub fn is_flat(&self) -> bool {
+        self.gains.iter().all(|g| *g == 0.0)
+    }
+
+    /// DB 저장용 `0,0,3.5,...` 형식을 읽습니다
+    pub fn parse(stored: &str) -> Option<Self> {
+        let values: Vec<f32> = stored
+            .split(',')
+            .map(|v| v.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        let gains: [f32; 10] = values.try_into().ok()?;
+        Some(Self {
+            gains: gains.map(|g| g.clamp(-MAX_GAIN_DB, MAX_GAIN_DB)),
+        })
+    }
+}
+
+impl fmt::Display for EqBands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values: Vec<String> = self.gains.iter().map(|g| g.to_string()).collect();
+        f.write_str(&values.join(","))
+    }
+}
+
+/// 서버의 현재 EQ. 재생 중인 트랙의 디코더가 함께 참조하므로 바꾸면 바로 적용됩니다.
+pub type SharedEq = Arc<RwLock<EqBands>>;
+
+/// RBJ peaking 필터 (transposed direct form II)
+#[derive(Clone, Copy, Debug, Default)]
+struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    z1: f32,
+    z2: f32,
+}
+
+impl Biquad {
+    fn peaking(freq: f32, gain_db: f32, sample_rate: f32) -> Self {
+        let a = 10f32.powf(gain_db / 40.0);
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let alpha = w0.sin() / (2.0 * BAND_Q);
+        let cos = w0.cos();
+        let a0 = 1.0 + alpha / a;
+        Self {
+            b0: (1.0 + alpha * a) / a0,
+            b1: -2.0 * cos / a0,
+            b2: (1.0 - alpha * a) / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha / a) / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    /// 상태는 유지한 채 계수만 바꿉니다 (설정 변경 시 튀는 소리 방지)
+    fn retune(&mut self, other: Biquad) {
+        *self = Biquad {
+            z1: self.z1,
+            z2: self.z2,
+            ..other
+        };
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x + self.z1;
+        self.z1 = self.b1 * x - self.a1 * y + self.z2;
+        self.z2 = self.b2 * x - self.a2 * y;
+        y
+    }
+}
+
+/// 채널별 필터 묶음
+pub struct Equalizer {
+    bands: EqBands,
+    sample_rate: f32,
+    /// `[채널][밴드]`, 게인이 0인 밴드는 `None`
+    filters: Vec<Vec<Option<Biquad>>>,
+    preamp: f32,
+}
+
+impl Equalizer {
+    pub fn new(bands: EqBands, sample_rate: u32, channels: usize) -> Self {
+        let mut eq = Self {
+            bands: EqBands::default(),
+            sample_rate: sample_rate as f32,
+            filters: vec![vec![None; BAND_FREQS.len()]; channels],
+            preamp: 1.0,
+        };
+        eq.set_bands(bands);
+        eq
+    }
+
+    pub fn bands(&self) -> EqBands {
+        self.bands
+    }
+
+    pub fn set_bands(&mut self, bands: EqBands) {
+        self.bands = bands;
+        for channel in &mut self.filters {
+            for (i, filter) in channel.iter_mut().enumerate() {
+                let gain = bands.gains[i];
+                // 나이퀴스트 주파수를 넘는 밴드는 건너뜀
+                if gain == 0.0 || BAND_FREQS[i] >= self.sample_rate / 2.0 {
+                    *filter = None;
+                    continue;
+                }
+                let tuned = Biquad::peaking(BAND_FREQS[i], gain, self.sample_rate);
+                match filter {
+                    Some(f) => f.retune(tuned),
+                    None => *filter = Some(tuned),
+                }
+            }
+        }
+        // 올린 만큼 전체 음량을 낮춰 클리핑 방지
+        let boost = bands.gains.iter().copied().fold(0.0f32, f32::max);
+        self.preamp = 10f32.powf(-boost / 20.0);
+    }
+
+    pub fn reset(&mut self) {
+        for filter in self.filters.iter_mut().flatten().flatten() {
+            filter.z1 = 0.0;
+            filter.z2 = 0.0;
+        }
+    }
+
+    pub fn process(&mut self, channel: usize, samples: &mut [f32]) {
+        let Some(filters) = self.filters.get_mut(channel) else {
+            return;
+        };
+        for sample in samples {
+            let mut x = *sample * self.preamp;
+            for filter in filters.iter_mut().flatten() {
+                x = filter.process(x);
+            }
+            *sample = x;
+        }
+    }
+}
+
+/// 원래 디코더의 출력에 EQ를 적용하는 디코더
+///
+/// songbird가 디코딩한 PCM을 믹서로 넘기기 전에 거치므로 밴드 설정이 재생 중에도 반영됩니다.
+struct EqDecoder {
+    inner: Box<dyn Decoder>,
+    shared: SharedEq,
+    eq: Option<Equalizer>,
+    out: Option<AudioBuffer<f32>>,
+    last_filtered: bool,
+}
+
+impl EqDecoder {
+    fn new(inner: Box<dyn Decoder>, shared: SharedEq) -> Self {
+        Self {
+            inner,
+            shared,
+            eq: None,
+            out: None,
+            last_filtered: false,
+        }
+    }
+}
+
+impl Decoder for EqDecoder {
+    fn try_new(
+        _params: &CodecParameters,
+        _options: &DecoderOptions,
+    ) -> symphonia::core::errors::Result<Self> {
+        Err(symphonia::core::errors::Error::Unsupported(
+            "EqDecoder는 다른 디코더를 감싸서만 만들 수 있습니다",
+        ))
+    }
+
+    fn supported_codecs() -> &'static [CodecDescriptor] {
+        &[]
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        if let Some(eq) = &mut self.eq {
+            eq.reset();
+        }
+    }
+
+    fn codec_params(&self) -> &CodecParameters {
+        self.inner.codec_params()
+    }
+
+    fn decode(&mut self, packet: &Packet) -> symphonia::core::errors::Result<AudioBufferRef<'_>> {
+        let bands = *self.shared.read().unwrap();
+        if bands.is_flat() && self.eq.as_ref().is_none_or(|eq| eq.bands().is_flat()) {
+            self.last_filtered = false;
+            return self.inner.decode(packet);
+        }
+
+        let decoded = self.inner.decode(packet)?;
+        let spec = *decoded.spec();
+        let channels = spec.channels.count();
+
+        // convert()는 채널 간격을 원본 용량 기준으로 쓰므로 용량까지 같아야 재사용 가능
+        let reusable = self
+            .out
+            .as_ref()
+            .is_some_and(|out| *out.spec() == spec && out.capacity() == decoded.capacity());
+        if !reusable {
+            self.out = None;
+        }
+        let out = self
+            .out
+            .get_or_insert_with(|| decoded.make_equivalent::<f32>());
+        decoded.convert(out);
+
+        let matches = self
+            .eq
+            .as_ref()
+            .is_some_and(|eq| eq.sample_rate == spec.rate as f32 && eq.filters.len() == channels);
+        if !matches {
+            self.eq = None;
+        }
+        let eq = self
+            .eq
+            .get_or_insert_with(|| Equalizer::new(bands, spec.rate, channels));
+        if eq.bands() != bands {
+            eq.set_bands(bands);
+        }
+        for channel in 0..channels {
+            eq.process(channel, out.chan_mut(channel));
+        }
+
+        self.last_filtered = true;
+        Ok(out.as_audio_buffer_ref())
+    }
+
+    fn finalize(&mut self) -> FinalizeResult {
+        self.inner.finalize()
+    }
+
+    fn last_decoded(&self) -> AudioBufferRef<'_> {
+        match &self.out {
+            Some(out) if self.last_filtered => out.as_audio_buffer_ref(),
+            _ => self.inner.last_decoded(),
+        }
+    }
+}
+
+/// 입력을 미리 준비해 디코더에 EQ를 끼웁니다.
+///
+/// 준비에 실패하면 오류를 돌려주며, 이때 입력은 소모됩니다.
+pub async fn with_equalizer(
+    input: Input,
+    shared: SharedEq,
+) -> Result<Input, songbird::input::MakePlayableError> {
+    match input.make_playable_async(&CODEC_REGISTRY, &PROBE).await? {
+        Input::Live(LiveInput::Parsed(mut parsed), rec) => {
+            parsed.decoder = Box::new(EqDecoder::new(parsed.decoder, shared));
+            Ok(Input::Live(LiveInput::Parsed(parsed), rec))
+        }
+        other => Ok(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        assert!(EqBands::preset("flat").unwrap().is_flat());
+        assert!(!EqBands::preset("Rock").unwrap().is_flat());
+        assert!(EqBands::preset("jazz").is_none());
+        assert_eq!(EqBands::preset("pop").unwrap().preset_name(), Some("pop"));
+        assert_eq!(EqBands::default().preset_name(), Some("flat"));
+
+        let mut custom = EqBands::default();
+        custom.set_gain(0, 3.0);
+        assert_eq!(custom.preset_name(), None);
+    }
+
+    #[test]
+    fn test_parse_band() {
+        assert_eq!(EqBands::parse_band("31"), Some(0));
+        assert_eq!(EqBands::parse_band("1k"), Some(5));
+        assert_eq!(EqBands::parse_band("1000"), Some(5));
+        assert_eq!(EqBands::parse_band("16kHz"), Some(9));
+        assert_eq!(EqBands::parse_band("2.0k"), Some(6));
+        assert_eq!(EqBands::parse_band("300"), None);
+        assert_eq!(EqBands::parse_band("bass"), None);
+    }
+
+    #[test]
+    fn test_set_gain_clamps_and_storage_roundtrip() {
+        let mut bands = EqBands::default();
+        assert_eq!(bands.set_gain(2, 30.0), MAX_GAIN_DB);
+        assert_eq!(bands.set_gain(3, -2.5), -2.5);
+        let stored = bands.to_string();
+        assert_eq!(EqBands::parse(&stored), Some(bands));
+        assert_eq!(EqBands::parse("1,2,3"), None);
+        assert_eq!(EqBands::parse("a,0,0,0,0,0,0,0,0,0"), None);
+    }
+
+    fn rms_after(eq: &mut Equalizer, freq: f32) -> f32 {
+        let rate = 48_000.0;
+        let mut samples: Vec<f32> = (0..48_000)
+            .map(|i| (2.0 * PI * freq * i as f32 / rate).sin() * 0.5)
+            .collect();
+        eq.reset();
+        eq.process(0, &mut samples);
+        // 필터가 안정된 뒤 구간만 측정
+        let tail = &samples[24_000..];
+        (tail.iter().map(|s| s * s).sum::<f32>() / tail.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_flat_is_passthrough() {
+        let mut eq = Equalizer::new(EqBands::default(), 48_000, 2);
+        let mut samples = vec![0.1, -0.2, 0.3];
+        eq.process(1, &mut samples);
+        assert_eq!(samples, vec![0.1, -0.2, 0.3]);
+    }
+
+    #[test]
+    fn test_band_boost_shapes_spectrum() {
+        let mut bands = EqBands::default();
+        bands.set_gain(5, 12.0);
+        let mut eq = Equalizer::new(bands, 48_000, 1);
+
+        let input_rms = 0.5 / 2f32.sqrt();
+        let at_1k = rms_after(&mut eq, 1000.0) / input_rms;
+        let at_8k = rms_after(&mut eq, 8000.0) / input_rms;
+
+        // 1kHz는 +12dB 후 프리앰프 -12dB로 원래 크기, 먼 대역은 -12dB 근처
+        assert!((at_1k - 1.0).abs() < 0.05, "1k gain {at_1k}");
+        assert!(at_8k < 0.3, "8k gain {at_8k}");
+    }
+}
@@ -0,0 +1,154 @@
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection};
+use serenity::model::id::UserId;
+
+use super::stats::now_secs;
+use super::Song;
+
+/// 사용자별로 저장하는 즐겨찾기 곡
+pub struct Favorites {
+    conn: Mutex<Connection>,
+}
+
+impl Favorites {
+    pub fn new(path: &str) -> Result<Self, rusqlite::Error> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS favorites (
+                user_id TEXT NOT NULL,
+                url TEXT NOT NULL,
+                song_json TEXT NOT NULL,
+                added_at INTEGER NOT NULL,
+                PRIMARY KEY (user_id, url)
+            );",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// 즐겨찾기에 추가합니다. 이미 있던 곡이면 `false`.
+    pub fn add(&self, user_id: UserId, song: &Song) -> bool {
+        // 신청자는 재생할 때마다 바뀌므로 저장하지 않음
+        let song = Song {
+            requester: String::new(),
+            ..song.clone()
+        };
+        let json = match serde_json::to_string(&song) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::error!("즐겨찾기 직렬화 실패: {e}");
+                return false;
+            }
+        };
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO favorites (user_id, url, song_json, added_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![user_id.get().to_string(), song.url, json, now_secs()],
+        )
+        .map(|inserted| inserted > 0)
+        .unwrap_or_else(|e| {
+            tracing::error!("즐겨찾기 저장 실패: {e}");
+            false
+        })
+    }
+
+    /// 추가한 순서대로의 즐겨찾기 목록
+    pub fn list(&self, user_id: UserId) -> Vec<Song> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn
+            .prepare(
+                "SELECT song_json FROM favorites WHERE user_id = ?1
+                 ORDER BY added_at ASC, rowid ASC",
+            )
+            .and_then(|mut stmt| {
+                stmt.query_map(params![user_id.get().to_string()], |row| {
+                    row.get::<_, String>(0)
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()
+            });
+
+        match result {
+            Ok(rows) => rows
+                .iter()
+                .filter_map(|json| serde_json::from_str(json).ok())
+                .collect(),
+            Err(e) => {
+                tracing::error!("즐겨찾기 조회 실패: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// `list`의 번호(1부터)로 즐겨찾기를 삭제합니다
+    pub fn remove(&self, user_id: UserId, position: usize) -> Option<Song> {
+        let song = self
+            .list(user_id)
+            .into_iter()
+            .nth(position.checked_sub(1)?)?;
+
+        let conn = self.conn.lock().unwrap();
+        match conn.execute(
+            "DELETE FROM favorites WHERE user_id = ?1 AND url = ?2",
+            params![user_id.get().to_string(), song.url],
+        ) {
+            Ok(deleted) if deleted > 0 => Some(song),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::error!("즐겨찾기 삭제 실패: {e}");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER: UserId = UserId::new(1);
+    const OTHER: UserId = UserId::new(2);
+
+    fn song(title: &str) -> Song {
+        Song {
+            title: title.to_string(),
+            url: format!("https://example.com/{title}"),
+            requester: "tester".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_add_list_and_dedupe() {
+        let favorites = Favorites::new(":memory:").unwrap();
+        assert!(favorites.add(USER, &song("A")));
+        assert!(favorites.add(USER, &song("B")));
+        assert!(!favorites.add(USER, &song("A")));
+
+        let list = favorites.list(USER);
+        let titles: Vec<_> = list.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["A", "B"]);
+        assert_eq!(list[0].requester, "");
+
+        // 사용자별로 분리
+        assert!(favorites.list(OTHER).is_empty());
+        assert!(favorites.add(OTHER, &song("A")));
+    }
+
+    #[test]
+    fn test_remove_by_position() {
+        let favorites = Favorites::new(":memory:").unwrap();
+        favorites.add(USER, &song("A"));
+        favorites.add(USER, &song("B"));
+        favorites.add(OTHER, &song("B"));
+
+        assert_eq!(favorites.remove(USER, 2).map(|s| s.title), Some("B".into()));
+        assert!(favorites.remove(USER, 2).is_none());
+        assert!(favorites.remove(USER, 0).is_none());
+        assert_eq!(favorites.list(USER).len(), 1);
+        assert_eq!(favorites.list(OTHER).len(), 1);
+    }
+}
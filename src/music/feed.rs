@@ -0,0 +1,62 @@
+use serde::Serialize;
+use serenity::model::id::GuildId;
+use tokio::sync::broadcast;
+
+use super::Song;
+
+/// 구독자가 늦게 읽어도 버리지 않고 쌓아둘 알림 수
+const CAPACITY: usize = 64;
+
+/// 서버에서 재생 중인 곡이 바뀐 알림 (`song`이 없으면 대기열이 끝남)
+#[derive(Clone, Debug, Serialize)]
+pub struct TrackChange {
+    pub guild_id: GuildId,
+    pub song: Option<Song>,
+}
+
+/// 모든 서버의 곡 전환 알림 채널 (`QueueManager`가 들고 있어 HTTP API와 공유)
+pub struct TrackFeed {
+    sender: broadcast::Sender<TrackChange>,
+}
+
+impl Default for TrackFeed {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(CAPACITY).0,
+        }
+    }
+}
+
+impl TrackFeed {
+    /// 모든 서버의 곡 전환 알림을 받습니다
+    pub fn subscribe(&self) -> broadcast::Receiver<TrackChange> {
+        self.sender.subscribe()
+    }
+
+    /// 곡 전환을 알립니다 (구독자가 없으면 버려짐)
+    pub fn publish(&self, guild_id: GuildId, song: Option<Song>) {
+        let _ = self.sender.send(TrackChange { guild_id, song });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_reaches_subscribers() {
+        let feed = TrackFeed::default();
+        let mut rx = feed.subscribe();
+        let song = Song {
+            title: "Feed Song".to_string(),
+            ..Default::default()
+        };
+        feed.publish(GuildId::new(7), Some(song));
+        feed.publish(GuildId::new(7), None);
+
+        let first = rx.recv().await.unwrap();
+        assert_eq!(first.guild_id, GuildId::new(7));
+        assert_eq!(first.song.unwrap().title, "Feed Song");
+        assert!(rx.recv().await.unwrap().song.is_none());
+    }
+}
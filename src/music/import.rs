@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+
+use super::source::YtDlp;
+use super::Song;
+
+/// 한 번에 가져올 수 있는 최대 줄 수
+pub const MAX_LINES: usize = 100;
+/// 첨부 파일 최대 크기 (바이트)
+pub const MAX_FILE_SIZE: u32 = 64 * 1024;
+
+/// 가져올 파일 종류 (확장자로 판단)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ListFormat {
+    Text,
+    Csv,
+}
+
+impl ListFormat {
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let (_, ext) = filename.rsplit_once('.')?;
+        match ext.to_ascii_lowercase().as_str() {
+            "txt" => Some(Self::Text),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// CSV 한 줄을 필드로 나눕니다 (큰따옴표로 감싼 필드와 `""` 이스케이프 지원)
+fn split_csv_row(row: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+/// 헤더에서 제목/아티스트 열을 찾습니다 (다른 서비스에서 내보낸 CSV용)
+fn csv_columns(header: &[String]) -> Option<(usize, Option<usize>)> {
+    let find = |names: &[&str]| {
+        header.iter().position(|h| {
+            let h = h.to_lowercase();
+            names.iter().any(|name| h == *name)
+        })
+    };
+    let title = find(&[
+        "title",
+        "track name",
+        "track",
+        "name",
+        "song",
+        "제목",
+        "곡명",
+    ])?;
+    let artist = find(&[
+        "artist",
+        "artist name(s)",
+        "artist name",
+        "artists",
+        "아티스트",
+        "가수",
+    ]);
+    Some((title, artist))
+}
+
+/// 붙여 넣은 목록이나 파일 내용을 검색어 목록으로 바꿉니다
+///
+/// 빈 줄과 `#`으로 시작하는 줄은 건너뜁니다. CSV에 제목/아티스트 헤더가 있으면
+/// `아티스트 - 제목`으로, 없으면 앞의 두 필드를 이어 검색어로 씁니다.
+pub fn parse_list(text: &str, format: ListFormat) -> Vec<String> {
+    let mut lines = text
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .peekable();
+
+    let queries: Vec<String> = match format {
+        ListFormat::Text => lines.map(String::from).collect(),
+        ListFormat::Csv => {
+            let header = lines.peek().map(|line| split_csv_row(line));
+            let columns = header.as_deref().and_then(csv_columns);
+            if columns.is_some() {
+                lines.next();
+            }
+            lines
+                .map(|line| {
+                    let fields = split_csv_row(line);
+                    let field = |i: usize| fields.get(i).map(String::as_str).unwrap_or("");
+                    match columns {
+                        Some((title, Some(artist))) if !field(artist).is_empty() => {
+                            format!("{} - {}", field(artist), field(title))
+                        }
+                        Some((title, _)) => field(title).to_string(),
+                        None => fields
+                            .iter()
+                            .take(2)
+                            .filter(|f| !f.is_empty())
+                            .cloned()
+                            .collect::<Vec<_>>()
+                            .join(" - "),
+                    }
+                })
+                .collect()
+        }
+    };
+
+    queries
+        .into_iter()
+        .filter(|query| !query.trim().is_empty())
+        .collect()
+}
+
+/// 검색어마다 곡 정보를 동시에 조회하고, 입력 순서대로 결과를 돌려줍니다
+///
+/// yt-dlp 동시 실행 수는 `YtDlp`에서 제한하므로 여기서는 모두 한꺼번에 시작합니다.
+/// 끝난 개수는 `progress`로 알립니다.
+pub async fn resolve_all(
+    ytdlp: &Arc<YtDlp>,
+    queries: &[String],
+    progress: &watch::Sender<usize>,
+) -> Vec<Result<Song, String>> {
+    let mut tasks = JoinSet::new();
+    for (index, query) in queries.iter().cloned().enumerate() {
+        let ytdlp = ytdlp.clone();
+        tasks.spawn(async move {
+            let result = ytdlp.song_info(&query).await.map_err(|e| e.to_string());
+            (index, result)
+        });
+    }
+
+    let mut results: Vec<Option<Result<Song, String>>> = vec![None; queries.len()];
+    let mut done = 0;
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok((index, result)) = joined {
+            results[index] = Some(result);
+        }
+        done += 1;
+        progress.send_replace(done);
+    }
+
+    results
+        .into_iter()
+        .map(|result| result.unwrap_or_else(|| Err("조회 작업이 중단되었습니다".to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_filename() {
+        assert_eq!(
+            ListFormat::from_filename("songs.TXT"),
+            Some(ListFormat::Text)
+        );
+        assert_eq!(
+            ListFormat::from_filename("export.v2.csv"),
+            Some(ListFormat::Csv)
+        );
+        assert_eq!(ListFormat::from_filename("playlist.json"), None);
+        assert_eq!(ListFormat::from_filename("csv"), None);
+    }
+
+    #[test]
+    fn test_parse_text_list() {
+        let text = "\u{feff}Never Gonna Give You Up\r\n\n  # 주석\n  IU - 밤편지  \nhttps://youtu.be/abc\n";
+        assert_eq!(
+            parse_list(text, ListFormat::Text),
+            [
+                "Never Gonna Give You Up",
+                "IU - 밤편지",
+                "https://youtu.be/abc"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_with_header() {
+        let csv = "\"Track Name\",\"Artist Name(s)\",\"Album\"\n\
+                   \"Hello, World\",\"Some \"\"Band\"\"\",\"X\"\n\
+                   Solo Song,,Y\n";
+        assert_eq!(
+            parse_list(csv, ListFormat::Csv),
+            ["Some \"Band\" - Hello, World", "Solo Song"]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_without_header() {
+        let csv = "Artist A,Song A,2020\nSong B\n";
+        assert_eq!(
+            parse_list(csv, ListFormat::Csv),
+            ["Artist A - Song A", "Song B"]
+        );
+    }
+}
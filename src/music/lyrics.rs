@@ -0,0 +1,414 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::segments::youtube_video_id;
+use super::Song;
+
+const LRCLIB_SEARCH_URL: &str = "https://lrclib.net/api/search";
+
+/// 가사 한 페이지의 최대 글자 수 (embed 설명 제한보다 여유 있게)
+pub const PAGE_CHARS: usize = 1800;
+
+/// 타임스탬프가 있는 가사 한 줄
+#[derive(Clone, Debug, PartialEq)]
+pub struct LyricLine {
+    pub time_secs: f64,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Lyrics {
+    /// 가사를 가져온 곳 (embed footer 표시용)
+    pub source: String,
+    pub plain: String,
+    /// LRC 가사가 있으면 시간 순으로 정렬된 줄
+    pub synced: Vec<LyricLine>,
+}
+
+impl Lyrics {
+    /// LRC 또는 일반 텍스트로 만듭니다. LRC라면 타임스탬프를 뺀 본문도 채웁니다.
+    pub fn from_text(source: &str, text: &str) -> Self {
+        let synced = parse_lrc(text);
+        let plain = if synced.is_empty() {
+            text.trim().to_string()
+        } else {
+            synced
+                .iter()
+                .map(|l| l.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        Self {
+            source: source.to_string(),
+            plain,
+            synced,
+        }
+    }
+
+    pub fn is_synced(&self) -> bool {
+        !self.synced.is_empty()
+    }
+}
+
+/// `mm:ss.xx` 타임스탬프를 초로 변환
+fn parse_timestamp(tag: &str) -> Option<f64> {
+    let (mins, secs) = tag.split_once(':')?;
+    let mins: u64 = mins.trim().parse().ok()?;
+    let secs: f64 = secs.trim().parse().ok()?;
+    (0.0..60.0)
+        .contains(&secs)
+        .then_some(mins as f64 * 60.0 + secs)
+}
+
+/// LRC 형식 가사를 파싱합니다.
+///
+/// 한 줄에 여러 타임스탬프(`[00:12.00][01:30.00]후렴`)를 허용하고,
+/// `[ar:가수]` 같은 메타데이터 태그는 무시합니다.
+pub fn parse_lrc(text: &str) -> Vec<LyricLine> {
+    let mut lines = Vec::new();
+    for raw in text.lines() {
+        let mut rest = raw.trim();
+        let mut times = Vec::new();
+        while let Some(tag_end) = rest.strip_prefix('[').and_then(|r| r.find(']')) {
+            match parse_timestamp(&rest[1..=tag_end]) {
+                Some(t) => times.push(t),
+                None => break,
+            }
+            rest = rest[tag_end + 2..].trim_start();
+        }
+        for time_secs in times {
+            lines.push(LyricLine {
+                time_secs,
+                text: rest.trim().to_string(),
+            });
+        }
+    }
+    lines.sort_by(|a, b| a.time_secs.total_cmp(&b.time_secs));
+    lines
+}
+
+/// 재생 위치에서 부르고 있는 줄
+pub fn current_line(lines: &[LyricLine], position_secs: f64) -> Option<usize> {
+    lines.iter().rposition(|l| l.time_secs <= position_secs)
+}
+
+/// 싱크 모드에서 보여줄 줄 범위 (현재 줄 앞 `before`줄, 뒤 `after`줄)
+pub fn synced_window(
+    len: usize,
+    current: Option<usize>,
+    before: usize,
+    after: usize,
+) -> std::ops::Range<usize> {
+    let center = current.unwrap_or(0);
+    let start = center.saturating_sub(before);
+    let end = (center + after + 1).min(len);
+    start..end
+}
+
+/// 줄 단위로 끊어 한 페이지가 `max_chars`를 넘지 않게 나눕니다
+pub fn paginate(text: &str, max_chars: usize) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut page = String::new();
+    let mut page_chars = 0;
+
+    for line in text.lines() {
+        let line: String = line.chars().take(max_chars).collect();
+        let line_chars = line.chars().count() + 1;
+        if page_chars + line_chars > max_chars && !page.is_empty() {
+            pages.push(std::mem::take(&mut page).trim_end().to_string());
+            page_chars = 0;
+        }
+        page.push_str(&line);
+        page.push('\n');
+        page_chars += line_chars;
+    }
+    if !page.trim().is_empty() {
+        pages.push(page.trim_end().to_string());
+    }
+    pages
+}
+
+/// 검색용 제목: 괄호 안 부가 정보(`(Official MV)` 등)를 뺍니다
+pub fn search_title(title: &str) -> String {
+    let mut depth = 0usize;
+    let cleaned: String = title
+        .chars()
+        .filter(|&c| match c {
+            '(' | '[' | '【' => {
+                depth += 1;
+                false
+            }
+            ')' | ']' | '】' => {
+                depth = depth.saturating_sub(1);
+                false
+            }
+            _ => depth == 0,
+        })
+        .collect();
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 곡의 가사를 제공합니다. 찾지 못하면 `Ok(None)`.
+#[async_trait]
+pub trait LyricsProvider: Send + Sync {
+    async fn lyrics(
+        &self,
+        song: &Song,
+    ) -> Result<Option<Lyrics>, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// LRCLIB(lrclib.net) 기반 제공자
+pub struct LrclibProvider {
+    http_client: reqwest::Client,
+}
+
+impl LrclibProvider {
+    pub fn new(http_client: reqwest::Client) -> Self {
+        Self { http_client }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LrclibEntry {
+    plain_lyrics: Option<String>,
+    synced_lyrics: Option<String>,
+}
+
+/// 검색 결과에서 싱크 가사가 있는 항목을 우선으로 고릅니다
+pub(crate) fn parse_lrclib_response(
+    body: &[u8],
+) -> Result<Option<Lyrics>, Box<dyn std::error::Error + Send + Sync>> {
+    let entries: Vec<LrclibEntry> = serde_json::from_slice(body)?;
+    let non_empty = |s: &Option<String>| s.as_deref().is_some_and(|s| !s.trim().is_empty());
+
+    let lyrics = entries
+        .iter()
+        .find(|e| non_empty(&e.synced_lyrics))
+        .and_then(|e| e.synced_lyrics.as_deref())
+        .or_else(|| {
+            entries
+                .iter()
+                .find(|e| non_empty(&e.plain_lyrics))
+                .and_then(|e| e.plain_lyrics.as_deref())
+        });
+    Ok(lyrics.map(|text| Lyrics::from_text("LRCLIB", text)))
+}
+
+#[async_trait]
+impl LyricsProvider for LrclibProvider {
+    async fn lyrics(
+        &self,
+        song: &Song,
+    ) -> Result<Option<Lyrics>, Box<dyn std::error::Error + Send + Sync>> {
+        let query = search_title(&song.title);
+        if query.is_empty() {
+            return Ok(None);
+        }
+        let body = self
+            .http_client
+            .get(LRCLIB_SEARCH_URL)
+            .query(&[("q", query.as_str())])
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        parse_lrclib_response(&body)
+    }
+}
+
+/// 디렉터리의 `.lrc`/`.txt` 파일에서 가사를 읽는 제공자 (테스트/오프라인용)
+///
+/// 파일 이름은 YouTube 영상 ID 또는 검색용 제목(`search_title`)입니다.
+pub struct LocalLyricsProvider {
+    dir: PathBuf,
+}
+
+impl LocalLyricsProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn candidates(&self, song: &Song) -> Vec<PathBuf> {
+        let mut stems = Vec::new();
+        if let Some(id) = youtube_video_id(&song.url) {
+            stems.push(id);
+        }
+        let title = search_title(&song.title);
+        if !title.is_empty() && !title.contains(['/', '\\']) {
+            stems.push(title);
+        }
+        stems
+            .iter()
+            .flat_map(|stem| ["lrc", "txt"].map(|ext| self.dir.join(format!("{stem}.{ext}"))))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for LocalLyricsProvider {
+    async fn lyrics(
+        &self,
+        song: &Song,
+    ) -> Result<Option<Lyrics>, Box<dyn std::error::Error + Send + Sync>> {
+        for path in self.candidates(song) {
+            match tokio::fs::read_to_string(&path).await {
+                Ok(text) => return Ok(Some(Lyrics::from_text("로컬 파일", &text))),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// 앞의 제공자부터 차례로 시도합니다. 실패한 제공자는 건너뜁니다.
+pub struct ChainedLyricsProvider {
+    providers: Vec<Arc<dyn LyricsProvider>>,
+}
+
+impl ChainedLyricsProvider {
+    pub fn new(providers: Vec<Arc<dyn LyricsProvider>>) -> Self {
+        Self { providers }
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for ChainedLyricsProvider {
+    async fn lyrics(
+        &self,
+        song: &Song,
+    ) -> Result<Option<Lyrics>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut last_error = None;
+        for provider in &self.providers {
+            match provider.lyrics(song).await {
+                Ok(Some(lyrics)) => return Ok(Some(lyrics)),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("가사 조회 실패: {e}");
+                    last_error = Some(e);
+                }
+            }
+        }
+        match last_error {
+            Some(e) => Err(e),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LRC: &str = "[ar:Artist]\n[ti:Title]\n[00:12.50]첫 줄\n[00:05.00]\n[00:20.00][01:00.00]후렴\nnot a lyric\n[bad]";
+
+    #[test]
+    fn test_parse_lrc() {
+        let lines = parse_lrc(LRC);
+        let times: Vec<f64> = lines.iter().map(|l| l.time_secs).collect();
+        assert_eq!(times, [5.0, 12.5, 20.0, 60.0]);
+        assert_eq!(lines[1].text, "첫 줄");
+        assert_eq!(lines[3].text, "후렴");
+        assert!(parse_lrc("그냥 가사\n두 번째 줄").is_empty());
+    }
+
+    #[test]
+    fn test_from_text() {
+        let synced = Lyrics::from_text("test", LRC);
+        assert!(synced.is_synced());
+        assert_eq!(synced.plain, "\n첫 줄\n후렴\n후렴");
+
+        let plain = Lyrics::from_text("test", "  가사\n두 번째 줄\n");
+        assert!(!plain.is_synced());
+        assert_eq!(plain.plain, "가사\n두 번째 줄");
+    }
+
+    #[test]
+    fn test_current_line_and_window() {
+        let lines = parse_lrc(LRC);
+        assert_eq!(current_line(&lines, 0.0), None);
+        assert_eq!(current_line(&lines, 12.5), Some(1));
+        assert_eq!(current_line(&lines, 30.0), Some(2));
+        assert_eq!(current_line(&lines, 999.0), Some(3));
+
+        assert_eq!(synced_window(10, None, 2, 3), 0..4);
+        assert_eq!(synced_window(10, Some(5), 2, 3), 3..9);
+        assert_eq!(synced_window(10, Some(9), 2, 3), 7..10);
+    }
+
+    #[test]
+    fn test_paginate() {
+        let text = "aaaa\nbbbb\ncccc";
+        assert_eq!(paginate(text, 10), vec!["aaaa\nbbbb", "cccc"]);
+        assert_eq!(paginate(text, 100), vec![text]);
+        assert!(paginate("", 10).is_empty());
+        // 한 줄이 너무 길면 잘라서 넣음
+        assert_eq!(paginate("abcdefghijkl", 5), vec!["abcde"]);
+    }
+
+    #[test]
+    fn test_search_title() {
+        assert_eq!(search_title("IU - Blueming (Official MV)"), "IU - Blueming");
+        assert_eq!(search_title("[MV] 좋은 날  【4K】"), "좋은 날");
+    }
+
+    #[test]
+    fn test_parse_lrclib_response_prefers_synced() {
+        let body = br#"[
+            {"plainLyrics": "plain only", "syncedLyrics": null},
+            {"plainLyrics": "line", "syncedLyrics": "[00:01.00]line"}
+        ]"#;
+        let lyrics = parse_lrclib_response(body).unwrap().unwrap();
+        assert!(lyrics.is_synced());
+        assert_eq!(lyrics.source, "LRCLIB");
+
+        let body = br#"[{"plainLyrics": "plain only", "syncedLyrics": ""}]"#;
+        let lyrics = parse_lrclib_response(body).unwrap().unwrap();
+        assert_eq!(lyrics.plain, "plain only");
+
+        assert!(parse_lrclib_response(b"[]").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_local_provider_and_chain() {
+        let dir = std::env::temp_dir().join(format!("everybot-lyrics-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("dQw4w9WgXcQ.lrc"), "[00:01.00]never gonna").unwrap();
+        std::fs::write(dir.join("IU - Blueming.txt"), "블루밍 가사").unwrap();
+
+        let local = LocalLyricsProvider::new(&dir);
+        let by_id = Song {
+            title: "Rick Astley - Never Gonna Give You Up".to_string(),
+            url: "https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string(),
+            ..Default::default()
+        };
+        let lyrics = local.lyrics(&by_id).await.unwrap().unwrap();
+        assert!(lyrics.is_synced());
+        assert_eq!(lyrics.source, "로컬 파일");
+
+        let by_title = Song {
+            title: "IU - Blueming (Official MV)".to_string(),
+            ..Default::default()
+        };
+        let lyrics = local.lyrics(&by_title).await.unwrap().unwrap();
+        assert_eq!(lyrics.plain, "블루밍 가사");
+
+        let missing = Song {
+            title: "없는 곡".to_string(),
+            ..Default::default()
+        };
+        let chain = ChainedLyricsProvider::new(vec![
+            Arc::new(LocalLyricsProvider::new(dir.join("nowhere"))),
+            Arc::new(local),
+        ]);
+        assert!(chain.lyrics(&by_title).await.unwrap().is_some());
+        assert!(chain.lyrics(&missing).await.unwrap().is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,20 +1,77 @@
+pub mod cache;
+pub mod chapters;
+pub mod driver;
+pub mod eq;
+pub mod favorites;
+pub mod feed;
+pub mod import;
+pub mod lyrics;
 pub mod player;
+pub mod policy;
 pub mod queue;
+pub mod quiz;
+pub mod radio;
+pub mod segments;
+pub mod settings;
 pub mod source;
+pub mod stage;
+pub mod stats;
 
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 
-use serenity::model::id::GuildId;
+use serde::{Deserialize, Serialize};
+use serenity::model::id::{ChannelId, GuildId, UserId};
 use songbird::tracks::TrackHandle;
-use tokio::sync::RwLock;
+use tokio::sync::{OwnedRwLockReadGuard, OwnedRwLockWriteGuard, RwLock};
 
-#[derive(Clone, Debug)]
+use segments::SegmentProvider;
+use stage::StageTopic;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+    pub title: String,
+    pub start_secs: f64,
+    pub end_secs: f64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Song {
     pub title: String,
     pub url: String,
     pub duration: Option<String>,
     pub requester: String,
+    /// 신청한 사용자 (자동 재생이나 HTTP API로 추가된 곡은 없음)
+    #[serde(default)]
+    pub requester_id: Option<UserId>,
+    pub chapters: Vec<Chapter>,
+    pub thumbnail: Option<String>,
+    pub uploader: Option<String>,
+    /// `YYYY-MM-DD` 형식
+    pub upload_date: Option<String>,
+    pub view_count: Option<u64>,
+    pub is_live: bool,
+    /// 인터넷 라디오 (끊기면 다시 연결하고, 건너뛸 수 없음)
+    #[serde(default)]
+    pub is_radio: bool,
+    /// 라디오에서 지금 방송 중인 곡 (ICY 메타데이터)
+    #[serde(default)]
+    pub stream_title: Option<String>,
+}
+
+impl Song {
+    /// 임베드에 표시할 요청자 (사용자 ID가 있으면 멘션)
+    pub fn requester_mention(&self) -> String {
+        match self.requester_id {
+            Some(id) => format!("<@{id}>"),
+            None => self.requester.clone(),
+        }
+    }
+
+    /// `user_id`가 신청한 곡인지
+    pub fn is_requested_by(&self, user_id: UserId) -> bool {
+        self.requester_id == Some(user_id)
+    }
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -25,6 +82,17 @@ pub enum LoopMode {
     Queue,
 }
 
+impl LoopMode {
+    /// 컨트롤러 버튼용 순환 순서: 끔 → 한 곡 → 전체 → 끔
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Off => Self::Song,
+            Self::Song => Self::Queue,
+            Self::Queue => Self::Off,
+        }
+    }
+}
+
 impl std::fmt::Display for LoopMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -35,12 +103,133 @@ impl std::fmt::Display for LoopMode {
     }
 }
 
+/// 대기열이 비었을 때 이어서 재생할 곡 (24/7 모드)
+#[derive(Clone, Debug, PartialEq)]
+pub enum IdleFallback {
+    /// 마지막 곡을 기반으로 한 YouTube 믹스에서 이어서 재생
+    Autoplay,
+    /// 저장된 재생목록 URL
+    Playlist(String),
+}
+
+impl IdleFallback {
+    /// `autoplay` 또는 재생목록 URL. `to_string()`의 역변환이기도 합니다.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        match input.to_lowercase().as_str() {
+            "autoplay" | "자동" | "자동재생" => Some(Self::Autoplay),
+            _ if source::is_url(input) => Some(Self::Playlist(input.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for IdleFallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Autoplay => write!(f, "autoplay"),
+            Self::Playlist(url) => write!(f, "{url}"),
+        }
+    }
+}
+
+/// /volume 인자: 절대값(`50`) 또는 상대값(`+10`, `-10`)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VolumeChange {
+    Set(u32),
+    Relative(i32),
+}
+
+impl VolumeChange {
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim().trim_end_matches('%');
+        if input.starts_with(['+', '-']) {
+            input.parse().ok().map(Self::Relative)
+        } else {
+            input.parse().ok().map(Self::Set)
+        }
+    }
+
+    /// 현재 볼륨(%)에 적용한 결과. 절대값은 0~100 범위를 벗어나면 `None`,
+    /// 상대값은 범위 안으로 잘라냅니다.
+    pub fn apply(self, current: u32) -> Option<u32> {
+        match self {
+            Self::Set(level) => (level <= 100).then_some(level),
+            Self::Relative(delta) => Some((current as i32 + delta).clamp(0, 100) as u32),
+        }
+    }
+}
+
+/// 셔플 모드 상태
+#[derive(Clone, Debug, Default)]
+pub struct ShuffleState {
+    /// 셔플 전 원래 순서 (/unshuffle 시 복원)
+    pub original_order: Vec<Song>,
+    /// 이번 순환에서 아직 재생되지 않은 곡 수 (전체 반복 시 0이 되면 다시 셔플)
+    pub cycle_left: usize,
+}
+
+/// /undo로 되돌릴 수 있는 대기열 변경
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QueueEdit {
+    Shuffle,
+    Unshuffle,
+    Remove,
+    Move,
+    Stop,
+}
+
+impl std::fmt::Display for QueueEdit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shuffle => write!(f, "셔플"),
+            Self::Unshuffle => write!(f, "셔플 해제"),
+            Self::Remove => write!(f, "곡 제거"),
+            Self::Move => write!(f, "곡 이동"),
+            Self::Stop => write!(f, "정지"),
+        }
+    }
+}
+
+/// 대기열을 바꾸기 직전의 상태
+#[derive(Clone, Debug)]
+pub struct QueueSnapshot {
+    pub edit: QueueEdit,
+    pub songs: VecDeque<Song>,
+    pub current_song: Option<Song>,
+    pub history: Vec<Song>,
+    pub shuffle: Option<ShuffleState>,
+}
+
 pub struct GuildQueue {
     pub songs: VecDeque<Song>,
     pub current_song: Option<Song>,
+    /// 재생이 끝난 곡들 (최근 곡이 마지막, /previous용)
+    pub history: Vec<Song>,
     pub loop_mode: LoopMode,
+    pub shuffle: Option<ShuffleState>,
+    /// 대기열 순서가 바뀔 때마다 증가 (오래된 인덱스로 동작하는 것을 방지)
+    pub version: u64,
     pub volume: f32,
     pub track_handle: Option<TrackHandle>,
+    /// 봇이 이 서버에서 접속해 있는 음성 채널
+    pub voice_channel: Option<ChannelId>,
+    /// 스테이지 채널에 있으면 곡이 바뀔 때 주제를 갱신
+    pub stage_topic: Option<StageTopic>,
+    /// 대기열이 비면 이어서 재생할 곡 (24/7 모드에서 설정)
+    pub idle_fallback: Option<IdleFallback>,
+    /// 설정되어 있으면 재생 시 스폰서/인트로 등 구간을 자동으로 건너뜁니다
+    pub segment_provider: Option<Arc<dyn SegmentProvider>>,
+    /// 재생 중인 트랙의 디코더와 공유하는 EQ (바꾸면 바로 반영)
+    pub equalizer: eq::SharedEq,
+    /// 대기열 변경 전 상태 (최근 변경이 마지막, /undo용)
+    pub undo_stack: Vec<QueueSnapshot>,
+    /// 듣는 사람이 없어 자동으로 일시정지했는지 (직접 일시정지한 곡은 자동으로 재개하지 않음)
+    pub auto_paused: bool,
+    /// 듣는 사람이 없어 기다리는 퇴장 타이머 (사람이 돌아오면 취소)
+    pub idle_timer: Option<u64>,
+    /// 곡을 추가할 때 검사하는 중복/길이/라이브/차단 정책
+    pub policy: policy::EnqueuePolicy,
 }
 
 impl Default for GuildQueue {
@@ -48,23 +237,130 @@ impl Default for GuildQueue {
         Self {
             songs: VecDeque::new(),
             current_song: None,
+            history: Vec::new(),
             loop_mode: LoopMode::Off,
+            shuffle: None,
+            version: 0,
             volume: 0.5,
             track_handle: None,
+            voice_channel: None,
+            stage_topic: None,
+            idle_fallback: None,
+            segment_provider: None,
+            equalizer: Default::default(),
+            undo_stack: Vec::new(),
+            auto_paused: false,
+            idle_timer: None,
+            policy: Default::default(),
         }
     }
 }
 
-pub type QueueManager = Arc<RwLock<HashMap<GuildId, GuildQueue>>>;
+/// 서버별 큐 저장소
+///
+/// 서버마다 별도의 잠금을 두어 한 서버의 곡 종료 처리나 명령이 다른 서버를 기다리게
+/// 하지 않습니다. 바깥 맵 잠금은 서버 큐의 `Arc`를 꺼내는 동안에만 잡고, `await`를
+/// 넘어 유지하지 않습니다.
+#[derive(Default)]
+pub struct GuildQueues {
+    guilds: std::sync::RwLock<HashMap<GuildId, Arc<RwLock<GuildQueue>>>>,
+    feed: feed::TrackFeed,
+}
+
+impl GuildQueues {
+    /// 곡 전환 알림 채널
+    pub fn feed(&self) -> &feed::TrackFeed {
+        &self.feed
+    }
+
+    pub fn get(&self, guild_id: GuildId) -> Option<Arc<RwLock<GuildQueue>>> {
+        self.guilds.read().unwrap().get(&guild_id).cloned()
+    }
+
+    /// 서버 큐를 가져오고, 없으면 새로 만듭니다
+    pub fn entry(&self, guild_id: GuildId) -> Arc<RwLock<GuildQueue>> {
+        if let Some(queue) = self.get(guild_id) {
+            return queue;
+        }
+        self.guilds
+            .write()
+            .unwrap()
+            .entry(guild_id)
+            .or_default()
+            .clone()
+    }
+
+    /// 서버 큐의 읽기 잠금 (큐가 없으면 `None`)
+    pub async fn read(&self, guild_id: GuildId) -> Option<OwnedRwLockReadGuard<GuildQueue>> {
+        Some(self.get(guild_id)?.read_owned().await)
+    }
+
+    /// 서버 큐의 쓰기 잠금 (없으면 새로 만듭니다)
+    pub async fn write(&self, guild_id: GuildId) -> OwnedRwLockWriteGuard<GuildQueue> {
+        self.entry(guild_id).write_owned().await
+    }
+
+    /// 큐가 만들어진 서버 목록
+    pub fn guild_ids(&self) -> Vec<GuildId> {
+        self.guilds.read().unwrap().keys().copied().collect()
+    }
+
+    /// 큐가 만들어진 서버 수
+    pub fn len(&self) -> usize {
+        self.guilds.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.guilds.read().unwrap().is_empty()
+    }
+}
+
+pub type QueueManager = Arc<GuildQueues>;
 
 pub fn new_queue_manager() -> QueueManager {
-    Arc::new(RwLock::new(HashMap::new()))
+    Arc::new(GuildQueues::default())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_idle_fallback_parse_roundtrip() {
+        assert_eq!(
+            IdleFallback::parse("autoplay"),
+            Some(IdleFallback::Autoplay)
+        );
+        assert_eq!(IdleFallback::parse("자동"), Some(IdleFallback::Autoplay));
+        assert_eq!(IdleFallback::parse("lofi"), None);
+
+        let playlist = IdleFallback::parse("https://youtube.com/playlist?list=abc").unwrap();
+        assert_eq!(IdleFallback::parse(&playlist.to_string()), Some(playlist));
+        assert_eq!(
+            IdleFallback::parse(&IdleFallback::Autoplay.to_string()),
+            Some(IdleFallback::Autoplay)
+        );
+    }
+
+    #[test]
+    fn test_volume_change_parse() {
+        assert_eq!(VolumeChange::parse("50"), Some(VolumeChange::Set(50)));
+        assert_eq!(VolumeChange::parse("+10"), Some(VolumeChange::Relative(10)));
+        assert_eq!(
+            VolumeChange::parse("-10%"),
+            Some(VolumeChange::Relative(-10))
+        );
+        assert_eq!(VolumeChange::parse("loud"), None);
+    }
+
+    #[test]
+    fn test_volume_change_apply() {
+        assert_eq!(VolumeChange::Set(30).apply(50), Some(30));
+        assert_eq!(VolumeChange::Set(150).apply(50), None);
+        assert_eq!(VolumeChange::Relative(10).apply(95), Some(100));
+        assert_eq!(VolumeChange::Relative(-10).apply(5), Some(0));
+    }
+
     #[test]
     fn test_loop_mode_display() {
         assert_eq!(LoopMode::Off.to_string(), "끔");
@@ -72,13 +368,40 @@ mod tests {
         assert_eq!(LoopMode::Queue.to_string(), "전체 반복");
     }
 
+    #[test]
+    fn test_loop_mode_next_cycles() {
+        assert_eq!(LoopMode::Off.next(), LoopMode::Song);
+        assert_eq!(LoopMode::Song.next(), LoopMode::Queue);
+        assert_eq!(LoopMode::Queue.next(), LoopMode::Off);
+    }
+
     #[test]
     fn test_guild_queue_default() {
         let q = GuildQueue::default();
         assert!(q.songs.is_empty());
         assert!(q.current_song.is_none());
+        assert!(q.history.is_empty());
         assert_eq!(q.loop_mode, LoopMode::Off);
+        assert!(q.shuffle.is_none());
         assert!((q.volume - 0.5).abs() < f32::EPSILON);
         assert!(q.track_handle.is_none());
+        assert!(q.segment_provider.is_none());
+        assert!(q.equalizer.read().unwrap().is_flat());
+        assert!(q.undo_stack.is_empty());
+    }
+
+    #[test]
+    fn test_requester_mention_and_ownership() {
+        let mut song = Song {
+            requester: "alice".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(song.requester_mention(), "alice");
+        assert!(!song.is_requested_by(UserId::new(42)));
+
+        song.requester_id = Some(UserId::new(42));
+        assert_eq!(song.requester_mention(), "<@42>");
+        assert!(song.is_requested_by(UserId::new(42)));
+        assert!(!song.is_requested_by(UserId::new(7)));
     }
 }
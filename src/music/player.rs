@@ -1,34 +1,107 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use serenity::model::id::GuildId;
-use songbird::events::{Event, EventContext, EventHandler, TrackEvent};
-use songbird::input::YoutubeDl;
-use songbird::Call;
+use songbird::events::{Event, EventContext, EventHandler};
+use songbird::input::Input;
+use songbird::tracks::PlayMode;
 use tokio::sync::Mutex;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
-use super::queue;
-use super::QueueManager;
-use super::Song;
+use super::driver::VoiceDriver;
+use super::policy::same_song;
+use super::quiz::QuizGames;
+use super::segments::{SegmentSkipper, SKIP_CHECK_INTERVAL};
+use super::settings::GuildSettings;
+use super::source::{self, StreamSource, YtDlp};
+use super::stats::{self, PlayEvent, PlayOutcome, PlayStats};
+use super::{eq, queue, radio};
+use super::{IdleFallback, QueueManager, Song};
 
-struct TrackEndNotifier {
+/// 자동 재생 시 한 번에 가져올 곡 수
+const AUTOPLAY_BATCH: usize = 10;
+/// 저장된 재생목록에서 한 번에 가져올 곡 수
+const PLAYLIST_BATCH: usize = 50;
+/// 스킵한 트랙의 재생 위치를 기다리는 최대 시간 (음성 연결이 없으면 응답하지 않음)
+const SKIP_INFO_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 재생에 함께 쓰는 자원. 시작 시 한 번 만들어 봇과 API가 공유합니다.
+#[derive(Clone)]
+pub struct Services {
+    pub http_client: reqwest::Client,
+    pub ytdlp: Arc<YtDlp>,
+    /// 재생 기록 저장소 (DB를 열지 못했으면 `None`)
+    pub stats: Option<Arc<PlayStats>>,
+    /// 진행 중인 음악 퀴즈 (퀴즈 중에는 대기열 곡을 바꾸지 않음)
+    pub quiz_games: Arc<QuizGames>,
+}
+
+struct TrackEndNotifier<D: VoiceDriver> {
     guild_id: GuildId,
     queue_manager: QueueManager,
-    http_client: reqwest::Client,
-    call: Arc<Mutex<Call>>,
+    services: Services,
+    call: Arc<Mutex<D>>,
 }
 
 #[async_trait]
-impl EventHandler for TrackEndNotifier {
-    async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
+impl<D: VoiceDriver> EventHandler for TrackEndNotifier<D> {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
         let guild_id = self.guild_id;
         let queue_manager = self.queue_manager.clone();
-        let http_client = self.http_client.clone();
+        let services = self.services.clone();
         let call = self.call.clone();
+        let ended = match ctx {
+            EventContext::Track(&[(state, handle)]) => Some((state.play_time, handle.uuid())),
+            _ => None,
+        };
 
         tokio::spawn(async move {
-            if let Err(e) = play_next(guild_id, &queue_manager, &http_client, &call, false).await {
+            // /leave 등으로 퇴장하면서 멈춘 트랙은 다음 곡으로 넘어가지 않음
+            if queue::get_voice_channel(&queue_manager, guild_id)
+                .await
+                .is_none()
+            {
+                return;
+            }
+            // 퀴즈 중에는 발췌 곡을 끊지 않도록 넘어가지 않음 (퀴즈가 끝나면 이어서 재생)
+            if services.quiz_games.is_running(guild_id) {
+                return;
+            }
+
+            // 라디오 연결이 끊기면 잠시 후 다시 연결하고, 곧바로 끊기는 방송국은 건너뜀
+            let mut skipped = false;
+            if queue::get_current(&queue_manager, guild_id)
+                .await
+                .is_some_and(|song| song.is_radio)
+            {
+                match ended {
+                    Some((played, _)) if played < radio::MIN_PLAY_TIME => {
+                        warn!("라디오가 바로 끊겨 다음 곡으로 넘어갑니다 (guild: {guild_id})");
+                        skipped = true;
+                    }
+                    _ => {
+                        tokio::time::sleep(radio::RECONNECT_DELAY).await;
+                        // 기다리는 동안 /radio stop 등으로 다른 곡이 시작됐으면 그대로 둠
+                        let replaced = match (
+                            ended,
+                            queue::get_track_handle(&queue_manager, guild_id).await,
+                        ) {
+                            (Some((_, uuid)), Some(current)) => current.uuid() != uuid,
+                            _ => false,
+                        };
+                        if replaced
+                            || queue::get_voice_channel(&queue_manager, guild_id)
+                                .await
+                                .is_none()
+                        {
+                            return;
+                        }
+                    }
+                }
+            }
+
+            if let Err(e) = play_next(guild_id, &queue_manager, &services, &call, skipped).await {
                 error!("다음 곡 재생 실패: {e}");
             }
         });
@@ -37,66 +110,308 @@ impl EventHandler for TrackEndNotifier {
     }
 }
 
-pub async fn play_song(
+/// 곡이 끝까지 재생되면 재생 기록을 남깁니다 (스킵은 `play_next`에서 기록)
+struct PlayRecorder {
+    stats: Arc<PlayStats>,
+    guild_id: GuildId,
+    song: Song,
+    started_at: i64,
+}
+
+#[async_trait]
+impl EventHandler for PlayRecorder {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        let EventContext::Track(&[(state, _)]) = ctx else {
+            return None;
+        };
+        // 재생 실패는 들은 곡이 아니므로 기록하지 않음
+        if state.playing != PlayMode::End {
+            return None;
+        }
+        self.stats.record(&PlayEvent {
+            guild_id: self.guild_id,
+            title: self.song.title.clone(),
+            url: self.song.url.clone(),
+            requester: self.song.requester.clone(),
+            requester_id: self.song.requester_id,
+            started_at: self.started_at,
+            listened: state.play_time,
+            outcome: PlayOutcome::Finished,
+        });
+        None
+    }
+}
+
+/// EQ를 끼운 입력. 곡 도중에 EQ를 켜도 반영되도록 꺼져 있을 때도 감쌉니다.
+///
+/// 디코더를 감싸려면 재생 전에 스트림을 미리 열어야 하므로, 실패하면 EQ 없이 다시 만듭니다.
+async fn equalized_input(
+    guild_id: GuildId,
+    queue_manager: &QueueManager,
+    services: &Services,
+    song: &Song,
+) -> Result<Input, Box<dyn std::error::Error + Send + Sync>> {
+    let stream = stream_source(services, song).await?;
+    let shared = queue::shared_equalizer(queue_manager, guild_id).await;
+    match eq::with_equalizer(stream.input(&services.http_client), shared).await {
+        Ok(input) => Ok(input),
+        Err(e) => {
+            tracing::warn!("EQ 적용 실패, EQ 없이 재생: {e}");
+            Ok(stream.input(&services.http_client))
+        }
+    }
+}
+
+/// 라디오는 스트림 주소를 직접 열고, 나머지는 yt-dlp로 가져옵니다
+async fn stream_source(
+    services: &Services,
+    song: &Song,
+) -> Result<StreamSource, Box<dyn std::error::Error + Send + Sync>> {
+    if song.is_radio {
+        Ok(StreamSource::direct(song.url.clone()))
+    } else {
+        services.ytdlp.stream_source(&song.url).await
+    }
+}
+
+pub async fn play_song<D: VoiceDriver>(
     guild_id: GuildId,
     queue_manager: &QueueManager,
-    http_client: &reqwest::Client,
-    call: &Arc<Mutex<Call>>,
+    services: &Services,
+    call: &Arc<Mutex<D>>,
     song: &Song,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let src = YoutubeDl::new(http_client.clone(), song.url.clone());
+    let input = equalized_input(guild_id, queue_manager, services, song).await?;
     let volume = queue::get_volume(queue_manager, guild_id).await;
 
     let track_handle = {
         let mut handler = call.lock().await;
-        let track_handle = handler.play_only(src.into());
+        let track_handle = handler.play_only(input.into());
         let _ = track_handle.set_volume(volume);
 
-        track_handle.add_event(
-            Event::Track(TrackEvent::End),
+        handler.add_end_event(
+            &track_handle,
             TrackEndNotifier {
                 guild_id,
                 queue_manager: queue_manager.clone(),
-                http_client: http_client.clone(),
+                services: services.clone(),
                 call: call.clone(),
             },
         )?;
+        if let Some(stats) = &services.stats {
+            handler.add_end_event(
+                &track_handle,
+                PlayRecorder {
+                    stats: stats.clone(),
+                    guild_id,
+                    song: song.clone(),
+                    started_at: stats::now_secs(),
+                },
+            )?;
+        }
 
         track_handle
     }; // handler lock dropped here
 
-    {
-        let mut queues = queue_manager.write().await;
-        if let Some(q) = queues.get_mut(&guild_id) {
-            q.track_handle = Some(track_handle);
-        }
+    if let Some(guild) = queue_manager.get(guild_id) {
+        let mut q = guild.write().await;
+        // 곡 준비 중에 /volume이 바뀌었을 수 있으므로 최신 값으로 다시 적용
+        let _ = track_handle.set_volume(q.volume);
+        q.track_handle = Some(track_handle.clone());
     }
 
+    if song.is_radio {
+        radio::watch_stream_title(
+            guild_id,
+            queue_manager.clone(),
+            services.http_client.clone(),
+            track_handle.clone(),
+            song.url.clone(),
+        );
+    }
+
+    // 구간 조회가 재생 시작을 늦추지 않도록 백그라운드에서 등록 (라이브는 탐색 불가)
+    let provider = if song.is_live {
+        None
+    } else {
+        queue::get_segment_provider(queue_manager, guild_id).await
+    };
+    if let Some(provider) = provider {
+        let song = song.clone();
+        tokio::spawn(async move {
+            match provider.segments(&song).await {
+                Ok(segments) if !segments.is_empty() => {
+                    let _ = track_handle.add_event(
+                        Event::Periodic(SKIP_CHECK_INTERVAL, None),
+                        SegmentSkipper::new(segments),
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => warn!("건너뛸 구간 조회 실패: {e}"),
+            }
+        });
+    }
+
+    if let Some(stage) = queue::get_stage_topic(queue_manager, guild_id).await {
+        let title = song.title.clone();
+        tokio::spawn(async move { stage.update(&title).await });
+    }
+
+    queue_manager.feed().publish(guild_id, Some(song.clone()));
     info!("재생 시작: {}", song.title);
     Ok(())
 }
 
-pub async fn play_next(
+/// 재생 중인 곡이 없을 때 대기열의 첫 곡을 재생합니다.
+///
+/// 새로 재생을 시작하므로 이 서버의 마지막 볼륨과 EQ를 복원합니다.
+/// 음악 퀴즈 중에는 시작하지 않고 `None`을 반환합니다 (퀴즈가 끝나면 시작).
+pub async fn start_playback<D: VoiceDriver>(
     guild_id: GuildId,
     queue_manager: &QueueManager,
-    http_client: &reqwest::Client,
-    call: &Arc<Mutex<Call>>,
+    services: &Services,
+    settings: Option<&GuildSettings>,
+    call: &Arc<Mutex<D>>,
+) -> Result<Option<Song>, Box<dyn std::error::Error + Send + Sync>> {
+    if services.quiz_games.is_running(guild_id) {
+        return Ok(None);
+    }
+    if let Some(settings) = settings {
+        if let Some(volume) = settings.volume(guild_id) {
+            queue::set_volume(queue_manager, guild_id, volume).await;
+        }
+        if let Some(bands) = settings.equalizer(guild_id) {
+            queue::set_equalizer(queue_manager, guild_id, bands).await;
+        }
+    }
+
+    let next = queue::get_next_song(queue_manager, guild_id, false).await;
+    if let Some(song) = &next {
+        play_song(guild_id, queue_manager, services, call, song).await?;
+    }
+    Ok(next)
+}
+
+/// 대기열이 비었을 때 24/7 설정에 따라 자동 재생 또는 저장된 재생목록으로 채웁니다
+async fn refill_from_fallback(
+    guild_id: GuildId,
+    queue_manager: &QueueManager,
+    ytdlp: &YtDlp,
+) -> Option<Song> {
+    let fallback = queue::get_idle_fallback(queue_manager, guild_id).await?;
+    let history = queue::get_history(queue_manager, guild_id).await;
+
+    let (url, limit) = match &fallback {
+        IdleFallback::Autoplay => (source::autoplay_url(history.last()?)?, AUTOPLAY_BATCH),
+        IdleFallback::Playlist(url) => (url.clone(), PLAYLIST_BATCH),
+    };
+
+    let songs = match ytdlp.playlist_songs(&url, limit).await {
+        Ok(songs) => songs,
+        Err(e) => {
+            warn!("대체 재생목록 조회 실패: {e}");
+            return None;
+        }
+    };
+
+    let mut added = 0;
+    for mut song in songs {
+        // 믹스는 방금 들은 곡으로 시작하므로 최근 재생한 곡은 제외
+        if fallback == IdleFallback::Autoplay && history.iter().any(|h| same_song(h, &song)) {
+            continue;
+        }
+        song.requester = "자동 재생".to_string();
+        // 서버 정책에 걸린 곡은 조용히 건너뜀
+        if queue::add_song(queue_manager, guild_id, song).await.is_ok() {
+            added += 1;
+        }
+    }
+
+    if added == 0 {
+        return None;
+    }
+    info!("대기열이 비어 {added}곡을 이어서 추가했습니다 (guild: {guild_id})");
+    queue::get_next_song(queue_manager, guild_id, true).await
+}
+
+/// 중간에 멈춘 곡을 스킵으로 기록합니다.
+///
+/// 다음 곡으로 교체되거나 정지·퇴장으로 끊긴 트랙은 종료 이벤트가 오지 않으므로
+/// 대기열에서 지우기 전에 직접 남깁니다. 이미 끝난 트랙은 `PlayRecorder`가 기록했으므로
+/// 건너뜁니다 (라디오가 바로 끊긴 경우 등).
+pub async fn record_skipped(guild_id: GuildId, queue_manager: &QueueManager, services: &Services) {
+    let Some(stats) = &services.stats else {
+        return;
+    };
+    let (Some(song), Some(handle)) = (
+        queue::get_current(queue_manager, guild_id).await,
+        queue::get_track_handle(queue_manager, guild_id).await,
+    ) else {
+        return;
+    };
+    let listened = match tokio::time::timeout(SKIP_INFO_TIMEOUT, handle.get_info()).await {
+        Ok(Ok(info)) if !info.playing.is_done() => info.position,
+        Ok(_) => return,
+        Err(_) => Duration::ZERO,
+    };
+
+    stats.record(&PlayEvent {
+        guild_id,
+        title: song.title,
+        url: song.url,
+        requester: song.requester,
+        requester_id: song.requester_id,
+        started_at: stats::now_secs() - listened.as_secs() as i64,
+        listened,
+        outcome: PlayOutcome::Skipped,
+    });
+}
+
+pub async fn play_next<D: VoiceDriver>(
+    guild_id: GuildId,
+    queue_manager: &QueueManager,
+    services: &Services,
+    call: &Arc<Mutex<D>>,
     was_skipped: bool,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let next = queue::get_next_song(queue_manager, guild_id, was_skipped).await;
+    services.quiz_games.check_music(guild_id)?;
+    if was_skipped {
+        record_skipped(guild_id, queue_manager, services).await;
+    }
+    let next = match queue::get_next_song(queue_manager, guild_id, was_skipped).await {
+        Some(song) => Some(song),
+        None => refill_from_fallback(guild_id, queue_manager, &services.ytdlp).await,
+    };
 
     match next {
         Some(song) => {
-            play_song(guild_id, queue_manager, http_client, call, &song).await?;
+            play_song(guild_id, queue_manager, services, call, &song).await?;
         }
         None => {
             info!("큐가 비었습니다 (guild: {guild_id})");
-            let mut queues = queue_manager.write().await;
-            if let Some(q) = queues.get_mut(&guild_id) {
-                q.track_handle = None;
+            if let Some(guild) = queue_manager.get(guild_id) {
+                guild.write().await.track_handle = None;
             }
+            queue_manager.feed().publish(guild_id, None);
         }
     }
 
     Ok(())
 }
+
+/// 직전에 재생한 곡으로 돌아갑니다. 기록이 없으면 `None`.
+pub async fn play_previous<D: VoiceDriver>(
+    guild_id: GuildId,
+    queue_manager: &QueueManager,
+    services: &Services,
+    call: &Arc<Mutex<D>>,
+) -> Result<Option<Song>, Box<dyn std::error::Error + Send + Sync>> {
+    services.quiz_games.check_music(guild_id)?;
+    match queue::previous(queue_manager, guild_id).await {
+        Some(song) => {
+            play_song(guild_id, queue_manager, services, call, &song).await?;
+            Ok(Some(song))
+        }
+        None => Ok(None),
+    }
+}
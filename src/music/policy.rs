@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+
+use super::segments::youtube_video_id;
+use super::{quiz, Song};
+
+/// 이미 대기열에 있는 곡을 다시 넣을 때의 처리
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateMode {
+    /// 그대로 추가
+    Allow,
+    /// 추가하되 알림
+    #[default]
+    Warn,
+    /// 추가하지 않음
+    Reject,
+}
+
+impl DuplicateMode {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "allow" | "허용" => Some(Self::Allow),
+            "warn" | "경고" => Some(Self::Warn),
+            "reject" | "거부" => Some(Self::Reject),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for DuplicateMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Allow => write!(f, "허용"),
+            Self::Warn => write!(f, "경고 후 추가"),
+            Self::Reject => write!(f, "거부"),
+        }
+    }
+}
+
+/// 서버별 곡 추가 정책 (`queue::add_song`에서 검사)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnqueuePolicy {
+    pub duplicates: DuplicateMode,
+    /// 이보다 긴 곡은 추가하지 않음 (라이브는 길이가 없으므로 `allow_live`로 제한)
+    pub max_duration_secs: Option<u64>,
+    pub allow_live: bool,
+    /// URL이나 제목에 포함되면 추가하지 않을 문자열 (대소문자 무시)
+    pub blocklist: Vec<String>,
+}
+
+impl Default for EnqueuePolicy {
+    fn default() -> Self {
+        Self {
+            duplicates: DuplicateMode::default(),
+            max_duration_secs: None,
+            allow_live: true,
+            blocklist: Vec::new(),
+        }
+    }
+}
+
+/// 정책에 걸려 곡을 추가하지 않은 이유
+#[derive(Clone, Debug, PartialEq)]
+pub enum EnqueueError {
+    /// 같은 곡의 위치 (0이면 재생 중)
+    Duplicate {
+        position: usize,
+    },
+    TooLong {
+        limit_secs: u64,
+    },
+    Live,
+    Blocked {
+        pattern: String,
+    },
+}
+
+impl std::fmt::Display for EnqueueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Duplicate { position: 0 } => write!(f, "지금 재생 중인 곡입니다."),
+            Self::Duplicate { position } => {
+                write!(f, "이미 대기열 #{position}에 있는 곡입니다.")
+            }
+            Self::TooLong { limit_secs } => write!(
+                f,
+                "이 서버에서는 {} 이하의 곡만 추가할 수 있습니다.",
+                format_limit(*limit_secs)
+            ),
+            Self::Live => write!(f, "이 서버에서는 라이브 스트림을 추가할 수 없습니다."),
+            Self::Blocked { pattern } => {
+                write!(f, "차단 목록(`{pattern}`)에 걸려 추가할 수 없습니다.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EnqueueError {}
+
+/// `90` → `1분 30초`, `3600` → `60분`
+pub fn format_limit(secs: u64) -> String {
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{s}초"),
+        (m, 0) => format!("{m}분"),
+        (m, s) => format!("{m}분 {s}초"),
+    }
+}
+
+/// 같은 곡인지 (YouTube는 영상 ID로, 그 외는 URL로 비교)
+pub fn same_song(a: &Song, b: &Song) -> bool {
+    match (youtube_video_id(&a.url), youtube_video_id(&b.url)) {
+        (Some(a), Some(b)) => a == b,
+        _ => a.url == b.url,
+    }
+}
+
+impl EnqueuePolicy {
+    /// 대기열과 상관없는 검사 (차단 목록, 라이브, 길이)
+    pub fn check(&self, song: &Song) -> Result<(), EnqueueError> {
+        let url = song.url.to_lowercase();
+        let title = song.title.to_lowercase();
+        if let Some(pattern) = self.blocklist.iter().find(|pattern| {
+            let pattern = pattern.to_lowercase();
+            url.contains(&pattern) || title.contains(&pattern)
+        }) {
+            return Err(EnqueueError::Blocked {
+                pattern: pattern.clone(),
+            });
+        }
+
+        if song.is_live {
+            return if self.allow_live {
+                Ok(())
+            } else {
+                Err(EnqueueError::Live)
+            };
+        }
+
+        let length = song.duration.as_deref().and_then(quiz::duration_secs);
+        match (self.max_duration_secs, length) {
+            (Some(limit_secs), Some(length)) if length > limit_secs => {
+                Err(EnqueueError::TooLong { limit_secs })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 차단 목록에 추가합니다. 이미 있으면 `false`.
+    pub fn block(&mut self, pattern: &str) -> bool {
+        let pattern = pattern.trim();
+        if pattern.is_empty()
+            || self
+                .blocklist
+                .iter()
+                .any(|p| p.eq_ignore_ascii_case(pattern))
+        {
+            return false;
+        }
+        self.blocklist.push(pattern.to_string());
+        true
+    }
+
+    /// 차단 목록에서 뺍니다. 없으면 `false`.
+    pub fn unblock(&mut self, pattern: &str) -> bool {
+        let before = self.blocklist.len();
+        self.blocklist
+            .retain(|p| !p.eq_ignore_ascii_case(pattern.trim()));
+        self.blocklist.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(url: &str, duration: Option<&str>) -> Song {
+        Song {
+            title: "Some Title".to_string(),
+            url: url.to_string(),
+            duration: duration.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_default_policy_allows_everything() {
+        let policy = EnqueuePolicy::default();
+        assert_eq!(policy.duplicates, DuplicateMode::Warn);
+        assert!(policy.check(&song("https://a", Some("720:00"))).is_ok());
+        let live = Song {
+            is_live: true,
+            ..song("https://live", None)
+        };
+        assert!(policy.check(&live).is_ok());
+    }
+
+    #[test]
+    fn test_length_live_and_blocklist() {
+        let mut policy = EnqueuePolicy {
+            max_duration_secs: Some(600),
+            allow_live: false,
+            ..Default::default()
+        };
+        assert!(policy.check(&song("https://a", Some("9:59"))).is_ok());
+        assert!(policy.check(&song("https://a", Some("10:00"))).is_ok());
+        assert_eq!(
+            policy.check(&song("https://a", Some("1:00:00"))),
+            Err(EnqueueError::TooLong { limit_secs: 600 })
+        );
+        // Unknown length is let through
+        assert!(policy.check(&song("https://a", None)).is_ok());
+
+        let live = Song {
+            is_live: true,
+            ..song("https://live", None)
+        };
+        assert_eq!(policy.check(&live), Err(EnqueueError::Live));
+
+        assert!(policy.block("earrape"));
+        assert!(!policy.block("EARRAPE"));
+        assert!(policy.block("bad.example"));
+        let loud = Song {
+            title: "EarRape Remix".to_string(),
+            ..song("https://a", Some("1:00"))
+        };
+        assert_eq!(
+            policy.check(&loud),
+            Err(EnqueueError::Blocked {
+                pattern: "earrape".to_string()
+            })
+        );
+        assert!(policy
+            .check(&song("https://bad.example/x", Some("1:00")))
+            .is_err());
+
+        assert!(policy.unblock("Earrape"));
+        assert!(!policy.unblock("earrape"));
+        assert!(policy.check(&loud).is_ok());
+    }
+
+    #[test]
+    fn test_same_song_and_messages() {
+        let a = song("https://www.youtube.com/watch?v=dQw4w9WgXcQ", None);
+        let b = song("https://youtu.be/dQw4w9WgXcQ", None);
+        assert!(same_song(&a, &b));
+        assert!(!same_song(&a, &song("https://youtu.be/other", None)));
+
+        assert_eq!(format_limit(90), "1분 30초");
+        assert_eq!(format_limit(3600), "60분");
+        assert_eq!(
+            EnqueueError::Duplicate { position: 0 }.to_string(),
+            "지금 재생 중인 곡입니다."
+        );
+    }
+
+    #[test]
+    fn test_policy_json_roundtrip() {
+        let policy = EnqueuePolicy {
+            duplicates: DuplicateMode::Reject,
+            max_duration_secs: Some(900),
+            allow_live: false,
+            blocklist: vec!["spam".to_string()],
+        };
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(
+            serde_json::from_str::<EnqueuePolicy>(&json).unwrap(),
+            policy
+        );
+        // Missing fields fall back to defaults
+        let partial: EnqueuePolicy = serde_json::from_str(r#"{"allow_live":false}"#).unwrap();
+        assert_eq!(partial.duplicates, DuplicateMode::Warn);
+        assert!(!partial.allow_live);
+    }
+}
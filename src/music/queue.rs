@@ -1,12 +1,171 @@
-use serenity::model::id::GuildId;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use rand::seq::SliceRandom;
+use serenity::model::id::{ChannelId, GuildId, UserId};
+use songbird::tracks::TrackHandle;
+
+use super::eq::{EqBands, SharedEq};
+use super::policy::{same_song, DuplicateMode, EnqueueError, EnqueuePolicy};
+use super::segments::SegmentProvider;
+use super::stage::StageTopic;
+use super::{
+    GuildQueue, IdleFallback, LoopMode, QueueEdit, QueueManager, QueueSnapshot, ShuffleState, Song,
+};
+
+/// 이전 곡 기록 최대 개수
+const HISTORY_LIMIT: usize = 50;
+/// 되돌리기 기록 최대 개수
+const UNDO_LIMIT: usize = 10;
+
+fn push_history(history: &mut Vec<Song>, song: Song) {
+    history.push(song);
+    if history.len() > HISTORY_LIMIT {
+        history.remove(0);
+    }
+}
+
+/// 대기열을 바꾸기 전에 호출해 /undo로 되돌릴 수 있게 합니다
+fn push_undo(queue: &mut GuildQueue, edit: QueueEdit) {
+    queue.undo_stack.push(QueueSnapshot {
+        edit,
+        songs: queue.songs.clone(),
+        current_song: queue.current_song.clone(),
+        history: queue.history.clone(),
+        shuffle: queue.shuffle.clone(),
+    });
+    if queue.undo_stack.len() > UNDO_LIMIT {
+        queue.undo_stack.remove(0);
+    }
+}
+
+fn remove_first_by_url(songs: &mut Vec<Song>, url: &str) {
+    if let Some(i) = songs.iter().position(|s| s.url == url) {
+        songs.remove(i);
+    }
+}
+
+/// 셔플된 곡들을 원래 순서대로 되돌립니다 (URL 기준, 원래 순서에 없는 곡은 뒤에 유지)
+pub(crate) fn restore_order(songs: VecDeque<Song>, original_order: &[Song]) -> VecDeque<Song> {
+    let mut pool: Vec<Option<Song>> = songs.into_iter().map(Some).collect();
+    let mut restored = VecDeque::with_capacity(pool.len());
+
+    for original in original_order {
+        let slot = pool
+            .iter_mut()
+            .find(|s| s.as_ref().is_some_and(|s| s.url == original.url));
+        if let Some(song) = slot.and_then(Option::take) {
+            restored.push_back(song);
+        }
+    }
+
+    restored.extend(pool.into_iter().flatten());
+    restored
+}
+
+/// 곡 목록을 섞되, 방금 재생한 곡이 바로 다시 나오지 않도록 합니다
+fn shuffle_avoiding(songs: &mut VecDeque<Song>, last_url: Option<&str>) {
+    let slice = songs.make_contiguous();
+    slice.shuffle(&mut rand::thread_rng());
+    if let Some(url) = last_url {
+        if slice.len() > 1 && slice[0].url == url {
+            let swap_with = slice.iter().position(|s| s.url != url).unwrap_or(0);
+            slice.swap(0, swap_with);
+        }
+    }
+}
+
+/// 대기열에 곡을 추가한 결과
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Added {
+    /// 대기열에서의 위치 (1부터)
+    pub position: usize,
+    /// 중복 정책이 경고일 때 이미 있던 같은 곡의 위치 (0이면 재생 중)
+    pub duplicate_of: Option<usize>,
+}
+
+/// 재생 중인 곡과 대기열에서 같은 곡의 위치 (0이면 재생 중)
+fn find_duplicate(queue: &GuildQueue, song: &Song) -> Option<usize> {
+    if queue
+        .current_song
+        .as_ref()
+        .is_some_and(|current| same_song(current, song))
+    {
+        return Some(0);
+    }
+    queue
+        .songs
+        .iter()
+        .position(|queued| same_song(queued, song))
+        .map(|i| i + 1)
+}
 
-use super::{LoopMode, QueueManager, Song};
+/// 정책에 걸리면 그 이유를, 통과하면 경고할 중복 위치를 돌려줍니다
+fn apply_policy(queue: &GuildQueue, song: &Song) -> Result<Option<usize>, EnqueueError> {
+    queue.policy.check(song)?;
+    match queue.policy.duplicates {
+        DuplicateMode::Allow => Ok(None),
+        DuplicateMode::Warn => Ok(find_duplicate(queue, song)),
+        DuplicateMode::Reject => match find_duplicate(queue, song) {
+            Some(position) => Err(EnqueueError::Duplicate { position }),
+            None => Ok(None),
+        },
+    }
+}
+
+/// 곡을 추가하지 않고 정책만 미리 검사합니다 (음성 채널에 들어가기 전에 사용)
+pub async fn check_policy(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    song: &Song,
+) -> Result<(), EnqueueError> {
+    match manager.read(guild_id).await {
+        Some(queue) => apply_policy(&queue, song).map(|_| ()),
+        None => EnqueuePolicy::default().check(song),
+    }
+}
+
+/// 서버 정책을 검사한 뒤 대기열에 곡을 추가합니다
+pub async fn add_song(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    song: Song,
+) -> Result<Added, EnqueueError> {
+    let mut guard = manager.write(guild_id).await;
+    let queue = &mut *guard;
+    let duplicate_of = apply_policy(queue, &song)?;
+
+    queue.version += 1;
+    let position = match queue.shuffle.as_mut() {
+        // 셔플 중에는 이번 순환의 끝에 넣어 반복 전에 재생되도록 함
+        Some(state) => {
+            state.original_order.push(song.clone());
+            let index = state.cycle_left.min(queue.songs.len());
+            queue.songs.insert(index, song);
+            state.cycle_left += 1;
+            index + 1
+        }
+        None => {
+            queue.songs.push_back(song);
+            queue.songs.len()
+        }
+    };
+    Ok(Added {
+        position,
+        duplicate_of,
+    })
+}
+
+pub async fn get_policy(manager: &QueueManager, guild_id: GuildId) -> EnqueuePolicy {
+    match manager.read(guild_id).await {
+        Some(queue) => queue.policy.clone(),
+        None => EnqueuePolicy::default(),
+    }
+}
 
-pub async fn add_song(manager: &QueueManager, guild_id: GuildId, song: Song) -> usize {
-    let mut queues = manager.write().await;
-    let queue = queues.entry(guild_id).or_default();
-    queue.songs.push_back(song);
-    queue.songs.len()
+pub async fn set_policy(manager: &QueueManager, guild_id: GuildId, policy: EnqueuePolicy) {
+    manager.write(guild_id).await.policy = policy;
 }
 
 pub async fn get_next_song(
@@ -14,39 +173,172 @@ pub async fn get_next_song(
     guild_id: GuildId,
     was_skipped: bool,
 ) -> Option<Song> {
-    let mut queues = manager.write().await;
-    let queue = queues.entry(guild_id).or_default();
-
-    if !was_skipped && queue.loop_mode == LoopMode::Song {
+    let mut guard = manager.write(guild_id).await;
+    let queue = &mut *guard;
+
+    // 라디오는 끝나지 않는 항목이므로 건너뛰기 전까지 다시 연결
+    let repeat = queue.loop_mode == LoopMode::Song
+        || queue
+            .current_song
+            .as_ref()
+            .is_some_and(|song| song.is_radio);
+    if !was_skipped && repeat && queue.current_song.is_some() {
         return queue.current_song.clone();
     }
 
+    queue.version += 1;
+    let finished = queue.current_song.take();
+    if let Some(ref song) = finished {
+        push_history(&mut queue.history, song.clone());
+    }
+
     if queue.loop_mode == LoopMode::Queue {
-        if let Some(current) = queue.current_song.take() {
+        let last_url = finished.as_ref().map(|s| s.url.clone());
+        if let Some(current) = finished {
             queue.songs.push_back(current);
         }
+
+        // 한 바퀴를 다 돌면 다시 셔플
+        if let Some(state) = queue.shuffle.as_mut() {
+            if state.cycle_left == 0 {
+                shuffle_avoiding(&mut queue.songs, last_url.as_deref());
+                state.cycle_left = queue.songs.len();
+            }
+        }
     }
 
     let next = queue.songs.pop_front();
+
+    if let (Some(state), Some(song)) = (queue.shuffle.as_mut(), next.as_ref()) {
+        state.cycle_left = state.cycle_left.saturating_sub(1);
+        if queue.loop_mode != LoopMode::Queue {
+            remove_first_by_url(&mut state.original_order, &song.url);
+        }
+    }
+
     queue.current_song = next.clone();
     next
 }
 
+/// 마지막으로 재생한 곡을 현재 곡으로 되돌리고, 현재 곡은 대기열 맨 앞에 넣습니다
+pub async fn previous(manager: &QueueManager, guild_id: GuildId) -> Option<Song> {
+    let mut guard = manager.write(guild_id).await;
+    let queue = &mut *guard;
+    let prev = queue.history.pop()?;
+
+    // 전체 반복 중에는 끝난 곡이 대기열 뒤에도 들어가 있으므로 중복 제거
+    if queue.loop_mode == LoopMode::Queue && queue.songs.back().is_some_and(|s| s.url == prev.url) {
+        queue.songs.pop_back();
+    }
+
+    if let Some(current) = queue.current_song.take() {
+        queue.songs.push_front(current);
+        if let Some(state) = queue.shuffle.as_mut() {
+            state.cycle_left += 1;
+        }
+    }
+
+    queue.current_song = Some(prev.clone());
+    queue.version += 1;
+    Some(prev)
+}
+
+pub async fn has_previous(manager: &QueueManager, guild_id: GuildId) -> bool {
+    manager
+        .read(guild_id)
+        .await
+        .is_some_and(|q| !q.history.is_empty())
+}
+
 pub async fn clear(manager: &QueueManager, guild_id: GuildId) {
-    let mut queues = manager.write().await;
-    if let Some(queue) = queues.get_mut(&guild_id) {
+    if let Some(guild) = manager.get(guild_id) {
+        let mut queue = guild.write().await;
+        if queue.current_song.is_some() || !queue.songs.is_empty() {
+            push_undo(&mut queue, QueueEdit::Stop);
+        }
         queue.songs.clear();
         queue.current_song = None;
+        queue.history.clear();
+        queue.shuffle = None;
+        queue.version += 1;
         queue.track_handle = None;
+        queue.voice_channel = None;
+        queue.stage_topic = None;
+        queue.auto_paused = false;
+        queue.idle_timer = None;
+        manager.feed().publish(guild_id, None);
+    }
+}
+
+/// 음성 채널에서 나갈 때 호출합니다. 대기열은 유지하고, 재생 중이던 곡은
+/// 다음에 다시 재생되도록 대기열 맨 앞으로 돌려놓습니다.
+pub async fn detach(manager: &QueueManager, guild_id: GuildId) -> Option<Song> {
+    let guild = manager.get(guild_id)?;
+    let mut guard = guild.write().await;
+    let queue = &mut *guard;
+
+    queue.track_handle = None;
+    queue.voice_channel = None;
+    queue.stage_topic = None;
+    queue.auto_paused = false;
+    queue.idle_timer = None;
+
+    let current = queue.current_song.take()?;
+    queue.songs.push_front(current.clone());
+    if let Some(state) = queue.shuffle.as_mut() {
+        state.cycle_left += 1;
+    }
+    queue.version += 1;
+    manager.feed().publish(guild_id, None);
+    Some(current)
+}
+
+/// 듣는 사람이 없어 자동으로 일시정지했음을 기록합니다
+pub async fn set_auto_paused(manager: &QueueManager, guild_id: GuildId) {
+    manager.write(guild_id).await.auto_paused = true;
+}
+
+/// 자동 일시정지 기록을 지우고, 자동으로 멈춘 곡이었는지 반환합니다
+pub async fn take_auto_paused(manager: &QueueManager, guild_id: GuildId) -> bool {
+    match manager.get(guild_id) {
+        Some(guild) => std::mem::take(&mut guild.write().await.auto_paused),
+        None => false,
     }
 }
 
+/// 퇴장 타이머를 시작하고 번호를 반환합니다. 이미 기다리는 중이면 `None`.
+pub async fn start_idle_timer(manager: &QueueManager, guild_id: GuildId) -> Option<u64> {
+    static NEXT_TIMER: AtomicU64 = AtomicU64::new(1);
+
+    let mut queue = manager.write(guild_id).await;
+    if queue.idle_timer.is_some() {
+        return None;
+    }
+    let timer = NEXT_TIMER.fetch_add(1, Ordering::Relaxed);
+    queue.idle_timer = Some(timer);
+    Some(timer)
+}
+
+/// 누군가 돌아오면 퇴장 타이머를 취소합니다
+pub async fn cancel_idle_timer(manager: &QueueManager, guild_id: GuildId) {
+    if let Some(guild) = manager.get(guild_id) {
+        guild.write().await.idle_timer = None;
+    }
+}
+
+/// `timer`가 취소되지 않고 남아 있는지
+pub async fn is_idle_timer(manager: &QueueManager, guild_id: GuildId, timer: u64) -> bool {
+    manager
+        .read(guild_id)
+        .await
+        .is_some_and(|q| q.idle_timer == Some(timer))
+}
+
 pub async fn get_queue_list(
     manager: &QueueManager,
     guild_id: GuildId,
 ) -> (Option<Song>, Vec<Song>) {
-    let queues = manager.read().await;
-    match queues.get(&guild_id) {
+    match manager.read(guild_id).await {
         Some(queue) => (
             queue.current_song.clone(),
             queue.songs.iter().cloned().collect(),
@@ -55,65 +347,413 @@ pub async fn get_queue_list(
     }
 }
 
+/// `user_id`가 신청해 대기 중인 곡과 위치 (1부터)
+pub async fn songs_requested_by(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Vec<(usize, Song)> {
+    match manager.read(guild_id).await {
+        Some(queue) => queue
+            .songs
+            .iter()
+            .enumerate()
+            .filter(|(_, song)| song.is_requested_by(user_id))
+            .map(|(i, song)| (i + 1, song.clone()))
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// 대기열과 현재 버전을 함께 반환합니다 (선택 메뉴 구성용)
+pub async fn get_upcoming(manager: &QueueManager, guild_id: GuildId) -> (Vec<Song>, u64) {
+    match manager.read(guild_id).await {
+        Some(queue) => (queue.songs.iter().cloned().collect(), queue.version),
+        None => (vec![], 0),
+    }
+}
+
+pub async fn get_version(manager: &QueueManager, guild_id: GuildId) -> u64 {
+    manager.read(guild_id).await.map_or(0, |q| q.version)
+}
+
+/// 셔플 모드를 켜고 큐를 섞습니다. 이미 켜져 있으면 원래 순서는 유지한 채 다시 섞습니다.
 pub async fn shuffle(manager: &QueueManager, guild_id: GuildId) -> usize {
-    use rand::seq::SliceRandom;
-
-    let mut queues = manager.write().await;
-    let queue = queues.entry(guild_id).or_default();
-    let mut songs: Vec<Song> = queue.songs.drain(..).collect();
-    songs.shuffle(&mut rand::thread_rng());
-    let len = songs.len();
-    queue.songs = songs.into();
+    let mut guard = manager.write(guild_id).await;
+    let queue = &mut *guard;
+    if !queue.songs.is_empty() {
+        push_undo(queue, QueueEdit::Shuffle);
+    }
+    queue.version += 1;
+
+    let original_order = match queue.shuffle.take() {
+        Some(state) => state.original_order,
+        None => queue.songs.iter().cloned().collect(),
+    };
+
+    shuffle_avoiding(&mut queue.songs, None);
+    let len = queue.songs.len();
+    queue.shuffle = Some(ShuffleState {
+        original_order,
+        cycle_left: len,
+    });
     len
 }
 
+/// 셔플 모드를 끄고 원래 순서를 복원합니다. 셔플 중이 아니었으면 `false`.
+pub async fn unshuffle(manager: &QueueManager, guild_id: GuildId) -> bool {
+    let mut guard = manager.write(guild_id).await;
+    let queue = &mut *guard;
+
+    if queue.shuffle.is_some() {
+        push_undo(queue, QueueEdit::Unshuffle);
+    }
+    match queue.shuffle.take() {
+        Some(state) => {
+            let songs = std::mem::take(&mut queue.songs);
+            queue.songs = restore_order(songs, &state.original_order);
+            queue.version += 1;
+            true
+        }
+        None => false,
+    }
+}
+
+pub async fn is_shuffled(manager: &QueueManager, guild_id: GuildId) -> bool {
+    manager
+        .read(guild_id)
+        .await
+        .is_some_and(|q| q.shuffle.is_some())
+}
+
 pub async fn remove_at(manager: &QueueManager, guild_id: GuildId, position: usize) -> Option<Song> {
-    let mut queues = manager.write().await;
-    let queue = queues.entry(guild_id).or_default();
-    if position > 0 && position <= queue.songs.len() {
-        queue.songs.remove(position - 1)
-    } else {
-        None
+    let mut guard = manager.write(guild_id).await;
+    remove_from(&mut guard, position)
+}
+
+fn remove_from(queue: &mut GuildQueue, position: usize) -> Option<Song> {
+    if position == 0 || position > queue.songs.len() {
+        return None;
+    }
+
+    push_undo(queue, QueueEdit::Remove);
+    let removed = queue.songs.remove(position - 1)?;
+    queue.version += 1;
+    if let Some(state) = queue.shuffle.as_mut() {
+        remove_first_by_url(&mut state.original_order, &removed.url);
+        if position <= state.cycle_left {
+            state.cycle_left -= 1;
+        }
+    }
+    Some(removed)
+}
+
+/// `from` 위치의 곡을 `to` 위치로 옮깁니다 (둘 다 1부터 시작)
+pub async fn move_to(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    from: usize,
+    to: usize,
+) -> Option<Song> {
+    let mut guard = manager.write(guild_id).await;
+    move_within(&mut guard, from, to)
+}
+
+/// 대기열 버전이 `version`일 때만 곡을 제거합니다 (선택 메뉴처럼 예전 화면을 보고 고른 경우)
+pub async fn remove_at_version(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    version: u64,
+    position: usize,
+) -> Option<Song> {
+    let mut queue = manager.write(guild_id).await;
+    if queue.version != version {
+        return None;
+    }
+    remove_from(&mut queue, position)
+}
+
+/// 대기열 버전이 `version`일 때만 곡을 옮깁니다
+pub async fn move_to_version(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    version: u64,
+    from: usize,
+    to: usize,
+) -> Option<Song> {
+    let mut queue = manager.write(guild_id).await;
+    if queue.version != version {
+        return None;
+    }
+    move_within(&mut queue, from, to)
+}
+
+fn move_within(queue: &mut GuildQueue, from: usize, to: usize) -> Option<Song> {
+    let len = queue.songs.len();
+    if from == 0 || from > len || to == 0 || to > len {
+        return None;
+    }
+    if from != to {
+        push_undo(queue, QueueEdit::Move);
+    }
+
+    let song = queue.songs.remove(from - 1)?;
+    queue.songs.insert(to - 1, song.clone());
+    queue.version += 1;
+    // 순환 경계 앞으로 옮긴 곡은 이번 순환에 넣고, 뒤로 옮긴 곡은 뺌
+    // (remove_at과 add_song처럼 처리해 경계 근처의 다른 곡이 밀려나지 않게 함)
+    if let Some(state) = queue.shuffle.as_mut() {
+        if from <= state.cycle_left {
+            state.cycle_left -= 1;
+        }
+        if to - 1 <= state.cycle_left {
+            state.cycle_left += 1;
+        }
+    }
+    Some(song)
+}
+
+/// 되돌릴 수 있는 마지막 변경
+pub async fn last_edit(manager: &QueueManager, guild_id: GuildId) -> Option<QueueEdit> {
+    manager
+        .read(guild_id)
+        .await
+        .and_then(|q| q.undo_stack.last().map(|s| s.edit))
+}
+
+/// 마지막 대기열 변경을 되돌립니다.
+///
+/// 재생 중인 곡은 건드리지 않습니다. 그 곡이 스냅샷 대기열에 들어 있으면 빼고, 스냅샷 이후에
+/// 추가된 곡은 뒤에 남깁니다. 정지를 되돌리면 정지 당시 재생 중이던 곡을 대기열 맨 앞에 다시
+/// 넣으므로, 재생은 호출하는 쪽에서 시작해야 합니다.
+pub async fn undo(manager: &QueueManager, guild_id: GuildId) -> Option<QueueEdit> {
+    let guild = manager.get(guild_id)?;
+    let mut guard = guild.write().await;
+    let queue = &mut *guard;
+    let snapshot = queue.undo_stack.pop()?;
+
+    let mut songs = snapshot.songs;
+    let mut shuffle = snapshot.shuffle;
+
+    let mut known: Vec<&str> = songs.iter().map(|s| s.url.as_str()).collect();
+    let mut added = Vec::new();
+    for song in &queue.songs {
+        match known.iter().position(|url| *url == song.url) {
+            Some(i) => {
+                known.remove(i);
+            }
+            None => added.push(song.clone()),
+        }
+    }
+
+    if snapshot.edit == QueueEdit::Stop {
+        if let Some(song) = snapshot.current_song {
+            songs.push_front(song);
+            if let Some(state) = shuffle.as_mut() {
+                state.cycle_left += 1;
+            }
+        }
+        let played_since = std::mem::replace(&mut queue.history, snapshot.history);
+        for song in played_since {
+            push_history(&mut queue.history, song);
+        }
+    } else if queue.loop_mode != LoopMode::Queue {
+        // 스냅샷 이후 재생했거나 재생 중인 곡은 다시 넣지 않음 (전체 반복이면 어차피 돌아옴)
+        let played = match snapshot.history.last() {
+            Some(last) => queue
+                .history
+                .iter()
+                .rposition(|s| s.url == last.url)
+                .map_or(0, |i| i + 1),
+            None => 0,
+        };
+        let still_current = |song: &Song| {
+            snapshot
+                .current_song
+                .as_ref()
+                .is_some_and(|s| s.url == song.url)
+        };
+        let current = queue.current_song.iter().filter(|s| !still_current(s));
+        for song in queue.history[played..].iter().chain(current) {
+            if let Some(i) = songs.iter().position(|s| s.url == song.url) {
+                songs.remove(i);
+            }
+            if let Some(state) = shuffle.as_mut() {
+                remove_first_by_url(&mut state.original_order, &song.url);
+            }
+        }
     }
+
+    if let Some(state) = shuffle.as_mut() {
+        state.original_order.extend(added.iter().cloned());
+        state.cycle_left = (state.cycle_left + added.len()).min(songs.len() + added.len());
+    }
+    songs.extend(added);
+
+    queue.songs = songs;
+    queue.shuffle = shuffle;
+    queue.version += 1;
+    Some(snapshot.edit)
 }
 
 pub async fn set_loop_mode(manager: &QueueManager, guild_id: GuildId, mode: LoopMode) -> LoopMode {
-    let mut queues = manager.write().await;
-    let queue = queues.entry(guild_id).or_default();
+    let mut guard = manager.write(guild_id).await;
+    let queue = &mut *guard;
     queue.loop_mode = mode.clone();
     mode
 }
 
 pub async fn set_volume(manager: &QueueManager, guild_id: GuildId, volume: f32) {
-    let mut queues = manager.write().await;
-    let queue = queues.entry(guild_id).or_default();
+    let mut guard = manager.write(guild_id).await;
+    let queue = &mut *guard;
     queue.volume = volume;
     if let Some(handle) = &queue.track_handle {
         let _ = handle.set_volume(volume);
     }
 }
 
+/// 서버 EQ를 바꿉니다. 재생 중인 곡에도 바로 적용됩니다.
+pub async fn set_equalizer(manager: &QueueManager, guild_id: GuildId, bands: EqBands) {
+    let shared = manager.write(guild_id).await.equalizer.clone();
+    *shared.write().unwrap() = bands;
+}
+
+pub async fn get_equalizer(manager: &QueueManager, guild_id: GuildId) -> EqBands {
+    manager
+        .read(guild_id)
+        .await
+        .map(|q| *q.equalizer.read().unwrap())
+        .unwrap_or_default()
+}
+
+/// 새 트랙의 디코더에 넘길 공유 EQ 핸들
+pub async fn shared_equalizer(manager: &QueueManager, guild_id: GuildId) -> SharedEq {
+    manager.write(guild_id).await.equalizer.clone()
+}
+
+pub async fn set_segment_provider(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    provider: Option<Arc<dyn SegmentProvider>>,
+) {
+    let mut guard = manager.write(guild_id).await;
+    let queue = &mut *guard;
+    queue.segment_provider = provider;
+}
+
+pub async fn get_segment_provider(
+    manager: &QueueManager,
+    guild_id: GuildId,
+) -> Option<Arc<dyn SegmentProvider>> {
+    manager
+        .read(guild_id)
+        .await
+        .and_then(|q| q.segment_provider.clone())
+}
+
+pub async fn set_voice_channel(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    channel_id: Option<ChannelId>,
+) {
+    manager.write(guild_id).await.voice_channel = channel_id;
+}
+
+pub async fn set_stage_topic(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    stage_topic: Option<StageTopic>,
+) {
+    manager.write(guild_id).await.stage_topic = stage_topic;
+}
+
+pub async fn get_stage_topic(manager: &QueueManager, guild_id: GuildId) -> Option<StageTopic> {
+    manager
+        .read(guild_id)
+        .await
+        .and_then(|q| q.stage_topic.clone())
+}
+
+pub async fn get_voice_channel(manager: &QueueManager, guild_id: GuildId) -> Option<ChannelId> {
+    manager.read(guild_id).await.and_then(|q| q.voice_channel)
+}
+
+pub async fn set_idle_fallback(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    fallback: Option<IdleFallback>,
+) {
+    manager.write(guild_id).await.idle_fallback = fallback;
+}
+
+pub async fn get_idle_fallback(manager: &QueueManager, guild_id: GuildId) -> Option<IdleFallback> {
+    manager
+        .read(guild_id)
+        .await
+        .and_then(|q| q.idle_fallback.clone())
+}
+
+/// 재생이 끝난 곡들 (오래된 곡부터)
+pub async fn get_history(manager: &QueueManager, guild_id: GuildId) -> Vec<Song> {
+    manager
+        .read(guild_id)
+        .await
+        .map(|q| q.history.clone())
+        .unwrap_or_default()
+}
+
 pub async fn get_current(manager: &QueueManager, guild_id: GuildId) -> Option<Song> {
-    let queues = manager.read().await;
-    queues.get(&guild_id).and_then(|q| q.current_song.clone())
+    manager
+        .read(guild_id)
+        .await
+        .and_then(|q| q.current_song.clone())
+}
+
+/// 재생 중인 라디오(`url`)의 방송 중인 곡을 바꿉니다. 바뀌었으면 `true`.
+pub async fn set_stream_title(
+    manager: &QueueManager,
+    guild_id: GuildId,
+    url: &str,
+    title: &str,
+) -> bool {
+    let Some(guild) = manager.get(guild_id) else {
+        return false;
+    };
+    let mut queue = guild.write().await;
+    match queue.current_song.as_mut() {
+        Some(song) if song.is_radio && song.url == url => {
+            if song.stream_title.as_deref() == Some(title) {
+                return false;
+            }
+            song.stream_title = Some(title.to_string());
+            true
+        }
+        _ => false,
+    }
+}
+
+pub async fn get_track_handle(manager: &QueueManager, guild_id: GuildId) -> Option<TrackHandle> {
+    manager
+        .read(guild_id)
+        .await
+        .and_then(|q| q.track_handle.clone())
 }
 
 pub async fn get_volume(manager: &QueueManager, guild_id: GuildId) -> f32 {
-    let queues = manager.read().await;
-    queues.get(&guild_id).map_or(0.5, |q| q.volume)
+    manager.read(guild_id).await.map_or(0.5, |q| q.volume)
 }
 
 pub async fn get_loop_mode(manager: &QueueManager, guild_id: GuildId) -> LoopMode {
-    let queues = manager.read().await;
-    queues
-        .get(&guild_id)
+    manager
+        .read(guild_id)
+        .await
         .map_or(LoopMode::Off, |q| q.loop_mode.clone())
 }
 
 pub async fn is_empty(manager: &QueueManager, guild_id: GuildId) -> bool {
-    let queues = manager.read().await;
-    queues
-        .get(&guild_id)
+    manager
+        .read(guild_id)
+        .await
         .is_none_or(|q| q.current_song.is_none() && q.songs.is_empty())
 }
 
@@ -129,6 +769,7 @@ mod tests {
             url: format!("https://example.com/{title}"),
             duration: Some("3:00".to_string()),
             requester: "tester".to_string(),
+            ..Default::default()
         }
     }
 
@@ -139,13 +780,22 @@ mod tests {
     async fn test_add_song_returns_position() {
         let manager = new_queue_manager();
 
-        let pos1 = add_song(&manager, GUILD, test_song("Song A")).await;
+        let pos1 = add_song(&manager, GUILD, test_song("Song A"))
+            .await
+            .unwrap()
+            .position;
         assert_eq!(pos1, 1);
 
-        let pos2 = add_song(&manager, GUILD, test_song("Song B")).await;
+        let pos2 = add_song(&manager, GUILD, test_song("Song B"))
+            .await
+            .unwrap()
+            .position;
         assert_eq!(pos2, 2);
 
-        let pos3 = add_song(&manager, GUILD, test_song("Song C")).await;
+        let pos3 = add_song(&manager, GUILD, test_song("Song C"))
+            .await
+            .unwrap()
+            .position;
         assert_eq!(pos3, 3);
     }
 
@@ -153,8 +803,10 @@ mod tests {
     #[tokio::test]
     async fn test_get_next_song_normal_mode() {
         let manager = new_queue_manager();
-        add_song(&manager, GUILD, test_song("First")).await;
-        add_song(&manager, GUILD, test_song("Second")).await;
+        add_song(&manager, GUILD, test_song("First")).await.unwrap();
+        add_song(&manager, GUILD, test_song("Second"))
+            .await
+            .unwrap();
 
         let next = get_next_song(&manager, GUILD, false).await;
         assert!(next.is_some());
@@ -175,8 +827,10 @@ mod tests {
     #[tokio::test]
     async fn test_get_next_song_loop_song_repeats_current() {
         let manager = new_queue_manager();
-        add_song(&manager, GUILD, test_song("Looping")).await;
-        add_song(&manager, GUILD, test_song("Next")).await;
+        add_song(&manager, GUILD, test_song("Looping"))
+            .await
+            .unwrap();
+        add_song(&manager, GUILD, test_song("Next")).await.unwrap();
 
         // Advance once to set current_song
         get_next_song(&manager, GUILD, false).await;
@@ -198,8 +852,10 @@ mod tests {
     #[tokio::test]
     async fn test_get_next_song_loop_song_skipped_advances() {
         let manager = new_queue_manager();
-        add_song(&manager, GUILD, test_song("First")).await;
-        add_song(&manager, GUILD, test_song("Second")).await;
+        add_song(&manager, GUILD, test_song("First")).await.unwrap();
+        add_song(&manager, GUILD, test_song("Second"))
+            .await
+            .unwrap();
 
         // Advance once to set current_song to "First"
         get_next_song(&manager, GUILD, false).await;
@@ -219,9 +875,9 @@ mod tests {
     #[tokio::test]
     async fn test_get_next_song_loop_queue_cycles() {
         let manager = new_queue_manager();
-        add_song(&manager, GUILD, test_song("A")).await;
-        add_song(&manager, GUILD, test_song("B")).await;
-        add_song(&manager, GUILD, test_song("C")).await;
+        add_song(&manager, GUILD, test_song("A")).await.unwrap();
+        add_song(&manager, GUILD, test_song("B")).await.unwrap();
+        add_song(&manager, GUILD, test_song("C")).await.unwrap();
 
         // Advance once: current = "A", queue = [B, C]
         get_next_song(&manager, GUILD, false).await;
@@ -256,8 +912,12 @@ mod tests {
     #[tokio::test]
     async fn test_clear_empties_queue_and_current() {
         let manager = new_queue_manager();
-        add_song(&manager, GUILD, test_song("Song 1")).await;
-        add_song(&manager, GUILD, test_song("Song 2")).await;
+        add_song(&manager, GUILD, test_song("Song 1"))
+            .await
+            .unwrap();
+        add_song(&manager, GUILD, test_song("Song 2"))
+            .await
+            .unwrap();
 
         // Advance to populate current_song
         get_next_song(&manager, GUILD, false).await;
@@ -280,7 +940,7 @@ mod tests {
         let manager = new_queue_manager();
         let titles = ["Alpha", "Beta", "Gamma", "Delta", "Epsilon"];
         for title in &titles {
-            add_song(&manager, GUILD, test_song(title)).await;
+            add_song(&manager, GUILD, test_song(title)).await.unwrap();
         }
 
         let count = shuffle(&manager, GUILD).await;
@@ -301,9 +961,11 @@ mod tests {
     #[tokio::test]
     async fn test_remove_at_removes_correct_song() {
         let manager = new_queue_manager();
-        add_song(&manager, GUILD, test_song("First")).await;
-        add_song(&manager, GUILD, test_song("Second")).await;
-        add_song(&manager, GUILD, test_song("Third")).await;
+        add_song(&manager, GUILD, test_song("First")).await.unwrap();
+        add_song(&manager, GUILD, test_song("Second"))
+            .await
+            .unwrap();
+        add_song(&manager, GUILD, test_song("Third")).await.unwrap();
 
         // Remove position 2 (1-indexed), which is "Second"
         let removed = remove_at(&manager, GUILD, 2).await;
@@ -320,7 +982,7 @@ mod tests {
     #[tokio::test]
     async fn test_remove_at_invalid_position_returns_none() {
         let manager = new_queue_manager();
-        add_song(&manager, GUILD, test_song("Only")).await;
+        add_song(&manager, GUILD, test_song("Only")).await.unwrap();
 
         // Position 0 is invalid
         let result = remove_at(&manager, GUILD, 0).await;
@@ -349,6 +1011,18 @@ mod tests {
         assert!((vol - 0.25).abs() < f32::EPSILON);
     }
 
+    #[tokio::test]
+    async fn test_set_equalizer_updates_shared_handle() {
+        let manager = new_queue_manager();
+        let shared = shared_equalizer(&manager, GUILD).await;
+
+        let rock = EqBands::preset("rock").unwrap();
+        set_equalizer(&manager, GUILD, rock).await;
+        assert_eq!(get_equalizer(&manager, GUILD).await, rock);
+        // 이미 재생 중인 트랙이 가진 핸들에도 반영
+        assert_eq!(*shared.read().unwrap(), rock);
+    }
+
     // 12. get_volume - returns default 0.5 when no guild queue
     #[tokio::test]
     async fn test_get_volume_default_when_no_guild() {
@@ -365,7 +1039,7 @@ mod tests {
         // No guild entry at all -> empty
         assert!(is_empty(&manager, GUILD).await);
 
-        add_song(&manager, GUILD, test_song("A")).await;
+        add_song(&manager, GUILD, test_song("A")).await.unwrap();
         assert!(!is_empty(&manager, GUILD).await);
 
         // Pop the song into current_song
@@ -395,4 +1069,540 @@ mod tests {
         set_loop_mode(&manager, GUILD, LoopMode::Off).await;
         assert_eq!(get_loop_mode(&manager, GUILD).await, LoopMode::Off);
     }
+
+    // 15. shuffle + unshuffle restores original order
+    #[tokio::test]
+    async fn test_unshuffle_restores_original_order() {
+        let manager = new_queue_manager();
+        let titles = ["A", "B", "C", "D", "E", "F"];
+        for title in &titles {
+            add_song(&manager, GUILD, test_song(title)).await.unwrap();
+        }
+
+        shuffle(&manager, GUILD).await;
+        assert!(is_shuffled(&manager, GUILD).await);
+
+        // Songs added and removed while shuffled are tracked too
+        add_song(&manager, GUILD, test_song("G")).await.unwrap();
+        let (_, shuffled) = get_queue_list(&manager, GUILD).await;
+        let pos_of_b = shuffled.iter().position(|s| s.title == "B").unwrap() + 1;
+        remove_at(&manager, GUILD, pos_of_b).await;
+
+        assert!(unshuffle(&manager, GUILD).await);
+        assert!(!is_shuffled(&manager, GUILD).await);
+
+        let (_, restored) = get_queue_list(&manager, GUILD).await;
+        let restored: Vec<&str> = restored.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(restored, vec!["A", "C", "D", "E", "F", "G"]);
+
+        // Unshuffle when not shuffled is a no-op
+        assert!(!unshuffle(&manager, GUILD).await);
+    }
+
+    // 16. restore_order keeps unknown songs at the end
+    #[test]
+    fn test_restore_order_keeps_unknown_songs() {
+        let songs: VecDeque<Song> = ["C", "X", "A"].iter().map(|t| test_song(t)).collect();
+        let original: Vec<Song> = ["A", "B", "C"].iter().map(|t| test_song(t)).collect();
+        let restored: Vec<String> = restore_order(songs, &original)
+            .into_iter()
+            .map(|s| s.title)
+            .collect();
+        assert_eq!(restored, vec!["A", "C", "X"]);
+    }
+
+    // 17. shuffle + LoopMode::Queue reshuffles every full cycle
+    #[tokio::test]
+    async fn test_shuffle_reshuffles_on_loop_wrap() {
+        let manager = new_queue_manager();
+        for title in ["A", "B", "C", "D"] {
+            add_song(&manager, GUILD, test_song(title)).await.unwrap();
+        }
+        set_loop_mode(&manager, GUILD, LoopMode::Queue).await;
+        shuffle(&manager, GUILD).await;
+
+        // Each cycle plays every song exactly once, across several wraps
+        for _ in 0..5 {
+            let mut cycle = Vec::new();
+            for _ in 0..4 {
+                cycle.push(get_next_song(&manager, GUILD, false).await.unwrap().title);
+            }
+            cycle.sort();
+            assert_eq!(cycle, vec!["A", "B", "C", "D"]);
+        }
+
+        let (_, upcoming) = get_queue_list(&manager, GUILD).await;
+        assert_eq!(upcoming.len(), 3);
+    }
+
+    // 18. clear resets shuffle mode
+    #[tokio::test]
+    async fn test_clear_resets_shuffle() {
+        let manager = new_queue_manager();
+        add_song(&manager, GUILD, test_song("A")).await.unwrap();
+        shuffle(&manager, GUILD).await;
+        clear(&manager, GUILD).await;
+        assert!(!is_shuffled(&manager, GUILD).await);
+    }
+
+    // 19. move_to - moves a song and rejects invalid positions
+    #[tokio::test]
+    async fn test_move_to() {
+        let manager = new_queue_manager();
+        for title in ["A", "B", "C"] {
+            add_song(&manager, GUILD, test_song(title)).await.unwrap();
+        }
+
+        let moved = move_to(&manager, GUILD, 3, 1).await;
+        assert_eq!(moved.unwrap().title, "C");
+
+        let (_, songs) = get_queue_list(&manager, GUILD).await;
+        let titles: Vec<&str> = songs.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["C", "A", "B"]);
+
+        assert!(move_to(&manager, GUILD, 0, 1).await.is_none());
+        assert!(move_to(&manager, GUILD, 1, 4).await.is_none());
+    }
+
+    // 20. version changes on every order-changing operation
+    #[tokio::test]
+    async fn test_version_bumps_on_mutation() {
+        let manager = new_queue_manager();
+        assert_eq!(get_version(&manager, GUILD).await, 0);
+
+        add_song(&manager, GUILD, test_song("A")).await.unwrap();
+        add_song(&manager, GUILD, test_song("B")).await.unwrap();
+        let v1 = get_version(&manager, GUILD).await;
+
+        // Non-order changes keep the version
+        set_volume(&manager, GUILD, 0.3).await;
+        assert_eq!(get_version(&manager, GUILD).await, v1);
+
+        move_to(&manager, GUILD, 2, 1).await;
+        let v2 = get_version(&manager, GUILD).await;
+        assert!(v2 > v1);
+
+        remove_at(&manager, GUILD, 1).await;
+        let (upcoming, v3) = get_upcoming(&manager, GUILD).await;
+        assert!(v3 > v2);
+        assert_eq!(upcoming.len(), 1);
+    }
+
+    // 21. previous - restores the last played song and requeues the current one
+    #[tokio::test]
+    async fn test_previous_restores_last_song() {
+        let manager = new_queue_manager();
+        for title in ["A", "B", "C"] {
+            add_song(&manager, GUILD, test_song(title)).await.unwrap();
+        }
+        assert!(previous(&manager, GUILD).await.is_none());
+
+        get_next_song(&manager, GUILD, false).await; // A
+        get_next_song(&manager, GUILD, true).await; // B
+        assert!(has_previous(&manager, GUILD).await);
+
+        let prev = previous(&manager, GUILD).await;
+        assert_eq!(prev.unwrap().title, "A");
+        assert_eq!(get_current(&manager, GUILD).await.unwrap().title, "A");
+
+        let (_, songs) = get_queue_list(&manager, GUILD).await;
+        let titles: Vec<&str> = songs.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["B", "C"]);
+        assert!(!has_previous(&manager, GUILD).await);
+    }
+
+    // 22. previous in LoopMode::Queue doesn't duplicate the looped song
+    #[tokio::test]
+    async fn test_previous_in_loop_queue_no_duplicate() {
+        let manager = new_queue_manager();
+        for title in ["A", "B"] {
+            add_song(&manager, GUILD, test_song(title)).await.unwrap();
+        }
+        set_loop_mode(&manager, GUILD, LoopMode::Queue).await;
+
+        get_next_song(&manager, GUILD, false).await; // A, queue [B]
+        get_next_song(&manager, GUILD, false).await; // B, queue [A]
+
+        previous(&manager, GUILD).await; // A, queue [B]
+        let (current, songs) = get_queue_list(&manager, GUILD).await;
+        assert_eq!(current.unwrap().title, "A");
+        let titles: Vec<&str> = songs.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["B"]);
+    }
+
+    // 23. set_segment_provider / get_segment_provider
+    #[tokio::test]
+    async fn test_set_and_get_segment_provider() {
+        use crate::music::segments::StaticSegmentProvider;
+
+        let manager = new_queue_manager();
+        assert!(get_segment_provider(&manager, GUILD).await.is_none());
+
+        set_segment_provider(
+            &manager,
+            GUILD,
+            Some(Arc::new(StaticSegmentProvider::new())),
+        )
+        .await;
+        assert!(get_segment_provider(&manager, GUILD).await.is_some());
+
+        set_segment_provider(&manager, GUILD, None).await;
+        assert!(get_segment_provider(&manager, GUILD).await.is_none());
+    }
+
+    // 24. detach - keeps the queue and puts the current song back in front
+    #[tokio::test]
+    async fn test_detach_keeps_queue() {
+        let manager = new_queue_manager();
+        add_song(&manager, GUILD, test_song("A")).await.unwrap();
+        add_song(&manager, GUILD, test_song("B")).await.unwrap();
+        get_next_song(&manager, GUILD, false).await;
+        set_voice_channel(&manager, GUILD, Some(ChannelId::new(10))).await;
+
+        let parked = detach(&manager, GUILD).await;
+        assert_eq!(parked.unwrap().title, "A");
+        assert!(get_current(&manager, GUILD).await.is_none());
+        assert!(get_voice_channel(&manager, GUILD).await.is_none());
+
+        let (_, songs) = get_queue_list(&manager, GUILD).await;
+        let titles: Vec<&str> = songs.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["A", "B"]);
+    }
+
+    // 25. set_voice_channel / clear resets the binding
+    #[tokio::test]
+    async fn test_voice_channel_binding() {
+        let manager = new_queue_manager();
+        assert!(get_voice_channel(&manager, GUILD).await.is_none());
+
+        set_voice_channel(&manager, GUILD, Some(ChannelId::new(10))).await;
+        assert_eq!(
+            get_voice_channel(&manager, GUILD).await,
+            Some(ChannelId::new(10))
+        );
+
+        clear(&manager, GUILD).await;
+        assert!(get_voice_channel(&manager, GUILD).await.is_none());
+    }
+
+    // 26. get_next_song in LoopMode::Song starts the queue when nothing is playing
+    #[tokio::test]
+    async fn test_loop_song_starts_when_idle() {
+        let manager = new_queue_manager();
+        set_loop_mode(&manager, GUILD, LoopMode::Song).await;
+        add_song(&manager, GUILD, test_song("A")).await.unwrap();
+
+        let first = get_next_song(&manager, GUILD, false).await;
+        assert_eq!(first.unwrap().title, "A");
+        let again = get_next_song(&manager, GUILD, false).await;
+        assert_eq!(again.unwrap().title, "A");
+    }
+
+    async fn titles(manager: &QueueManager) -> Vec<String> {
+        let (_, songs) = get_queue_list(manager, GUILD).await;
+        songs.into_iter().map(|s| s.title).collect()
+    }
+
+    // 27. undo - restores order after shuffle without touching the current song
+    #[tokio::test]
+    async fn test_undo_shuffle_keeps_current() {
+        let manager = new_queue_manager();
+        for t in ["A", "B", "C", "D", "E"] {
+            add_song(&manager, GUILD, test_song(t)).await.unwrap();
+        }
+        get_next_song(&manager, GUILD, false).await;
+
+        shuffle(&manager, GUILD).await;
+        assert_eq!(last_edit(&manager, GUILD).await, Some(QueueEdit::Shuffle));
+        assert_eq!(undo(&manager, GUILD).await, Some(QueueEdit::Shuffle));
+
+        assert_eq!(titles(&manager).await, ["B", "C", "D", "E"]);
+        assert!(!is_shuffled(&manager, GUILD).await);
+        assert_eq!(get_current(&manager, GUILD).await.unwrap().title, "A");
+        assert_eq!(undo(&manager, GUILD).await, None);
+    }
+
+    // 28. undo - restores a removed song but not ones played or added since
+    #[tokio::test]
+    async fn test_undo_remove_skips_played_and_keeps_added() {
+        let manager = new_queue_manager();
+        for t in ["A", "B", "C", "D"] {
+            add_song(&manager, GUILD, test_song(t)).await.unwrap();
+        }
+        get_next_song(&manager, GUILD, false).await;
+        remove_at(&manager, GUILD, 3).await; // D
+
+        get_next_song(&manager, GUILD, false).await; // B 재생
+        add_song(&manager, GUILD, test_song("E")).await.unwrap();
+
+        assert_eq!(undo(&manager, GUILD).await, Some(QueueEdit::Remove));
+        assert_eq!(titles(&manager).await, ["C", "D", "E"]);
+        assert_eq!(get_current(&manager, GUILD).await.unwrap().title, "B");
+    }
+
+    // 29. undo - a stop puts the stopped song back in front with its history
+    #[tokio::test]
+    async fn test_undo_stop_restores_current_and_history() {
+        let manager = new_queue_manager();
+        for t in ["A", "B", "C"] {
+            add_song(&manager, GUILD, test_song(t)).await.unwrap();
+        }
+        get_next_song(&manager, GUILD, false).await;
+        get_next_song(&manager, GUILD, false).await;
+
+        clear(&manager, GUILD).await;
+        assert!(is_empty(&manager, GUILD).await);
+
+        assert_eq!(undo(&manager, GUILD).await, Some(QueueEdit::Stop));
+        assert_eq!(titles(&manager).await, ["B", "C"]);
+        assert!(get_current(&manager, GUILD).await.is_none());
+        assert!(has_previous(&manager, GUILD).await);
+    }
+
+    // 30. undo stack is bounded and only records real changes
+    #[tokio::test]
+    async fn test_undo_stack_bounded() {
+        let manager = new_queue_manager();
+        shuffle(&manager, GUILD).await;
+        clear(&manager, GUILD).await;
+        assert!(remove_at(&manager, GUILD, 1).await.is_none());
+        assert_eq!(last_edit(&manager, GUILD).await, None);
+
+        add_song(&manager, GUILD, test_song("A")).await.unwrap();
+        add_song(&manager, GUILD, test_song("B")).await.unwrap();
+        for _ in 0..UNDO_LIMIT + 5 {
+            move_to(&manager, GUILD, 1, 2).await;
+        }
+        let mut undone = 0;
+        while undo(&manager, GUILD).await.is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, UNDO_LIMIT);
+    }
+
+    // 31. idle timer - only one timer at a time, cancelled when someone returns
+    #[tokio::test]
+    async fn test_idle_timer_and_auto_pause() {
+        let manager = new_queue_manager();
+        let started = start_idle_timer(&manager, GUILD).await.unwrap();
+        assert!(start_idle_timer(&manager, GUILD).await.is_none());
+        assert!(is_idle_timer(&manager, GUILD, started).await);
+
+        cancel_idle_timer(&manager, GUILD).await;
+        assert!(!is_idle_timer(&manager, GUILD, started).await);
+        let restarted = start_idle_timer(&manager, GUILD).await.unwrap();
+        assert_ne!(restarted, started);
+        assert!(!is_idle_timer(&manager, GUILD, started).await);
+
+        assert!(!take_auto_paused(&manager, GUILD).await);
+        set_auto_paused(&manager, GUILD).await;
+        assert!(take_auto_paused(&manager, GUILD).await);
+        assert!(!take_auto_paused(&manager, GUILD).await);
+
+        // Leaving the channel forgets both
+        set_auto_paused(&manager, GUILD).await;
+        clear(&manager, GUILD).await;
+        assert!(!take_auto_paused(&manager, GUILD).await);
+        assert!(!is_idle_timer(&manager, GUILD, restarted).await);
+    }
+
+    // 32. songs_requested_by - only the user's pending songs, with queue positions
+    #[tokio::test]
+    async fn test_songs_requested_by() {
+        let manager = new_queue_manager();
+        let alice = UserId::new(1);
+        for (t, owner) in [
+            ("A", alice),
+            ("B", UserId::new(2)),
+            ("C", alice),
+            ("D", alice),
+        ] {
+            let mut song = test_song(t);
+            song.requester_id = Some(owner);
+            add_song(&manager, GUILD, song).await.unwrap();
+        }
+        get_next_song(&manager, GUILD, false).await;
+
+        let mine: Vec<_> = songs_requested_by(&manager, GUILD, alice)
+            .await
+            .into_iter()
+            .map(|(pos, song)| (pos, song.title))
+            .collect();
+        assert_eq!(mine, [(2, "C".to_string()), (3, "D".to_string())]);
+        assert!(songs_requested_by(&manager, GUILD, UserId::new(3))
+            .await
+            .is_empty());
+    }
+
+    // 33. add_song policy - duplicates warn or reject, length/live/blocklist reject
+    #[tokio::test]
+    async fn test_add_song_policy() {
+        let manager = new_queue_manager();
+        add_song(&manager, GUILD, test_song("A")).await.unwrap();
+        add_song(&manager, GUILD, test_song("B")).await.unwrap();
+        get_next_song(&manager, GUILD, false).await;
+
+        // Default policy warns but still adds
+        let added = add_song(&manager, GUILD, test_song("B")).await.unwrap();
+        assert_eq!(added.position, 2);
+        assert_eq!(added.duplicate_of, Some(1));
+        let added = add_song(&manager, GUILD, test_song("A")).await.unwrap();
+        assert_eq!(added.duplicate_of, Some(0));
+        assert_eq!(
+            add_song(&manager, GUILD, test_song("C"))
+                .await
+                .unwrap()
+                .duplicate_of,
+            None
+        );
+
+        let mut policy = get_policy(&manager, GUILD).await;
+        policy.duplicates = DuplicateMode::Reject;
+        set_policy(&manager, GUILD, policy.clone()).await;
+        assert_eq!(
+            add_song(&manager, GUILD, test_song("A")).await,
+            Err(EnqueueError::Duplicate { position: 0 })
+        );
+
+        policy.max_duration_secs = Some(120);
+        set_policy(&manager, GUILD, policy).await;
+        let mut short = test_song("D");
+        short.duration = Some("1:30".to_string());
+        assert!(check_policy(&manager, GUILD, &short).await.is_ok());
+        assert_eq!(
+            add_song(&manager, GUILD, test_song("D")).await,
+            Err(EnqueueError::TooLong { limit_secs: 120 })
+        );
+        assert_eq!(add_song(&manager, GUILD, short).await.unwrap().position, 5);
+        assert_eq!(titles(&manager).await, ["B", "B", "A", "C", "D"]);
+    }
+
+    // 34. radio - replays until skipped, stream title only updates the playing station
+    #[tokio::test]
+    async fn test_radio_is_endless_until_skipped() {
+        let manager = new_queue_manager();
+        let mut station = test_song("Station");
+        station.is_radio = true;
+        add_song(&manager, GUILD, station).await.unwrap();
+        add_song(&manager, GUILD, test_song("After")).await.unwrap();
+
+        get_next_song(&manager, GUILD, false).await;
+        for _ in 0..3 {
+            assert_eq!(
+                get_next_song(&manager, GUILD, false).await.unwrap().title,
+                "Station"
+            );
+        }
+
+        let url = "https://example.com/Station";
+        assert!(set_stream_title(&manager, GUILD, url, "Artist - One").await);
+        assert!(!set_stream_title(&manager, GUILD, url, "Artist - One").await);
+        assert!(!set_stream_title(&manager, GUILD, "https://other", "X").await);
+        assert_eq!(
+            get_current(&manager, GUILD)
+                .await
+                .unwrap()
+                .stream_title
+                .as_deref(),
+            Some("Artist - One")
+        );
+
+        assert_eq!(
+            get_next_song(&manager, GUILD, true).await.unwrap().title,
+            "After"
+        );
+        assert!(!set_stream_title(&manager, GUILD, url, "Artist - Two").await);
+    }
+
+    // 35. move_to while shuffled keeps the shuffle cycle boundary in place
+    #[tokio::test]
+    async fn test_move_to_keeps_shuffle_cycle() {
+        let manager = new_queue_manager();
+        for title in ["A", "B", "C", "D"] {
+            add_song(&manager, GUILD, test_song(title)).await.unwrap();
+        }
+        set_loop_mode(&manager, GUILD, LoopMode::Queue).await;
+        shuffle(&manager, GUILD).await;
+        for _ in 0..3 {
+            get_next_song(&manager, GUILD, false).await;
+        }
+        let cycle_left = |manager: &QueueManager| {
+            let manager = manager.clone();
+            async move {
+                manager
+                    .read(GUILD)
+                    .await
+                    .unwrap()
+                    .shuffle
+                    .as_ref()
+                    .unwrap()
+                    .cycle_left
+            }
+        };
+        // One song left in this cycle, then the two already played
+        assert_eq!(cycle_left(&manager).await, 1);
+        let (_, songs) = get_queue_list(&manager, GUILD).await;
+        let unplayed = songs[0].title.clone();
+
+        // An already played song moved to the front joins the cycle without pushing the unplayed one out
+        let moved = move_to(&manager, GUILD, 3, 1).await.unwrap();
+        assert_eq!(cycle_left(&manager).await, 2);
+        let next: Vec<String> = vec![
+            get_next_song(&manager, GUILD, false).await.unwrap().title,
+            get_next_song(&manager, GUILD, false).await.unwrap().title,
+        ];
+        assert_eq!(next, vec![moved.title, unplayed]);
+
+        // Moving an unplayed song past the boundary takes it out of the cycle
+        let mut guard = manager.write(GUILD).await;
+        guard.shuffle.as_mut().unwrap().cycle_left = 2;
+        drop(guard);
+        move_to(&manager, GUILD, 1, 3).await.unwrap();
+        assert_eq!(cycle_left(&manager).await, 1);
+        move_to(&manager, GUILD, 3, 1).await.unwrap();
+        assert_eq!(cycle_left(&manager).await, 2);
+    }
+
+    // 36. remove_at_version / move_to_version only act on the version the user saw
+    #[tokio::test]
+    async fn test_versioned_edits_reject_stale_version() {
+        let manager = new_queue_manager();
+        for title in ["A", "B", "C"] {
+            add_song(&manager, GUILD, test_song(title)).await.unwrap();
+        }
+        let (_, seen) = get_upcoming(&manager, GUILD).await;
+
+        // Someone else removes A after the menu was rendered
+        remove_at(&manager, GUILD, 1).await;
+        assert!(remove_at_version(&manager, GUILD, seen, 2).await.is_none());
+        assert!(move_to_version(&manager, GUILD, seen, 2, 1).await.is_none());
+        let (_, songs) = get_queue_list(&manager, GUILD).await;
+        let titles: Vec<&str> = songs.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["B", "C"]);
+
+        let (_, current) = get_upcoming(&manager, GUILD).await;
+        let moved = move_to_version(&manager, GUILD, current, 2, 1).await;
+        assert_eq!(moved.unwrap().title, "C");
+        let current = get_version(&manager, GUILD).await;
+        let removed = remove_at_version(&manager, GUILD, current, 2).await;
+        assert_eq!(removed.unwrap().title, "B");
+    }
+
+    // 37. clear / detach tell feed subscribers that nothing is playing
+    #[tokio::test]
+    async fn test_clear_and_detach_publish_feed() {
+        let manager = new_queue_manager();
+        let mut rx = manager.feed().subscribe();
+
+        add_song(&manager, GUILD, test_song("A")).await.unwrap();
+        get_next_song(&manager, GUILD, false).await;
+        detach(&manager, GUILD).await;
+        let change = rx.recv().await.unwrap();
+        assert_eq!(change.guild_id, GUILD);
+        assert!(change.song.is_none());
+
+        get_next_song(&manager, GUILD, false).await;
+        clear(&manager, GUILD).await;
+        assert!(rx.recv().await.unwrap().song.is_none());
+    }
 }
@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serenity::model::id::{GuildId, UserId};
+use tokio::sync::Notify;
+
+/// 라운드 수 기본값과 범위
+pub const DEFAULT_ROUNDS: usize = 5;
+pub const MAX_ROUNDS: usize = 20;
+/// 라운드당 제한 시간 기본값과 범위 (초)
+pub const DEFAULT_ROUND_SECS: u64 = 20;
+pub const MIN_ROUND_SECS: u64 = 10;
+pub const MAX_ROUND_SECS: u64 = 60;
+
+/// 퀴즈 중에 대기열 곡을 바꾸거나 다시 틀려고 할 때의 안내
+pub const MUSIC_ON_HOLD: &str =
+    "음악 퀴즈가 진행 중입니다. 퀴즈가 끝나면 대기열을 이어서 재생합니다.";
+
+/// 바로 맞혔을 때와 마지막 순간에 맞혔을 때의 점수
+const MAX_POINTS: u32 = 100;
+const MIN_POINTS: u32 = 20;
+/// 이 이상 비슷하면 정답으로 인정
+const MATCH_THRESHOLD: f64 = 0.75;
+
+/// 제목 비교용 정규화: 괄호 안 부가 정보와 `feat.` 이후를 버리고 글자/숫자만 남김
+pub fn normalize(title: &str) -> String {
+    let lower = title.to_lowercase();
+    let mut cut = lower.as_str();
+    for marker in [" feat.", " feat ", " ft.", " ft "] {
+        if let Some(i) = cut.find(marker) {
+            cut = &cut[..i];
+        }
+    }
+
+    let mut depth = 0usize;
+    cut.chars()
+        .filter(|&c| match c {
+            '(' | '[' | '【' | '「' => {
+                depth += 1;
+                false
+            }
+            ')' | ']' | '】' | '」' => {
+                depth = depth.saturating_sub(1);
+                false
+            }
+            _ => depth == 0 && c.is_alphanumeric(),
+        })
+        .collect()
+}
+
+/// 정답으로 인정할 제목 후보: 전체 제목과 `가수 - 곡` 형식의 곡 부분
+fn answers(title: &str) -> Vec<String> {
+    let mut answers = vec![normalize(title)];
+    if let Some((_, song)) = title.rsplit_once(" - ") {
+        answers.push(normalize(song));
+    }
+    answers.retain(|a| !a.is_empty());
+    answers
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+/// 0.0 ~ 1.0 유사도 (편집 거리 기준)
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / longest as f64
+}
+
+/// 답이 제목과 충분히 비슷한지
+pub fn is_correct(guess: &str, title: &str) -> bool {
+    let guess = normalize(guess);
+    if guess.chars().count() < 2 {
+        return false;
+    }
+    answers(title).iter().any(|answer| {
+        // 가수 이름까지 붙여 쓴 답도 인정
+        (answer.chars().count() >= 4 && guess.contains(answer.as_str()))
+            || similarity(&guess, answer) >= MATCH_THRESHOLD
+    })
+}
+
+/// 빨리 맞힐수록 높은 점수
+pub fn points_for(elapsed: Duration, limit: Duration) -> u32 {
+    if limit.is_zero() {
+        return MIN_POINTS;
+    }
+    let ratio = (elapsed.as_secs_f64() / limit.as_secs_f64()).clamp(0.0, 1.0);
+    MAX_POINTS - ((MAX_POINTS - MIN_POINTS) as f64 * ratio).round() as u32
+}
+
+/// `m:ss` 또는 `h:mm:ss` 길이를 초로 변환
+pub fn duration_secs(duration: &str) -> Option<u64> {
+    duration
+        .split(':')
+        .try_fold(0u64, |acc, part| Some(acc * 60 + part.parse::<u64>().ok()?))
+}
+
+/// 발췌 시작 위치: 곡 길이의 20% ~ 60% 사이에서 고르되 끝까지 `length`가 남도록
+pub fn excerpt_start(total_secs: u64, length: Duration, pick: f64) -> Duration {
+    let length = length.as_secs();
+    if total_secs <= length {
+        return Duration::ZERO;
+    }
+    let earliest = total_secs / 5;
+    let latest = (total_secs * 3 / 5).min(total_secs - length).max(earliest);
+    let offset = earliest as f64 + (latest - earliest) as f64 * pick.clamp(0.0, 1.0);
+    Duration::from_secs(offset as u64)
+}
+
+/// 한 게임의 점수판
+#[derive(Debug, Default)]
+pub struct Scoreboard {
+    scores: HashMap<UserId, u32>,
+}
+
+impl Scoreboard {
+    pub fn award(&mut self, user_id: UserId, points: u32) -> u32 {
+        let total = self.scores.entry(user_id).or_default();
+        *total += points;
+        *total
+    }
+
+    /// 점수 높은 순 (동점이면 사용자 ID 순)
+    pub fn ranking(&self) -> Vec<(UserId, u32)> {
+        let mut ranking: Vec<_> = self.scores.iter().map(|(u, s)| (*u, *s)).collect();
+        ranking.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranking
+    }
+}
+
+/// 진행 중인 퀴즈 (서버당 하나)
+///
+/// 음악 대기열(`GuildQueue`)과는 따로 관리되므로 퀴즈가 대기열을 바꾸지 않습니다.
+#[derive(Default)]
+pub struct QuizGames {
+    games: Mutex<HashMap<GuildId, Arc<Notify>>>,
+}
+
+impl QuizGames {
+    /// 새 게임을 등록합니다. 이미 진행 중이면 `None`.
+    pub fn start(&self, guild_id: GuildId) -> Option<Arc<Notify>> {
+        let mut games = self.games.lock().unwrap();
+        if games.contains_key(&guild_id) {
+            return None;
+        }
+        let stop = Arc::new(Notify::new());
+        games.insert(guild_id, stop.clone());
+        Some(stop)
+    }
+
+    /// 진행 중인 게임에 중지를 요청합니다
+    pub fn stop(&self, guild_id: GuildId) -> bool {
+        match self.games.lock().unwrap().get(&guild_id) {
+            Some(stop) => {
+                stop.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn finish(&self, guild_id: GuildId) {
+        self.games.lock().unwrap().remove(&guild_id);
+    }
+
+    pub fn is_running(&self, guild_id: GuildId) -> bool {
+        self.games.lock().unwrap().contains_key(&guild_id)
+    }
+
+    /// 대기열 곡을 바꾸거나 다시 틀어도 되는지 (퀴즈 중에는 발췌 곡이 끊기지 않도록 막음)
+    pub fn check_music(&self, guild_id: GuildId) -> Result<(), &'static str> {
+        if self.is_running(guild_id) {
+            return Err(MUSIC_ON_HOLD);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_strips_decorations() {
+        assert_eq!(normalize("IU - Blueming (Official MV)"), "iublueming");
+        assert_eq!(normalize("Song [Lyrics] feat. Someone"), "song");
+        assert_eq!(normalize("아이유(IU) - 좋은 날"), "아이유좋은날");
+    }
+
+    #[test]
+    fn test_is_correct_fuzzy() {
+        let title = "IU - Blueming (Official MV)";
+        assert!(is_correct("blueming", title));
+        assert!(is_correct("Bluming", title));
+        assert!(is_correct("iu blueming", title));
+        assert!(is_correct("아이유 blueming!!", title));
+        assert!(!is_correct("celebrity", title));
+        assert!(!is_correct("b", title));
+
+        assert!(is_correct("좋은날", "아이유(IU) - 좋은 날"));
+        assert!(!is_correct("나쁜날씨", "아이유(IU) - 좋은 날"));
+    }
+
+    #[test]
+    fn test_points_decrease_with_time() {
+        let limit = Duration::from_secs(20);
+        assert_eq!(points_for(Duration::ZERO, limit), 100);
+        assert_eq!(points_for(Duration::from_secs(10), limit), 60);
+        assert_eq!(points_for(Duration::from_secs(20), limit), 20);
+        assert_eq!(points_for(Duration::from_secs(99), limit), 20);
+    }
+
+    #[test]
+    fn test_duration_and_excerpt_start() {
+        assert_eq!(duration_secs("3:25"), Some(205));
+        assert_eq!(duration_secs("1:00:00"), Some(3600));
+        assert_eq!(duration_secs("live"), None);
+
+        let len = Duration::from_secs(20);
+        assert_eq!(excerpt_start(200, len, 0.0), Duration::from_secs(40));
+        assert_eq!(excerpt_start(200, len, 1.0), Duration::from_secs(120));
+        // 짧은 곡은 끝까지 들을 수 있는 위치까지만
+        assert_eq!(excerpt_start(30, len, 1.0), Duration::from_secs(10));
+        assert_eq!(excerpt_start(15, len, 0.5), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_scoreboard_ranking() {
+        let mut board = Scoreboard::default();
+        board.award(UserId::new(2), 50);
+        board.award(UserId::new(1), 80);
+        assert_eq!(board.award(UserId::new(2), 30), 80);
+        assert_eq!(
+            board.ranking(),
+            vec![(UserId::new(1), 80), (UserId::new(2), 80)]
+        );
+    }
+
+    #[test]
+    fn test_one_game_per_guild() {
+        let games = QuizGames::default();
+        let guild = GuildId::new(1);
+        assert!(games.start(guild).is_some());
+        assert!(games.start(guild).is_none());
+        assert!(games.is_running(guild));
+        assert!(games.stop(guild));
+        games.finish(guild);
+        assert!(!games.stop(guild));
+        assert!(games.start(guild).is_some());
+    }
+}
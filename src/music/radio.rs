@@ -0,0 +1,290 @@
+use std::time::Duration;
+
+use serenity::model::id::GuildId;
+use songbird::tracks::TrackHandle;
+use tracing::{info, warn};
+
+use super::{queue, QueueManager, Song};
+
+/// 서버마다 저장할 수 있는 라디오 프리셋 수
+pub const MAX_PRESETS: usize = 25;
+/// 프리셋 이름 최대 길이 (글자 수)
+pub const MAX_NAME_LEN: usize = 32;
+/// 연결이 끊긴 방송을 다시 연결하기 전 대기 시간
+pub const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+/// 이보다 짧게 재생되고 끊기면 방송국에 문제가 있는 것으로 보고 다음 곡으로 넘어감
+pub const MIN_PLAY_TIME: Duration = Duration::from_secs(10);
+/// 방송 중인 곡 제목을 다시 확인하는 간격
+const TITLE_POLL_INTERVAL: Duration = Duration::from_secs(20);
+/// 곡 제목 조회 한 번에 쓸 최대 시간
+const TITLE_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const NOT_SKIPPABLE: &str =
+    "라디오는 건너뛸 수 없습니다. `/radio stop`으로 끄면 다음 곡으로 넘어갑니다.";
+
+/// 스킵이나 바로 재생으로 다음 곡으로 넘어가도 되는지 (재생 중인 라디오는 `/radio stop`으로만 끔)
+pub async fn check_skippable(
+    manager: &QueueManager,
+    guild_id: GuildId,
+) -> Result<(), &'static str> {
+    if queue::get_current(manager, guild_id)
+        .await
+        .is_some_and(|song| song.is_radio)
+    {
+        return Err(NOT_SKIPPABLE);
+    }
+    Ok(())
+}
+
+/// 서버에 저장된 인터넷 라디오 방송국
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadioPreset {
+    pub name: String,
+    pub url: String,
+}
+
+impl RadioPreset {
+    /// 대기열에 넣을 끝나지 않는 항목
+    pub fn to_song(&self) -> Song {
+        Song {
+            title: self.name.clone(),
+            url: self.url.clone(),
+            is_live: true,
+            is_radio: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// 프리셋 URL 검사 (Icecast/SHOUTcast 스트림 주소)
+pub fn is_stream_url(url: &str) -> bool {
+    let url = url.trim();
+    (url.starts_with("http://") || url.starts_with("https://"))
+        && !url.contains(char::is_whitespace)
+        && url.len() > "https://".len()
+}
+
+/// ICY 메타데이터 블록에서 `StreamTitle` 값을 꺼냅니다
+///
+/// 제목 안에 작은따옴표가 들어가는 방송국이 많아서 `';`까지를 제목으로 봅니다.
+pub fn parse_stream_title(metadata: &str) -> Option<String> {
+    const KEY: &str = "StreamTitle='";
+    let start = metadata.find(KEY)? + KEY.len();
+    let rest = &metadata[start..];
+    let end = rest
+        .find("';")
+        .or_else(|| rest.rfind('\''))
+        .unwrap_or(rest.len());
+    let title = rest[..end].trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// `icy-metaint` 바이트마다 끼어드는 메타데이터 블록을 오디오 스트림에서 골라냅니다
+pub struct IcyReader {
+    metaint: usize,
+    /// 다음 메타데이터 블록까지 남은 오디오 바이트
+    audio_left: usize,
+    /// 읽는 중인 메타데이터 블록 (길이, 읽은 바이트)
+    block: Option<(usize, Vec<u8>)>,
+}
+
+impl IcyReader {
+    pub fn new(metaint: usize) -> Self {
+        Self {
+            metaint,
+            audio_left: metaint,
+            block: None,
+        }
+    }
+
+    /// 받은 바이트를 넣고, 비어 있지 않은 메타데이터 블록이 완성되면 돌려줍니다
+    pub fn push(&mut self, mut bytes: &[u8]) -> Option<String> {
+        while !bytes.is_empty() {
+            match self.block.as_mut() {
+                None if self.audio_left > 0 => {
+                    let skip = self.audio_left.min(bytes.len());
+                    self.audio_left -= skip;
+                    bytes = &bytes[skip..];
+                }
+                None => {
+                    // 길이 바이트 × 16이 메타데이터 길이
+                    let len = bytes[0] as usize * 16;
+                    bytes = &bytes[1..];
+                    if len == 0 {
+                        self.audio_left = self.metaint;
+                    } else {
+                        self.block = Some((len, Vec::with_capacity(len)));
+                    }
+                }
+                Some((len, data)) => {
+                    let take = (*len - data.len()).min(bytes.len());
+                    data.extend_from_slice(&bytes[..take]);
+                    bytes = &bytes[take..];
+                    if data.len() == *len {
+                        let text = String::from_utf8_lossy(data)
+                            .trim_end_matches('\0')
+                            .to_string();
+                        self.block = None;
+                        self.audio_left = self.metaint;
+                        return Some(text);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// 방송국에 잠깐 접속해 지금 나오는 곡 제목을 읽습니다 (ICY 메타데이터를 보내지 않으면 `None`)
+pub async fn fetch_stream_title(
+    http_client: &reqwest::Client,
+    url: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut response = http_client
+        .get(url)
+        .header("Icy-MetaData", "1")
+        .send()
+        .await?
+        .error_for_status()?;
+    let Some(metaint) = response
+        .headers()
+        .get("icy-metaint")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|&n| n > 0)
+    else {
+        return Ok(None);
+    };
+
+    let mut reader = IcyReader::new(metaint);
+    while let Some(chunk) = response.chunk().await? {
+        if let Some(metadata) = reader.push(&chunk) {
+            return Ok(parse_stream_title(&metadata));
+        }
+    }
+    Ok(None)
+}
+
+/// `track`이 재생되는 동안 방송 중인 곡 제목을 주기적으로 갱신합니다
+pub fn watch_stream_title(
+    guild_id: GuildId,
+    queue_manager: QueueManager,
+    http_client: reqwest::Client,
+    track: TrackHandle,
+    url: String,
+) {
+    tokio::spawn(async move {
+        loop {
+            // 다른 곡으로 넘어갔거나 다시 연결했으면 그 트랙의 감시에 맡김
+            let still_playing = queue::get_track_handle(&queue_manager, guild_id)
+                .await
+                .is_some_and(|current| current.uuid() == track.uuid());
+            if !still_playing {
+                return;
+            }
+
+            match tokio::time::timeout(TITLE_FETCH_TIMEOUT, fetch_stream_title(&http_client, &url))
+                .await
+            {
+                Ok(Ok(Some(title))) => {
+                    if queue::set_stream_title(&queue_manager, guild_id, &url, &title).await {
+                        info!("라디오 곡 변경: {title} (guild: {guild_id})");
+                        let song = queue::get_current(&queue_manager, guild_id).await;
+                        queue_manager.feed().publish(guild_id, song);
+                    }
+                }
+                Ok(Ok(None)) => return, // 곡 제목을 보내지 않는 방송국
+                Ok(Err(e)) => warn!("라디오 곡 제목 조회 실패: {e}"),
+                Err(_) => warn!("라디오 곡 제목 조회 시간 초과: {url}"),
+            }
+
+            tokio::time::sleep(TITLE_POLL_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream_title() {
+        assert_eq!(
+            parse_stream_title("StreamTitle='Artist - Song';StreamUrl='http://x';").as_deref(),
+            Some("Artist - Song")
+        );
+        assert_eq!(
+            parse_stream_title("StreamTitle='Don't Stop Me Now';").as_deref(),
+            Some("Don't Stop Me Now")
+        );
+        assert_eq!(
+            parse_stream_title("StreamTitle='No Terminator'").as_deref(),
+            Some("No Terminator")
+        );
+        assert!(parse_stream_title("StreamTitle='';").is_none());
+        assert!(parse_stream_title("StreamUrl='http://x';").is_none());
+    }
+
+    /// `metaint` 바이트 오디오마다 길이 바이트와 16바이트 단위로 채운 메타데이터를 넣은 스트림
+    fn icy_stream(metaint: usize, blocks: &[&str]) -> Vec<u8> {
+        let mut stream = Vec::new();
+        for block in blocks {
+            stream.extend(std::iter::repeat_n(0xAA, metaint));
+            let len = block.len().div_ceil(16);
+            stream.push(len as u8);
+            stream.extend_from_slice(block.as_bytes());
+            stream.extend(std::iter::repeat_n(0, len * 16 - block.len()));
+        }
+        stream
+    }
+
+    #[test]
+    fn test_icy_reader_across_chunks() {
+        let stream = icy_stream(100, &["", "StreamTitle='Chunked';"]);
+
+        // Feed a byte at a time so every boundary lands mid-chunk
+        let mut reader = IcyReader::new(100);
+        let found: Vec<_> = stream.iter().filter_map(|b| reader.push(&[*b])).collect();
+        assert_eq!(found, ["StreamTitle='Chunked';"]);
+
+        // All at once: the empty block is skipped
+        let mut reader = IcyReader::new(100);
+        assert_eq!(
+            reader.push(&stream).as_deref(),
+            Some("StreamTitle='Chunked';")
+        );
+    }
+
+    #[test]
+    fn test_preset_to_song_and_url_check() {
+        let preset = RadioPreset {
+            name: "Lofi FM".to_string(),
+            url: "https://stream.example.com/lofi.mp3".to_string(),
+        };
+        let song = preset.to_song();
+        assert!(song.is_radio && song.is_live);
+        assert_eq!(song.title, "Lofi FM");
+
+        assert!(is_stream_url("http://icecast.example.com:8000/live"));
+        assert!(!is_stream_url("ftp://example.com/live"));
+        assert!(!is_stream_url("https://"));
+        assert!(!is_stream_url("https://exa mple.com"));
+    }
+
+    #[tokio::test]
+    async fn test_check_skippable() {
+        let manager = crate::music::new_queue_manager();
+        let guild = GuildId::new(1);
+        assert!(check_skippable(&manager, guild).await.is_ok());
+
+        let preset = RadioPreset {
+            name: "Lofi FM".to_string(),
+            url: "https://stream.example.com/lofi.mp3".to_string(),
+        };
+        queue::add_song(&manager, guild, preset.to_song())
+            .await
+            .unwrap();
+        queue::get_next_song(&manager, guild, false).await;
+        assert_eq!(check_skippable(&manager, guild).await, Err(NOT_SKIPPABLE));
+    }
+}
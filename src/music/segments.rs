@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use songbird::events::{Event, EventContext, EventHandler};
+use tracing::info;
+
+use super::Song;
+
+const SPONSORBLOCK_API_URL: &str = "https://sponsor.ajay.app/api/skipSegments";
+const DEFAULT_CATEGORIES: &[&str] = &["sponsor", "intro", "outro", "selfpromo"];
+
+/// 재생 위치를 확인해 건너뛸 구간인지 검사하는 주기
+pub const SKIP_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub category: String,
+}
+
+/// 곡에서 자동으로 건너뛸 구간(스폰서, 인트로 등)을 제공합니다.
+#[async_trait]
+pub trait SegmentProvider: Send + Sync {
+    async fn segments(
+        &self,
+        song: &Song,
+    ) -> Result<Vec<Segment>, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// SponsorBlock API 기반 구간 제공자
+pub struct SponsorBlockProvider {
+    http_client: reqwest::Client,
+    categories: Vec<String>,
+}
+
+impl SponsorBlockProvider {
+    pub fn new(http_client: reqwest::Client) -> Self {
+        Self {
+            http_client,
+            categories: DEFAULT_CATEGORIES.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SponsorBlockSegment {
+    segment: [f64; 2],
+    category: String,
+}
+
+pub(crate) fn parse_sponsorblock_response(
+    body: &[u8],
+) -> Result<Vec<Segment>, Box<dyn std::error::Error + Send + Sync>> {
+    let raw: Vec<SponsorBlockSegment> = serde_json::from_slice(body)?;
+    let mut segments: Vec<Segment> = raw
+        .into_iter()
+        .filter(|s| s.segment[1] > s.segment[0])
+        .map(|s| Segment {
+            start_secs: s.segment[0],
+            end_secs: s.segment[1],
+            category: s.category,
+        })
+        .collect();
+    segments.sort_by(|a, b| a.start_secs.total_cmp(&b.start_secs));
+    Ok(segments)
+}
+
+#[async_trait]
+impl SegmentProvider for SponsorBlockProvider {
+    async fn segments(
+        &self,
+        song: &Song,
+    ) -> Result<Vec<Segment>, Box<dyn std::error::Error + Send + Sync>> {
+        let video_id = match youtube_video_id(&song.url) {
+            Some(id) => id,
+            None => return Ok(vec![]),
+        };
+
+        let categories = serde_json::to_string(&self.categories)?;
+        let resp = self
+            .http_client
+            .get(SPONSORBLOCK_API_URL)
+            .query(&[("videoID", video_id.as_str()), ("categories", &categories)])
+            .send()
+            .await?;
+
+        // 등록된 구간이 없으면 404를 반환
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(vec![]);
+        }
+
+        let body = resp.error_for_status()?.bytes().await?;
+        parse_sponsorblock_response(&body)
+    }
+}
+
+/// URL별로 고정된 구간을 돌려주는 로컬 제공자 (테스트/오프라인용)
+#[derive(Default)]
+pub struct StaticSegmentProvider {
+    segments: HashMap<String, Vec<Segment>>,
+}
+
+impl StaticSegmentProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_segments(mut self, url: &str, segments: Vec<Segment>) -> Self {
+        self.segments.insert(url.to_string(), segments);
+        self
+    }
+}
+
+#[async_trait]
+impl SegmentProvider for StaticSegmentProvider {
+    async fn segments(
+        &self,
+        song: &Song,
+    ) -> Result<Vec<Segment>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.segments.get(&song.url).cloned().unwrap_or_default())
+    }
+}
+
+/// YouTube URL에서 영상 ID를 추출합니다
+pub fn youtube_video_id(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let (host, path) = rest.split_once('/')?;
+    let host = host.trim_start_matches("www.").trim_start_matches("m.");
+
+    let id = match host {
+        "youtu.be" => path.split(['?', '&', '#']).next(),
+        "youtube.com" | "music.youtube.com" => {
+            if let Some(shorts) = path.strip_prefix("shorts/") {
+                shorts.split(['?', '&', '#']).next()
+            } else {
+                path.split_once('?')
+                    .map(|(_, query)| query)
+                    .and_then(|query| query.split('&').find_map(|kv| kv.strip_prefix("v=")))
+                    .map(|v| v.split('#').next().unwrap_or(v))
+            }
+        }
+        _ => None,
+    }?;
+
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+/// 재생 위치가 속한 건너뛸 구간
+pub fn segment_at(segments: &[Segment], position_secs: f64) -> Option<&Segment> {
+    segments
+        .iter()
+        .find(|s| s.start_secs <= position_secs && position_secs < s.end_secs)
+}
+
+/// 주기적으로 재생 위치를 확인해 구간 끝으로 이동하는 이벤트 핸들러
+pub(crate) struct SegmentSkipper {
+    segments: Vec<Segment>,
+}
+
+impl SegmentSkipper {
+    pub(crate) fn new(segments: Vec<Segment>) -> Self {
+        Self { segments }
+    }
+}
+
+#[async_trait]
+impl EventHandler for SegmentSkipper {
+    async fn act(&self, ctx: &EventContext<'_>) -> Option<Event> {
+        if let EventContext::Track(tracks) = ctx {
+            for (state, handle) in tracks.iter() {
+                let position = state.position.as_secs_f64();
+                if let Some(segment) = segment_at(&self.segments, position) {
+                    info!(
+                        "구간 건너뛰기: {} ({:.1}s → {:.1}s)",
+                        segment.category, segment.start_secs, segment.end_secs
+                    );
+                    let _ = handle.seek(Duration::from_secs_f64(segment.end_secs));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: f64, end: f64) -> Segment {
+        Segment {
+            start_secs: start,
+            end_secs: end,
+            category: "sponsor".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_youtube_video_id() {
+        let cases = [
+            (
+                "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+                Some("dQw4w9WgXcQ"),
+            ),
+            (
+                "https://youtube.com/watch?list=x&v=abc123&t=10",
+                Some("abc123"),
+            ),
+            ("https://youtu.be/abc123?t=5", Some("abc123")),
+            ("https://music.youtube.com/watch?v=xyz", Some("xyz")),
+            ("https://www.youtube.com/shorts/short1", Some("short1")),
+            ("https://soundcloud.com/artist/track", None),
+            ("https://youtube.com/watch", None),
+            ("not a url", None),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(youtube_video_id(url).as_deref(), expected, "url: {url}");
+        }
+    }
+
+    #[test]
+    fn test_parse_sponsorblock_response_sorts_and_filters() {
+        let body = br#"[
+            {"segment":[120.0,150.5],"category":"outro","UUID":"b"},
+            {"segment":[10.0,25.0],"category":"sponsor","UUID":"a"},
+            {"segment":[50.0,50.0],"category":"intro","UUID":"c"}
+        ]"#;
+        let segments = parse_sponsorblock_response(body).unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].category, "sponsor");
+        assert_eq!(segments[1].end_secs, 150.5);
+    }
+
+    #[test]
+    fn test_segment_at() {
+        let segments = vec![segment(10.0, 20.0), segment(60.0, 70.0)];
+        assert!(segment_at(&segments, 5.0).is_none());
+        assert_eq!(segment_at(&segments, 10.0), Some(&segments[0]));
+        assert!(segment_at(&segments, 20.0).is_none());
+        assert_eq!(segment_at(&segments, 65.0), Some(&segments[1]));
+    }
+
+    #[tokio::test]
+    async fn test_static_provider_returns_by_url() {
+        let song = Song {
+            url: "https://example.com/a".to_string(),
+            ..Default::default()
+        };
+        let provider =
+            StaticSegmentProvider::new().with_segments(&song.url, vec![segment(0.0, 5.0)]);
+
+        assert_eq!(provider.segments(&song).await.unwrap().len(), 1);
+
+        let other = Song {
+            url: "https://example.com/b".to_string(),
+            ..Default::default()
+        };
+        assert!(provider.segments(&other).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,457 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serenity::model::id::{ChannelId, GuildId};
+use std::sync::Mutex;
+
+use super::eq::EqBands;
+use super::policy::EnqueuePolicy;
+use super::radio::RadioPreset;
+use super::IdleFallback;
+
+/// 24/7 모드로 고정된 서버의 설정
+#[derive(Clone, Debug, PartialEq)]
+pub struct StaySetting {
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
+    pub fallback: Option<IdleFallback>,
+}
+
+/// 나중에 추가된 컬럼을 기존 DB에도 추가합니다
+fn ensure_column(conn: &Connection, column: &str, definition: &str) -> rusqlite::Result<()> {
+    let exists = conn
+        .prepare("SELECT 1 FROM pragma_table_info('guild_settings') WHERE name = ?1")?
+        .exists(params![column])?;
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE guild_settings ADD COLUMN {column} {definition};"
+        ))?;
+    }
+    Ok(())
+}
+
+/// 서버별로 재시작 후에도 유지되는 음악 설정
+pub struct GuildSettings {
+    conn: Mutex<Connection>,
+}
+
+impl GuildSettings {
+    pub fn new(path: &str) -> Result<Self, rusqlite::Error> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS guild_settings (
+                guild_id TEXT PRIMARY KEY,
+                volume REAL
+            );
+            CREATE TABLE IF NOT EXISTS radio_presets (
+                guild_id TEXT NOT NULL,
+                name TEXT NOT NULL COLLATE NOCASE,
+                url TEXT NOT NULL,
+                PRIMARY KEY (guild_id, name)
+            );",
+        )?;
+        ensure_column(&conn, "stay_channel_id", "TEXT")?;
+        ensure_column(&conn, "idle_fallback", "TEXT")?;
+        ensure_column(&conn, "equalizer", "TEXT")?;
+        ensure_column(&conn, "enqueue_policy", "TEXT")?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// 마지막으로 설정한 볼륨 (0.0 ~ 1.0)
+    pub fn volume(&self, guild_id: GuildId) -> Option<f32> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT volume FROM guild_settings WHERE guild_id = ?1",
+            params![guild_id.get().to_string()],
+            |row| row.get::<_, Option<f64>>(0),
+        )
+        .optional()
+        .unwrap_or_else(|e| {
+            tracing::error!("볼륨 설정 조회 실패: {e}");
+            None
+        })
+        .flatten()
+        .map(|v| v as f32)
+    }
+
+    pub fn set_volume(&self, guild_id: GuildId, volume: f32) {
+        let conn = self.conn.lock().unwrap();
+        if let Err(e) = conn.execute(
+            "INSERT INTO guild_settings (guild_id, volume) VALUES (?1, ?2)
+             ON CONFLICT(guild_id) DO UPDATE SET volume = excluded.volume",
+            params![guild_id.get().to_string(), volume as f64],
+        ) {
+            tracing::error!("볼륨 설정 저장 실패: {e}");
+        }
+    }
+
+    /// 마지막으로 설정한 EQ
+    pub fn equalizer(&self, guild_id: GuildId) -> Option<EqBands> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT equalizer FROM guild_settings WHERE guild_id = ?1",
+            params![guild_id.get().to_string()],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+        .unwrap_or_else(|e| {
+            tracing::error!("EQ 설정 조회 실패: {e}");
+            None
+        })
+        .flatten()
+        .and_then(|stored| EqBands::parse(&stored))
+    }
+
+    pub fn set_equalizer(&self, guild_id: GuildId, bands: &EqBands) {
+        let conn = self.conn.lock().unwrap();
+        if let Err(e) = conn.execute(
+            "INSERT INTO guild_settings (guild_id, equalizer) VALUES (?1, ?2)
+             ON CONFLICT(guild_id) DO UPDATE SET equalizer = excluded.equalizer",
+            params![guild_id.get().to_string(), bands.to_string()],
+        ) {
+            tracing::error!("EQ 설정 저장 실패: {e}");
+        }
+    }
+}
+
+impl GuildSettings {
+    /// 곡 추가 정책이 저장된 모든 서버 (시작 시 복원용)
+    pub fn all_enqueue_policies(&self) -> Vec<(GuildId, EnqueuePolicy)> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = match conn.prepare(
+            "SELECT guild_id, enqueue_policy FROM guild_settings
+             WHERE enqueue_policy IS NOT NULL",
+        ) {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::error!("곡 추가 정책 조회 실패: {e}");
+                return Vec::new();
+            }
+        };
+
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        });
+
+        match rows {
+            Ok(rows) => rows
+                .filter_map(|r| r.ok())
+                .filter_map(|(guild, policy)| {
+                    Some((
+                        GuildId::new(guild.parse().ok()?),
+                        serde_json::from_str(&policy).ok()?,
+                    ))
+                })
+                .collect(),
+            Err(e) => {
+                tracing::error!("곡 추가 정책 조회 실패: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn set_enqueue_policy(&self, guild_id: GuildId, policy: &EnqueuePolicy) {
+        let stored = match serde_json::to_string(policy) {
+            Ok(stored) => stored,
+            Err(e) => {
+                tracing::error!("곡 추가 정책 직렬화 실패: {e}");
+                return;
+            }
+        };
+        let conn = self.conn.lock().unwrap();
+        if let Err(e) = conn.execute(
+            "INSERT INTO guild_settings (guild_id, enqueue_policy) VALUES (?1, ?2)
+             ON CONFLICT(guild_id) DO UPDATE SET enqueue_policy = excluded.enqueue_policy",
+            params![guild_id.get().to_string(), stored],
+        ) {
+            tracing::error!("곡 추가 정책 저장 실패: {e}");
+        }
+    }
+}
+
+impl GuildSettings {
+    /// 서버의 라디오 프리셋 (이름순)
+    pub fn radio_presets(&self, guild_id: GuildId) -> Vec<RadioPreset> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = match conn
+            .prepare("SELECT name, url FROM radio_presets WHERE guild_id = ?1 ORDER BY name")
+        {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::error!("라디오 프리셋 조회 실패: {e}");
+                return Vec::new();
+            }
+        };
+
+        let rows = stmt.query_map(params![guild_id.get().to_string()], |row| {
+            Ok(RadioPreset {
+                name: row.get(0)?,
+                url: row.get(1)?,
+            })
+        });
+
+        match rows {
+            Ok(rows) => rows.filter_map(|r| r.ok()).collect(),
+            Err(e) => {
+                tracing::error!("라디오 프리셋 조회 실패: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// 이름으로 프리셋을 찾습니다 (대소문자 무시)
+    pub fn radio_preset(&self, guild_id: GuildId, name: &str) -> Option<RadioPreset> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT name, url FROM radio_presets WHERE guild_id = ?1 AND name = ?2",
+            params![guild_id.get().to_string(), name.trim()],
+            |row| {
+                Ok(RadioPreset {
+                    name: row.get(0)?,
+                    url: row.get(1)?,
+                })
+            },
+        )
+        .optional()
+        .unwrap_or_else(|e| {
+            tracing::error!("라디오 프리셋 조회 실패: {e}");
+            None
+        })
+    }
+
+    /// 프리셋을 추가합니다. 같은 이름이 이미 있으면 `false`.
+    pub fn add_radio_preset(&self, guild_id: GuildId, preset: &RadioPreset) -> bool {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR IGNORE INTO radio_presets (guild_id, name, url) VALUES (?1, ?2, ?3)",
+            params![guild_id.get().to_string(), preset.name, preset.url],
+        )
+        .map(|inserted| inserted > 0)
+        .unwrap_or_else(|e| {
+            tracing::error!("라디오 프리셋 저장 실패: {e}");
+            false
+        })
+    }
+
+    /// 프리셋을 삭제합니다. 없던 이름이면 `false`.
+    pub fn remove_radio_preset(&self, guild_id: GuildId, name: &str) -> bool {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM radio_presets WHERE guild_id = ?1 AND name = ?2",
+            params![guild_id.get().to_string(), name.trim()],
+        )
+        .map(|deleted| deleted > 0)
+        .unwrap_or_else(|e| {
+            tracing::error!("라디오 프리셋 삭제 실패: {e}");
+            false
+        })
+    }
+}
+
+impl GuildSettings {
+    /// 24/7 모드 설정 (꺼져 있으면 `None`)
+    pub fn stay(&self, guild_id: GuildId) -> Option<StaySetting> {
+        self.stays(Some(guild_id)).into_iter().next()
+    }
+
+    /// 24/7 모드가 켜진 모든 서버 (시작 시 재접속용)
+    pub fn all_stays(&self) -> Vec<StaySetting> {
+        self.stays(None)
+    }
+
+    fn stays(&self, guild_id: Option<GuildId>) -> Vec<StaySetting> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = match conn.prepare(
+            "SELECT guild_id, stay_channel_id, idle_fallback FROM guild_settings
+             WHERE stay_channel_id IS NOT NULL AND (?1 IS NULL OR guild_id = ?1)",
+        ) {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::error!("24/7 설정 조회 실패: {e}");
+                return Vec::new();
+            }
+        };
+
+        let rows = stmt.query_map(params![guild_id.map(|g| g.get().to_string())], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        });
+
+        match rows {
+            Ok(rows) => rows
+                .filter_map(|r| r.ok())
+                .filter_map(|(guild, channel, fallback)| {
+                    Some(StaySetting {
+                        guild_id: GuildId::new(guild.parse().ok()?),
+                        channel_id: ChannelId::new(channel.parse().ok()?),
+                        fallback: fallback.as_deref().and_then(IdleFallback::parse),
+                    })
+                })
+                .collect(),
+            Err(e) => {
+                tracing::error!("24/7 설정 조회 실패: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// 24/7 모드를 켜거나(`Some`) 끕니다(`None`)
+    pub fn set_stay(
+        &self,
+        guild_id: GuildId,
+        channel_id: Option<ChannelId>,
+        fallback: Option<&IdleFallback>,
+    ) {
+        let conn = self.conn.lock().unwrap();
+        if let Err(e) = conn.execute(
+            "INSERT INTO guild_settings (guild_id, stay_channel_id, idle_fallback)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(guild_id) DO UPDATE SET
+                stay_channel_id = excluded.stay_channel_id,
+                idle_fallback = excluded.idle_fallback",
+            params![
+                guild_id.get().to_string(),
+                channel_id.map(|c| c.get().to_string()),
+                fallback.map(|f| f.to_string()),
+            ],
+        ) {
+            tracing::error!("24/7 설정 저장 실패: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_roundtrip() {
+        let settings = GuildSettings::new(":memory:").unwrap();
+        let gid = GuildId::new(1);
+
+        assert_eq!(settings.volume(gid), None);
+
+        settings.set_volume(gid, 0.3);
+        assert_eq!(settings.volume(gid), Some(0.3));
+
+        settings.set_volume(gid, 0.8);
+        assert_eq!(settings.volume(gid), Some(0.8));
+        assert_eq!(settings.volume(GuildId::new(2)), None);
+    }
+
+    #[test]
+    fn test_equalizer_roundtrip() {
+        let settings = GuildSettings::new(":memory:").unwrap();
+        let gid = GuildId::new(1);
+        settings.set_volume(gid, 0.4);
+        assert_eq!(settings.equalizer(gid), None);
+
+        let rock = EqBands::preset("rock").unwrap();
+        settings.set_equalizer(gid, &rock);
+        assert_eq!(settings.equalizer(gid), Some(rock));
+        assert_eq!(settings.volume(gid), Some(0.4));
+    }
+
+    #[test]
+    fn test_stay_roundtrip() {
+        let settings = GuildSettings::new(":memory:").unwrap();
+        let gid = GuildId::new(1);
+        settings.set_volume(gid, 0.4);
+
+        assert!(settings.stay(gid).is_none());
+
+        settings.set_stay(gid, Some(ChannelId::new(10)), Some(&IdleFallback::Autoplay));
+        settings.set_stay(
+            GuildId::new(2),
+            Some(ChannelId::new(20)),
+            Some(&IdleFallback::Playlist(
+                "https://youtube.com/playlist?list=abc".to_string(),
+            )),
+        );
+
+        let stay = settings.stay(gid).unwrap();
+        assert_eq!(stay.channel_id, ChannelId::new(10));
+        assert_eq!(stay.fallback, Some(IdleFallback::Autoplay));
+        assert_eq!(settings.all_stays().len(), 2);
+        // 볼륨 등 다른 설정은 그대로 유지
+        assert_eq!(settings.volume(gid), Some(0.4));
+
+        settings.set_stay(gid, None, None);
+        assert!(settings.stay(gid).is_none());
+        assert_eq!(settings.all_stays().len(), 1);
+    }
+
+    #[test]
+    fn test_enqueue_policy_roundtrip() {
+        let settings = GuildSettings::new(":memory:").unwrap();
+        let gid = GuildId::new(1);
+        settings.set_volume(gid, 0.4);
+        settings.set_volume(GuildId::new(2), 0.6);
+        assert!(settings.all_enqueue_policies().is_empty());
+
+        let mut policy = EnqueuePolicy {
+            max_duration_secs: Some(600),
+            ..Default::default()
+        };
+        policy.block("spam");
+        settings.set_enqueue_policy(gid, &policy);
+
+        assert_eq!(settings.all_enqueue_policies(), [(gid, policy)]);
+        assert_eq!(settings.volume(gid), Some(0.4));
+    }
+
+    #[test]
+    fn test_radio_presets() {
+        let settings = GuildSettings::new(":memory:").unwrap();
+        let gid = GuildId::new(1);
+        let preset = |name: &str| RadioPreset {
+            name: name.to_string(),
+            url: format!("https://radio.example.com/{name}"),
+        };
+
+        assert!(settings.add_radio_preset(gid, &preset("lofi")));
+        assert!(settings.add_radio_preset(gid, &preset("Jazz")));
+        assert!(settings.add_radio_preset(GuildId::new(2), &preset("lofi")));
+        // Names are unique per guild regardless of case
+        assert!(!settings.add_radio_preset(gid, &preset("LOFI")));
+
+        let names: Vec<_> = settings
+            .radio_presets(gid)
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, ["Jazz", "lofi"]);
+        assert_eq!(settings.radio_preset(gid, "jazz"), Some(preset("Jazz")));
+
+        assert!(settings.remove_radio_preset(gid, "JAZZ"));
+        assert!(!settings.remove_radio_preset(gid, "jazz"));
+        assert_eq!(settings.radio_presets(gid), [preset("lofi")]);
+        assert_eq!(settings.radio_presets(GuildId::new(2)).len(), 1);
+    }
+
+    #[test]
+    fn test_migrates_old_schema() {
+        let path =
+            std::env::temp_dir().join(format!("everybot-settings-{}.db", std::process::id()));
+        let path = path.to_str().unwrap();
+        {
+            let conn = Connection::open(path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE guild_settings (guild_id TEXT PRIMARY KEY, volume REAL);
+                 INSERT INTO guild_settings VALUES ('1', 0.7);",
+            )
+            .unwrap();
+        }
+
+        let settings = GuildSettings::new(path).unwrap();
+        assert_eq!(settings.volume(GuildId::new(1)), Some(0.7));
+        settings.set_stay(GuildId::new(1), Some(ChannelId::new(5)), None);
+        assert!(settings.stay(GuildId::new(1)).is_some());
+
+        drop(settings);
+        let _ = std::fs::remove_file(path);
+    }
+}
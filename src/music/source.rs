@@ -1,7 +1,22 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::Deserialize;
+use songbird::input::{HlsRequest, HttpRequest, Input};
 use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use super::cache::MetadataCache;
+use super::{Chapter, Song};
 
-use super::Song;
+#[derive(Deserialize)]
+struct YtDlpChapter {
+    title: Option<String>,
+    start_time: f64,
+    end_time: f64,
+}
 
 #[derive(Deserialize)]
 struct YtDlpOutput {
@@ -9,73 +24,385 @@ struct YtDlpOutput {
     duration: Option<f64>,
     webpage_url: Option<String>,
     original_url: Option<String>,
+    chapters: Option<Vec<YtDlpChapter>>,
+    thumbnail: Option<String>,
+    uploader: Option<String>,
+    channel: Option<String>,
+    upload_date: Option<String>,
+    view_count: Option<u64>,
+    is_live: Option<bool>,
+    live_status: Option<String>,
 }
 
 pub(crate) fn is_url(query: &str) -> bool {
     query.starts_with("http://") || query.starts_with("https://")
 }
 
+/// yt-dlp의 `YYYYMMDD` 날짜를 `YYYY-MM-DD`로 변환
+fn format_upload_date(raw: &str) -> Option<String> {
+    if raw.len() != 8 || !raw.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("{}-{}-{}", &raw[0..4], &raw[4..6], &raw[6..8]))
+}
+
+fn format_duration(secs: f64) -> String {
+    let secs = secs as u64;
+    let mins = secs / 60;
+    let remaining = secs % 60;
+    format!("{mins}:{remaining:02}")
+}
+
+#[derive(Deserialize)]
+struct YtDlpPlaylist {
+    entries: Option<Vec<YtDlpPlaylistEntry>>,
+}
+
+#[derive(Deserialize)]
+struct YtDlpPlaylistEntry {
+    id: Option<String>,
+    title: Option<String>,
+    url: Option<String>,
+    duration: Option<f64>,
+    uploader: Option<String>,
+    channel: Option<String>,
+}
+
+/// `--flat-playlist -J` 출력을 곡 목록으로 변환합니다 (URL이 없는 항목은 제외)
+pub(crate) fn parse_flat_playlist(
+    stdout: &[u8],
+) -> Result<Vec<Song>, Box<dyn std::error::Error + Send + Sync>> {
+    let playlist: YtDlpPlaylist = serde_json::from_slice(stdout)?;
+
+    Ok(playlist
+        .entries
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| {
+            let url = entry.url.filter(|u| is_url(u)).or_else(|| {
+                entry
+                    .id
+                    .map(|id| format!("https://www.youtube.com/watch?v={id}"))
+            })?;
+            Some(Song {
+                title: entry.title.unwrap_or_else(|| "알 수 없음".to_string()),
+                url,
+                duration: entry.duration.map(format_duration),
+                uploader: entry.uploader.or(entry.channel),
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+/// 곡을 시작점으로 한 YouTube 믹스 재생목록 URL (자동 재생용)
+pub fn autoplay_url(song: &Song) -> Option<String> {
+    let id = super::segments::youtube_video_id(&song.url)?;
+    Some(format!("https://www.youtube.com/watch?v={id}&list=RD{id}"))
+}
+
 pub(crate) fn parse_yt_dlp_output(
     stdout: &[u8],
 ) -> Result<Song, Box<dyn std::error::Error + Send + Sync>> {
     let info: YtDlpOutput = serde_json::from_slice(stdout)?;
 
-    let duration = info.duration.map(|d| {
-        let secs = d as u64;
-        let mins = secs / 60;
-        let remaining = secs % 60;
-        format!("{mins}:{remaining:02}")
-    });
+    let duration = info.duration.map(format_duration);
 
     let url = info.webpage_url.or(info.original_url).unwrap_or_default();
+    let is_live = info.is_live.unwrap_or(false) || info.live_status.as_deref() == Some("is_live");
+
+    let chapters = info
+        .chapters
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| Chapter {
+            title: c.title.unwrap_or_else(|| format!("챕터 {}", i + 1)),
+            start_secs: c.start_time,
+            end_secs: c.end_time,
+        })
+        .collect();
 
     Ok(Song {
         title: info.title.unwrap_or_else(|| "알 수 없음".to_string()),
         url,
         duration,
         requester: String::new(),
+        requester_id: None,
+        chapters,
+        thumbnail: info.thumbnail,
+        uploader: info.uploader.or(info.channel),
+        upload_date: info.upload_date.as_deref().and_then(format_upload_date),
+        view_count: info.view_count,
+        is_live,
+        ..Default::default()
+    })
+}
+
+#[derive(Deserialize)]
+struct YtDlpStream {
+    url: String,
+    http_headers: Option<HashMap<String, String>>,
+    filesize: Option<u64>,
+    protocol: Option<String>,
+}
+
+/// 재생할 오디오 스트림. 한 번 조회해 두면 입력을 여러 번 만들 수 있습니다.
+#[derive(Clone, Debug)]
+pub struct StreamSource {
+    url: String,
+    headers: HeaderMap,
+    content_length: Option<u64>,
+    hls: bool,
+}
+
+impl StreamSource {
+    /// 주소를 그대로 여는 스트림 (라디오 등)
+    pub fn direct(url: String) -> Self {
+        Self {
+            url,
+            headers: HeaderMap::new(),
+            content_length: None,
+            hls: false,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn input(&self, http_client: &reqwest::Client) -> Input {
+        if self.hls {
+            HlsRequest::new_with_headers(
+                http_client.clone(),
+                self.url.clone(),
+                self.headers.clone(),
+            )
+            .into()
+        } else {
+            HttpRequest {
+                client: http_client.clone(),
+                request: self.url.clone(),
+                headers: self.headers.clone(),
+                content_length: self.content_length,
+            }
+            .into()
+        }
+    }
+}
+
+/// `-j` 출력에서 선택된 포맷의 스트림 주소와 요청 헤더를 꺼냅니다
+pub(crate) fn parse_stream(
+    stdout: &[u8],
+) -> Result<StreamSource, Box<dyn std::error::Error + Send + Sync>> {
+    let info: YtDlpStream = serde_json::from_slice(stdout)?;
+
+    let headers = info
+        .http_headers
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(k, v)| {
+            Some((
+                HeaderName::from_bytes(k.as_bytes()).ok()?,
+                HeaderValue::from_str(&v).ok()?,
+            ))
+        })
+        .collect();
+
+    Ok(StreamSource {
+        url: info.url,
+        headers,
+        content_length: info.filesize,
+        hls: info.protocol.as_deref() == Some("m3u8_native"),
     })
 }
 
-pub async fn get_song_info(query: &str) -> Result<Song, Box<dyn std::error::Error + Send + Sync>> {
-    let search_query = if is_url(query) {
-        query.to_string()
-    } else {
-        format!("ytsearch1:{query}")
-    };
+/// yt-dlp 실행 설정
+#[derive(Clone, Debug)]
+pub struct YtDlpConfig {
+    /// 실행 파일 경로
+    pub binary: String,
+    /// 모든 호출에 붙일 추가 인자 (쿠키 파일, 프록시 등)
+    pub extra_args: Vec<String>,
+    /// 동시에 실행할 수 있는 최대 프로세스 수
+    pub max_concurrent: usize,
+    /// 호출당 제한 시간 (넘으면 프로세스를 종료)
+    pub timeout: Duration,
+}
+
+impl Default for YtDlpConfig {
+    fn default() -> Self {
+        Self {
+            binary: "yt-dlp".to_string(),
+            extra_args: Vec::new(),
+            max_concurrent: 4,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl YtDlpConfig {
+    /// 쿠키 파일과 프록시를 추가 인자로 붙입니다
+    pub fn with_cookies_and_proxy(mut self, cookies: Option<&str>, proxy: Option<&str>) -> Self {
+        if let Some(cookies) = cookies {
+            self.extra_args
+                .extend(["--cookies".to_string(), cookies.to_string()]);
+        }
+        if let Some(proxy) = proxy {
+            self.extra_args
+                .extend(["--proxy".to_string(), proxy.to_string()]);
+        }
+        self
+    }
+}
+
+/// 동시 실행 수와 실행 시간을 제한하는 yt-dlp 실행기
+pub struct YtDlp {
+    binary: String,
+    extra_args: Vec<String>,
+    timeout: Duration,
+    permits: Semaphore,
+    cache: Option<MetadataCache>,
+}
+
+impl YtDlp {
+    pub fn new(config: YtDlpConfig) -> Self {
+        Self {
+            binary: config.binary,
+            extra_args: config.extra_args,
+            timeout: config.timeout,
+            permits: Semaphore::new(config.max_concurrent.max(1)),
+            cache: None,
+        }
+    }
+
+    pub fn with_cache(mut self, cache: MetadataCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// yt-dlp를 실행해 stdout을 반환합니다. 빈 자리가 날 때까지 기다리고, 제한 시간을 넘기면 종료합니다.
+    async fn run(
+        &self,
+        args: &[&str],
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let _permit = self.permits.acquire().await?;
+
+        let child = Command::new(&self.binary)
+            .args(&self.extra_args)
+            .args(args)
+            .kill_on_drop(true)
+            .output();
 
-    let output = Command::new("yt-dlp")
-        .args([
-            "-j",
-            "-f",
-            "bestaudio",
-            "--no-playlist",
-            "--no-warnings",
-            &search_query,
-        ])
-        .output()
-        .await?;
+        // 시간 초과 시 future가 drop되면서 kill_on_drop으로 프로세스도 종료됨
+        let output = match tokio::time::timeout(self.timeout, child).await {
+            Ok(output) => output?,
+            Err(_) => {
+                warn!("yt-dlp 시간 초과로 종료: {args:?}");
+                return Err(format!("yt-dlp 시간 초과 ({}초)", self.timeout.as_secs()).into());
+            }
+        };
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("yt-dlp 오류: {stderr}").into());
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("yt-dlp 오류: {stderr}").into());
+        }
+        Ok(output.stdout)
     }
 
-    parse_yt_dlp_output(&output.stdout)
+    pub async fn song_info(
+        &self,
+        query: &str,
+    ) -> Result<Song, Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(song) = self.cache.as_ref().and_then(|c| c.get(query)) {
+            return Ok(song);
+        }
+
+        let search_query = if is_url(query) {
+            query.to_string()
+        } else {
+            format!("ytsearch1:{query}")
+        };
+
+        let stdout = self
+            .run(&[
+                "-j",
+                "-f",
+                "bestaudio",
+                "--no-playlist",
+                "--no-warnings",
+                &search_query,
+            ])
+            .await?;
+        let song = parse_yt_dlp_output(&stdout)?;
+
+        // 라이브 상태는 바뀔 수 있으므로 캐시하지 않음
+        if let Some(cache) = self.cache.as_ref().filter(|_| !song.is_live) {
+            cache.put(query, &song);
+        }
+        Ok(song)
+    }
+
+    /// 재생목록의 곡들을 가져옵니다 (최대 `limit`곡, 개별 곡 정보는 조회하지 않음)
+    pub async fn playlist_songs(
+        &self,
+        url: &str,
+        limit: usize,
+    ) -> Result<Vec<Song>, Box<dyn std::error::Error + Send + Sync>> {
+        let stdout = self
+            .run(&[
+                "-J",
+                "--flat-playlist",
+                "--no-warnings",
+                "--playlist-end",
+                &limit.to_string(),
+                url,
+            ])
+            .await?;
+        parse_flat_playlist(&stdout)
+    }
+
+    /// 재생할 스트림 주소를 조회합니다 (메타데이터 조회와 같은 동시 실행 제한과 제한 시간 적용)
+    pub async fn stream_source(
+        &self,
+        url: &str,
+    ) -> Result<StreamSource, Box<dyn std::error::Error + Send + Sync>> {
+        let stdout = self
+            .run(&[
+                "-j",
+                "-f",
+                "ba[abr>0][vcodec=none]/best",
+                "--no-playlist",
+                "--no-warnings",
+                url,
+            ])
+            .await?;
+        parse_stream(&stdout)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
 
     // 1. Full JSON with all fields
     #[test]
     fn test_parse_full_output() {
-        let json = br#"{"title":"Test Song","duration":185.0,"webpage_url":"https://youtube.com/watch?v=abc","original_url":"https://youtube.com/watch?v=abc"}"#;
+        let json = br#"{"title":"Test Song","duration":185.0,"webpage_url":"https://youtube.com/watch?v=abc","original_url":"https://youtube.com/watch?v=abc","thumbnail":"https://i.ytimg.com/vi/abc/hq.jpg","uploader":"Test Artist","channel":"Test Channel","upload_date":"20240131","view_count":1234567,"is_live":false,"live_status":"not_live"}"#;
         let song = parse_yt_dlp_output(json).unwrap();
         assert_eq!(song.title, "Test Song");
         assert_eq!(song.url, "https://youtube.com/watch?v=abc");
         assert_eq!(song.duration, Some("3:05".to_string()));
+        assert_eq!(
+            song.thumbnail.as_deref(),
+            Some("https://i.ytimg.com/vi/abc/hq.jpg")
+        );
+        assert_eq!(song.uploader.as_deref(), Some("Test Artist"));
+        assert_eq!(song.upload_date.as_deref(), Some("2024-01-31"));
+        assert_eq!(song.view_count, Some(1234567));
+        assert!(!song.is_live);
     }
 
     // 2. Missing title defaults to "알 수 없음"
@@ -130,7 +457,47 @@ mod tests {
         assert_eq!(song.url, "");
     }
 
-    // 7. is_url detection
+    // 7. Chapters are parsed in order, untitled ones get a default name
+    #[test]
+    fn test_parse_chapters() {
+        let json = br#"{"title":"Mix","webpage_url":"https://example.com","chapters":[{"start_time":0.0,"end_time":95.5,"title":"Opening"},{"start_time":95.5,"end_time":200.0}]}"#;
+        let song = parse_yt_dlp_output(json).unwrap();
+        assert_eq!(song.chapters.len(), 2);
+        assert_eq!(song.chapters[0].title, "Opening");
+        assert_eq!(song.chapters[1].title, "챕터 2");
+        assert!((song.chapters[1].start_secs - 95.5).abs() < f64::EPSILON);
+    }
+
+    // 8. Missing or null chapters → empty
+    #[test]
+    fn test_parse_no_chapters() {
+        let json = br#"{"title":"Single","webpage_url":"https://example.com","chapters":null}"#;
+        let song = parse_yt_dlp_output(json).unwrap();
+        assert!(song.chapters.is_empty());
+    }
+
+    // 9. Live stream detection via is_live or live_status
+    #[test]
+    fn test_parse_live_stream() {
+        let json = br#"{"title":"24/7 Radio","webpage_url":"https://example.com","is_live":true}"#;
+        assert!(parse_yt_dlp_output(json).unwrap().is_live);
+
+        let json = br#"{"title":"24/7 Radio","webpage_url":"https://example.com","is_live":null,"live_status":"is_live"}"#;
+        assert!(parse_yt_dlp_output(json).unwrap().is_live);
+    }
+
+    // 10. Uploader falls back to channel; malformed upload_date is dropped
+    #[test]
+    fn test_parse_uploader_fallback_and_bad_date() {
+        let json = br#"{"title":"T","webpage_url":"https://example.com","channel":"Only Channel","upload_date":"2024-01"}"#;
+        let song = parse_yt_dlp_output(json).unwrap();
+        assert_eq!(song.uploader.as_deref(), Some("Only Channel"));
+        assert!(song.upload_date.is_none());
+        assert!(song.thumbnail.is_none());
+        assert!(song.view_count.is_none());
+    }
+
+    // 11. is_url detection
     #[test]
     fn test_is_url_detection() {
         assert!(is_url("http://example.com"));
@@ -139,4 +506,134 @@ mod tests {
         assert!(!is_url("lofi hip hop"));
         assert!(!is_url("ftp://example.com"));
     }
+
+    #[test]
+    fn test_parse_flat_playlist() {
+        let json = br#"{"title":"Mix","entries":[
+            {"id":"aaa","title":"First","url":"https://www.youtube.com/watch?v=aaa","duration":61.0,"channel":"Ch"},
+            {"id":"bbb","title":"Second","url":null},
+            {"title":"No id or url"}
+        ]}"#;
+        let songs = parse_flat_playlist(json).unwrap();
+        assert_eq!(songs.len(), 2);
+        assert_eq!(songs[0].duration.as_deref(), Some("1:01"));
+        assert_eq!(songs[0].uploader.as_deref(), Some("Ch"));
+        assert_eq!(songs[1].url, "https://www.youtube.com/watch?v=bbb");
+    }
+
+    #[test]
+    fn test_parse_stream() {
+        let json = br#"{"title":"T","url":"https://rr1.example.com/audio","filesize":4096,"protocol":"https","http_headers":{"User-Agent":"Mozilla/5.0","Bad Header":"x"}}"#;
+        let stream = parse_stream(json).unwrap();
+        assert_eq!(stream.url(), "https://rr1.example.com/audio");
+        assert_eq!(stream.content_length, Some(4096));
+        assert!(!stream.hls);
+        assert_eq!(stream.headers.len(), 1);
+        assert_eq!(stream.headers["user-agent"], "Mozilla/5.0");
+
+        let json = br#"{"url":"https://example.com/live.m3u8","protocol":"m3u8_native"}"#;
+        let stream = parse_stream(json).unwrap();
+        assert!(stream.hls);
+        assert!(stream.content_length.is_none());
+
+        assert!(parse_stream(br#"{"title":"no url"}"#).is_err());
+    }
+
+    #[test]
+    fn test_autoplay_url() {
+        let song = Song {
+            url: "https://youtu.be/abc123".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            autoplay_url(&song).as_deref(),
+            Some("https://www.youtube.com/watch?v=abc123&list=RDabc123")
+        );
+
+        let other = Song {
+            url: "https://soundcloud.com/a/b".to_string(),
+            ..Default::default()
+        };
+        assert!(autoplay_url(&other).is_none());
+    }
+
+    /// 테스트용 가짜 yt-dlp: `sh <script>`로 실행해 쓰기 직후 실행 시의 ETXTBSY를 피함
+    fn fake_ytdlp(name: &str, body: &str, timeout: Duration, max_concurrent: usize) -> YtDlp {
+        let path = std::env::temp_dir().join(format!(
+            "everybot-fake-ytdlp-{name}-{}.sh",
+            std::process::id()
+        ));
+        std::fs::write(&path, body).unwrap();
+        YtDlp::new(YtDlpConfig {
+            binary: "sh".to_string(),
+            extra_args: vec![path.to_string_lossy().into_owned()],
+            max_concurrent,
+            timeout,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_run_times_out_and_kills() {
+        let ytdlp = fake_ytdlp("slow", "sleep 10\n", Duration::from_millis(200), 1);
+        let started = std::time::Instant::now();
+        let err = ytdlp.song_info("anything").await.unwrap_err();
+        assert!(err.to_string().contains("시간 초과"));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_cache_skips_second_process() {
+        let counter =
+            std::env::temp_dir().join(format!("everybot-fake-ytdlp-count-{}", std::process::id()));
+        let _ = std::fs::remove_file(&counter);
+        let body = format!(
+            "echo call >> {}\necho '{{\"title\":\"Fake\",\"webpage_url\":\"https://example.com/x\"}}'\n",
+            counter.display()
+        );
+        let cache = MetadataCache::new(":memory:", Duration::from_secs(60)).unwrap();
+        let ytdlp = fake_ytdlp("cached", &body, Duration::from_secs(5), 2).with_cache(cache);
+
+        assert_eq!(ytdlp.song_info("Fake Song").await.unwrap().title, "Fake");
+        assert_eq!(ytdlp.song_info("fake  song").await.unwrap().title, "Fake");
+
+        let calls = std::fs::read_to_string(&counter).unwrap();
+        assert_eq!(calls.lines().count(), 1);
+        let _ = std::fs::remove_file(&counter);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_is_bounded() {
+        let body =
+            "sleep 0.3\necho '{\"title\":\"Slow\",\"webpage_url\":\"https://example.com/s\"}'\n";
+        let ytdlp = Arc::new(fake_ytdlp("bounded", body, Duration::from_secs(5), 1));
+        let started = std::time::Instant::now();
+
+        let tasks: Vec<_> = (0..3)
+            .map(|i| {
+                let ytdlp = ytdlp.clone();
+                tokio::spawn(async move { ytdlp.song_info(&format!("q{i}")).await.is_ok() })
+            })
+            .collect();
+        for task in tasks {
+            assert!(task.await.unwrap());
+        }
+
+        // 한 번에 하나씩만 실행되므로 최소 3번의 대기 시간
+        assert!(started.elapsed() >= Duration::from_millis(900));
+    }
+
+    #[test]
+    fn test_config_cookies_and_proxy() {
+        let config = YtDlpConfig::default()
+            .with_cookies_and_proxy(Some("/data/cookies.txt"), Some("socks5://127.0.0.1:1080"));
+        assert_eq!(
+            config.extra_args,
+            vec![
+                "--cookies",
+                "/data/cookies.txt",
+                "--proxy",
+                "socks5://127.0.0.1:1080"
+            ]
+        );
+    }
 }
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use serenity::builder::{CreateStageInstance, EditStageInstance};
+use serenity::http::Http;
+use serenity::model::id::ChannelId;
+use serenity::model::Permissions;
+use tracing::warn;
+
+/// 스테이지 주제 최대 길이
+const TOPIC_LIMIT: usize = 120;
+
+/// 스테이지 채널에서 봇이 말할 수 있게 되는 방법
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StageSpeak {
+    /// 멤버 음소거 권한으로 스스로 청중 상태를 해제
+    Unsuppress,
+    /// 발언권을 요청하고 진행자의 수락을 기다림
+    RequestToSpeak,
+}
+
+impl StageSpeak {
+    /// 채널 권한으로 가능한 방법을 고릅니다 (둘 다 없으면 `None`)
+    pub fn from_permissions(permissions: Permissions) -> Option<Self> {
+        if permissions.mute_members() {
+            Some(Self::Unsuppress)
+        } else if permissions.request_to_speak() {
+            Some(Self::RequestToSpeak)
+        } else {
+            None
+        }
+    }
+}
+
+/// 현재 곡 제목으로 만든 스테이지 주제
+pub fn topic_for(title: &str) -> String {
+    let topic = format!("🎵 {title}");
+    if topic.chars().count() <= TOPIC_LIMIT {
+        return topic;
+    }
+    let mut truncated: String = topic.chars().take(TOPIC_LIMIT - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// 곡이 바뀔 때 스테이지 주제를 갱신합니다
+#[derive(Clone)]
+pub struct StageTopic {
+    http: Arc<Http>,
+    channel_id: ChannelId,
+}
+
+impl StageTopic {
+    pub fn new(http: Arc<Http>, channel_id: ChannelId) -> Self {
+        Self { http, channel_id }
+    }
+
+    pub async fn update(&self, title: &str) {
+        let topic = topic_for(title);
+        let edited = self
+            .channel_id
+            .edit_stage_instance(&self.http, EditStageInstance::new().topic(&topic))
+            .await;
+
+        // 진행 중인 스테이지가 없으면 새로 시작
+        if edited.is_err() {
+            if let Err(e) = self
+                .channel_id
+                .create_stage_instance(&self.http, CreateStageInstance::new(&topic))
+                .await
+            {
+                warn!("스테이지 주제 변경 실패: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speak_mode_from_permissions() {
+        assert_eq!(
+            StageSpeak::from_permissions(Permissions::MUTE_MEMBERS | Permissions::REQUEST_TO_SPEAK),
+            Some(StageSpeak::Unsuppress)
+        );
+        assert_eq!(
+            StageSpeak::from_permissions(Permissions::REQUEST_TO_SPEAK),
+            Some(StageSpeak::RequestToSpeak)
+        );
+        assert_eq!(
+            StageSpeak::from_permissions(Permissions::CONNECT | Permissions::SPEAK),
+            None
+        );
+    }
+
+    #[test]
+    fn test_topic_for_truncates() {
+        assert_eq!(topic_for("Song"), "🎵 Song");
+
+        let long = "가".repeat(200);
+        let topic = topic_for(&long);
+        assert_eq!(topic.chars().count(), TOPIC_LIMIT);
+        assert!(topic.ends_with('…'));
+    }
+}
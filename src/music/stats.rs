@@ -0,0 +1,448 @@
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection};
+use serenity::model::id::{GuildId, UserId};
+
+/// 시간대별 통계에 사용할 UTC 기준 시차 (KST)
+const UTC_OFFSET: &str = "+9 hours";
+/// 신청자 순위의 묶음 기준. ID가 없는 예전 기록은 표시 이름으로 묶습니다.
+const REQUESTER_KEY: &str = "COALESCE(requester_id, 'name:' || requester)";
+
+pub fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// 한 곡이 어떻게 끝났는지
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlayOutcome {
+    /// 끝까지 재생됨
+    Finished,
+    /// 스킵, 정지, 퇴장 등으로 중간에 멈춤
+    Skipped,
+}
+
+impl PlayOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            PlayOutcome::Finished => "finished",
+            PlayOutcome::Skipped => "skipped",
+        }
+    }
+}
+
+/// 재생 기록 한 건
+#[derive(Clone, Debug)]
+pub struct PlayEvent {
+    pub guild_id: GuildId,
+    pub title: String,
+    pub url: String,
+    pub requester: String,
+    pub requester_id: Option<UserId>,
+    pub started_at: i64,
+    pub listened: Duration,
+    pub outcome: PlayOutcome,
+}
+
+/// `/stats music`의 조회 기간
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StatsWindow {
+    #[default]
+    Week,
+    Month,
+    All,
+}
+
+impl StatsWindow {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "7d" | "7" | "week" | "주" => Some(Self::Week),
+            "30d" | "30" | "month" | "월" => Some(Self::Month),
+            "all" | "전체" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    /// 이 기간의 시작 시각 (전체 기간이면 0)
+    pub fn since(self, now: i64) -> i64 {
+        match self {
+            Self::Week => now - 7 * 86_400,
+            Self::Month => now - 30 * 86_400,
+            Self::All => 0,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Week => "최근 7일",
+            Self::Month => "최근 30일",
+            Self::All => "전체 기간",
+        }
+    }
+}
+
+/// 기간 내 전체 요약
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StatsSummary {
+    pub plays: u64,
+    pub skips: u64,
+    pub listened: Duration,
+    /// (시, 재생 수) — KST 기준
+    pub busiest_hour: Option<(u32, u64)>,
+    /// (요일 0=일요일, 재생 수) — KST 기준
+    pub busiest_weekday: Option<(u32, u64)>,
+}
+
+/// 순위표 한 줄
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankedEntry {
+    pub label: String,
+    /// 곡 순위일 때만 링크
+    pub url: Option<String>,
+    pub plays: u64,
+}
+
+/// 서버별 음악 재생 기록
+pub struct PlayStats {
+    conn: Mutex<Connection>,
+}
+
+impl PlayStats {
+    pub fn new(path: &str) -> Result<Self, rusqlite::Error> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS play_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                guild_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                url TEXT NOT NULL,
+                requester TEXT NOT NULL,
+                started_at INTEGER NOT NULL,
+                listened_secs INTEGER NOT NULL,
+                outcome TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_play_events_guild_time
+                ON play_events(guild_id, started_at);",
+        )?;
+        // 신청자 ID는 나중에 추가된 컬럼
+        let has_requester_id = conn
+            .prepare("SELECT 1 FROM pragma_table_info('play_events') WHERE name = 'requester_id'")?
+            .exists([])?;
+        if !has_requester_id {
+            conn.execute_batch("ALTER TABLE play_events ADD COLUMN requester_id TEXT;")?;
+        }
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn record(&self, event: &PlayEvent) {
+        let conn = self.conn.lock().unwrap();
+        if let Err(e) = conn.execute(
+            "INSERT INTO play_events
+                (guild_id, title, url, requester, requester_id, started_at, listened_secs, outcome)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                event.guild_id.get().to_string(),
+                event.title,
+                event.url,
+                event.requester,
+                event.requester_id.map(|id| id.get().to_string()),
+                event.started_at,
+                event.listened.as_secs() as i64,
+                event.outcome.as_str(),
+            ],
+        ) {
+            tracing::error!("재생 기록 저장 실패: {e}");
+        }
+    }
+
+    pub fn summary(&self, guild_id: GuildId, since: i64) -> StatsSummary {
+        self.try_summary(guild_id, since).unwrap_or_else(|e| {
+            tracing::error!("재생 통계 조회 실패: {e}");
+            StatsSummary::default()
+        })
+    }
+
+    fn try_summary(&self, guild_id: GuildId, since: i64) -> rusqlite::Result<StatsSummary> {
+        let conn = self.conn.lock().unwrap();
+        let guild = guild_id.get().to_string();
+
+        let (plays, skips, listened) = conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(outcome = 'skipped'), 0),
+                    COALESCE(SUM(listened_secs), 0)
+             FROM play_events WHERE guild_id = ?1 AND started_at >= ?2",
+            params![guild, since],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            },
+        )?;
+
+        let busiest = |format: &str| -> rusqlite::Result<Option<(u32, u64)>> {
+            let sql = format!(
+                "SELECT CAST(strftime('{format}', started_at, 'unixepoch', '{UTC_OFFSET}') AS INTEGER) AS slot,
+                        COUNT(*) AS plays
+                 FROM play_events WHERE guild_id = ?1 AND started_at >= ?2
+                 GROUP BY slot ORDER BY plays DESC, slot ASC LIMIT 1"
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            let mut rows = stmt.query(params![guild, since])?;
+            match rows.next()? {
+                Some(row) => Ok(Some((
+                    row.get::<_, i64>(0)? as u32,
+                    row.get::<_, i64>(1)? as u64,
+                ))),
+                None => Ok(None),
+            }
+        };
+
+        Ok(StatsSummary {
+            plays: plays as u64,
+            skips: skips as u64,
+            listened: Duration::from_secs(listened.max(0) as u64),
+            busiest_hour: busiest("%H")?,
+            busiest_weekday: busiest("%w")?,
+        })
+    }
+
+    /// 많이 재생된 곡 (URL 기준)
+    pub fn top_songs(
+        &self,
+        guild_id: GuildId,
+        since: i64,
+        limit: usize,
+        offset: usize,
+    ) -> Vec<RankedEntry> {
+        self.ranked(
+            "SELECT MAX(title), url, COUNT(*) AS plays FROM play_events
+             WHERE guild_id = ?1 AND started_at >= ?2
+             GROUP BY url ORDER BY plays DESC, MAX(started_at) DESC
+             LIMIT ?3 OFFSET ?4",
+            guild_id,
+            since,
+            limit,
+            offset,
+        )
+    }
+
+    /// 곡을 많이 신청한 사용자 (ID가 있으면 멘션으로 표시해 이름이 바뀌어도 한 줄로 묶임)
+    pub fn top_requesters(
+        &self,
+        guild_id: GuildId,
+        since: i64,
+        limit: usize,
+        offset: usize,
+    ) -> Vec<RankedEntry> {
+        self.ranked(
+            &format!(
+                "SELECT CASE WHEN requester_id IS NULL THEN requester
+                             ELSE '<@' || requester_id || '>' END,
+                        NULL, COUNT(*) AS plays, {REQUESTER_KEY} AS who
+                 FROM play_events
+                 WHERE guild_id = ?1 AND started_at >= ?2
+                 GROUP BY who ORDER BY plays DESC, who ASC
+                 LIMIT ?3 OFFSET ?4"
+            ),
+            guild_id,
+            since,
+            limit,
+            offset,
+        )
+    }
+
+    /// 순위표 페이지 계산용 항목 수 (곡 수, 신청자 수)
+    pub fn distinct_counts(&self, guild_id: GuildId, since: i64) -> (usize, usize) {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!(
+                "SELECT COUNT(DISTINCT url), COUNT(DISTINCT {REQUESTER_KEY}) FROM play_events
+                 WHERE guild_id = ?1 AND started_at >= ?2"
+            ),
+            params![guild_id.get().to_string(), since],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)? as usize,
+                    row.get::<_, i64>(1)? as usize,
+                ))
+            },
+        )
+        .unwrap_or_else(|e| {
+            tracing::error!("재생 통계 조회 실패: {e}");
+            (0, 0)
+        })
+    }
+
+    fn ranked(
+        &self,
+        sql: &str,
+        guild_id: GuildId,
+        since: i64,
+        limit: usize,
+        offset: usize,
+    ) -> Vec<RankedEntry> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.prepare(sql).and_then(|mut stmt| {
+            stmt.query_map(
+                params![
+                    guild_id.get().to_string(),
+                    since,
+                    limit as i64,
+                    offset as i64
+                ],
+                |row| {
+                    Ok(RankedEntry {
+                        label: row.get(0)?,
+                        url: row.get(1)?,
+                        plays: row.get::<_, i64>(2)? as u64,
+                    })
+                },
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()
+        });
+        result.unwrap_or_else(|e| {
+            tracing::error!("재생 순위 조회 실패: {e}");
+            Vec::new()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: GuildId = GuildId::new(1);
+    /// 2024-01-01 00:00:00 UTC (월요일, KST 09시)
+    const T0: i64 = 1_704_067_200;
+
+    fn event(title: &str, requester: &str, started_at: i64, outcome: PlayOutcome) -> PlayEvent {
+        PlayEvent {
+            guild_id: GUILD,
+            title: title.to_string(),
+            url: format!("https://example.com/{title}"),
+            requester: requester.to_string(),
+            requester_id: None,
+            started_at,
+            listened: Duration::from_secs(600),
+            outcome,
+        }
+    }
+
+    fn sample() -> PlayStats {
+        let stats = PlayStats::new(":memory:").unwrap();
+        stats.record(&event("A", "alice", T0, PlayOutcome::Finished));
+        stats.record(&event("A", "bob", T0 + 60, PlayOutcome::Skipped));
+        stats.record(&event("B", "alice", T0 + 120, PlayOutcome::Finished));
+        stats.record(&event("A", "alice", T0 + 3_600, PlayOutcome::Finished));
+        // 40일 전 기록
+        stats.record(&event(
+            "C",
+            "carol",
+            T0 - 40 * 86_400,
+            PlayOutcome::Finished,
+        ));
+        // 다른 서버 기록은 섞이지 않음
+        stats.record(&PlayEvent {
+            guild_id: GuildId::new(2),
+            ..event("A", "dave", T0, PlayOutcome::Finished)
+        });
+        stats
+    }
+
+    #[test]
+    fn test_window_parse_and_since() {
+        assert_eq!(StatsWindow::parse("7d"), Some(StatsWindow::Week));
+        assert_eq!(StatsWindow::parse("30D"), Some(StatsWindow::Month));
+        assert_eq!(StatsWindow::parse("전체"), Some(StatsWindow::All));
+        assert_eq!(StatsWindow::parse("1y"), None);
+        assert_eq!(StatsWindow::Week.since(T0), T0 - 7 * 86_400);
+        assert_eq!(StatsWindow::All.since(T0), 0);
+    }
+
+    #[test]
+    fn test_summary() {
+        let stats = sample();
+        let summary = stats.summary(GUILD, StatsWindow::Month.since(T0 + 3_600));
+        assert_eq!(summary.plays, 4);
+        assert_eq!(summary.skips, 1);
+        assert_eq!(summary.listened, Duration::from_secs(2_400));
+        assert_eq!(summary.busiest_hour, Some((9, 3)));
+        assert_eq!(summary.busiest_weekday, Some((1, 4)));
+
+        let all = stats.summary(GUILD, StatsWindow::All.since(T0));
+        assert_eq!(all.plays, 5);
+    }
+
+    #[test]
+    fn test_leaderboards_and_paging() {
+        let stats = sample();
+        let since = StatsWindow::Week.since(T0 + 3_600);
+
+        let songs = stats.top_songs(GUILD, since, 10, 0);
+        assert_eq!(songs.len(), 2);
+        assert_eq!(songs[0].label, "A");
+        assert_eq!(songs[0].plays, 3);
+        assert_eq!(songs[0].url.as_deref(), Some("https://example.com/A"));
+
+        let requesters = stats.top_requesters(GUILD, since, 10, 0);
+        assert_eq!(requesters[0].label, "alice");
+        assert_eq!(requesters[0].plays, 3);
+        assert_eq!(requesters[0].url, None);
+
+        let second_page = stats.top_songs(GUILD, since, 1, 1);
+        assert_eq!(second_page.len(), 1);
+        assert_eq!(second_page[0].label, "B");
+
+        assert_eq!(stats.distinct_counts(GUILD, since), (2, 2));
+        assert_eq!(stats.distinct_counts(GUILD, 0), (3, 3));
+    }
+
+    #[test]
+    fn test_requesters_keyed_by_id() {
+        let stats = sample();
+        let alice = UserId::new(11);
+        // 같은 사람이 표시 이름을 바꿔도 한 사람으로 집계
+        for (name, at) in [("alice", T0 + 7_200), ("앨리스", T0 + 7_260)] {
+            stats.record(&PlayEvent {
+                requester_id: Some(alice),
+                ..event("D", name, at, PlayOutcome::Finished)
+            });
+        }
+        // 이름이 같아도 다른 사람은 따로 집계
+        stats.record(&PlayEvent {
+            requester_id: Some(UserId::new(12)),
+            ..event("D", "alice", T0 + 7_320, PlayOutcome::Finished)
+        });
+
+        let since = StatsWindow::Week.since(T0 + 7_200);
+        let requesters: Vec<_> = stats
+            .top_requesters(GUILD, since, 10, 0)
+            .into_iter()
+            .map(|e| (e.label, e.plays))
+            .collect();
+        assert_eq!(
+            requesters,
+            [
+                ("alice".to_string(), 3),
+                ("<@11>".to_string(), 2),
+                ("<@12>".to_string(), 1),
+                ("bob".to_string(), 1),
+            ]
+        );
+        assert_eq!(stats.distinct_counts(GUILD, since), (3, 4));
+    }
+
+    #[test]
+    fn test_empty_guild() {
+        let stats = PlayStats::new(":memory:").unwrap();
+        let summary = stats.summary(GUILD, 0);
+        assert_eq!(summary, StatsSummary::default());
+        assert!(stats.top_songs(GUILD, 0, 10, 0).is_empty());
+    }
+}
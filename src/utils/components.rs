@@ -6,13 +6,44 @@ use serenity::builder::{
 };
 use serenity::collector::ComponentInteractionCollector;
 use serenity::model::application::ButtonStyle;
+use serenity::model::id::GuildId;
 
-use crate::music::Song;
+use crate::music::eq::{self, EqBands};
+use crate::music::{queue, LoopMode, QueueManager, Song};
 
 // ── Music components (PR #21) ───────────────────────────────────────────────
 
-pub fn music_buttons(is_paused: bool) -> CreateActionRow {
-    let pause_resume = if is_paused {
+/// 컨트롤러 버튼을 그리는 데 필요한 재생 상태
+#[derive(Clone, Debug, Default)]
+pub struct ControllerState {
+    pub is_paused: bool,
+    pub loop_mode: LoopMode,
+    pub shuffled: bool,
+    pub has_previous: bool,
+}
+
+impl ControllerState {
+    pub async fn load(manager: &QueueManager, guild_id: GuildId) -> Self {
+        let is_paused = match queue::get_track_handle(manager, guild_id).await {
+            Some(h) => h
+                .get_info()
+                .await
+                .map(|info| info.playing == songbird::tracks::PlayMode::Pause)
+                .unwrap_or(false),
+            None => false,
+        };
+
+        Self {
+            is_paused,
+            loop_mode: queue::get_loop_mode(manager, guild_id).await,
+            shuffled: queue::is_shuffled(manager, guild_id).await,
+            has_previous: queue::has_previous(manager, guild_id).await,
+        }
+    }
+}
+
+fn playback_buttons(state: &ControllerState) -> Vec<CreateButton> {
+    let pause_resume = if state.is_paused {
         CreateButton::new("music_resume")
             .label("재개")
             .emoji('▶')
@@ -34,29 +65,156 @@ pub fn music_buttons(is_paused: bool) -> CreateActionRow {
         .emoji('⏹')
         .style(ButtonStyle::Danger);
 
-    CreateActionRow::Buttons(vec![pause_resume, skip, stop])
+    let queue_view = CreateButton::new("music_queue_view")
+        .label("대기열")
+        .emoji('📋')
+        .style(ButtonStyle::Secondary);
+
+    let favorite = CreateButton::new("music_favorite")
+        .emoji('❤')
+        .style(ButtonStyle::Secondary);
+
+    vec![pause_resume, skip, stop, queue_view, favorite]
+}
+
+fn control_buttons(state: &ControllerState) -> Vec<CreateButton> {
+    let previous = CreateButton::new("music_previous")
+        .label("이전")
+        .emoji('⏮')
+        .style(ButtonStyle::Secondary)
+        .disabled(!state.has_previous);
+
+    let loop_emoji = match state.loop_mode {
+        LoopMode::Off => '➡',
+        LoopMode::Song => '🔂',
+        LoopMode::Queue => '🔁',
+    };
+    let loop_cycle = CreateButton::new("music_loop")
+        .label(format!("반복: {}", state.loop_mode))
+        .emoji(loop_emoji)
+        .style(if state.loop_mode == LoopMode::Off {
+            ButtonStyle::Secondary
+        } else {
+            ButtonStyle::Success
+        });
+
+    let shuffle = CreateButton::new("music_shuffle")
+        .label("셔플")
+        .emoji('🔀')
+        .style(if state.shuffled {
+            ButtonStyle::Success
+        } else {
+            ButtonStyle::Secondary
+        });
+
+    let volume_down = CreateButton::new("music_volume_down")
+        .label("-10%")
+        .emoji('🔉')
+        .style(ButtonStyle::Secondary);
+
+    let volume_up = CreateButton::new("music_volume_up")
+        .label("+10%")
+        .emoji('🔊')
+        .style(ButtonStyle::Secondary);
+
+    vec![previous, loop_cycle, shuffle, volume_down, volume_up]
+}
+
+pub fn music_buttons(state: &ControllerState) -> CreateActionRow {
+    CreateActionRow::Buttons(playback_buttons(state))
+}
+
+/// 두 번째 줄: 이전 곡, 반복 모드 순환, 셔플, 볼륨 조절
+pub fn music_control_buttons(state: &ControllerState) -> CreateActionRow {
+    CreateActionRow::Buttons(control_buttons(state))
 }
 
 pub fn music_buttons_disabled() -> CreateActionRow {
+    let buttons = playback_buttons(&ControllerState::default())
+        .into_iter()
+        .map(|b| b.disabled(true))
+        .collect();
+    CreateActionRow::Buttons(buttons)
+}
+
+fn music_control_buttons_disabled() -> CreateActionRow {
+    let buttons = control_buttons(&ControllerState::default())
+        .into_iter()
+        .map(|b| b.disabled(true))
+        .collect();
+    CreateActionRow::Buttons(buttons)
+}
+
+/// 대기열 보기(ephemeral) 페이지 이동 버튼.
+///
+/// Custom ID patterns (1부터 시작, 한 페이지뿐이어도 겹치지 않도록 방향별 접두사):
+/// - Previous: `music_queue_prev_{page - 1}`
+/// - Next: `music_queue_next_{page + 1}`
+pub fn queue_page_row(page: usize, total_pages: usize) -> CreateActionRow {
+    let total_pages = total_pages.max(1);
     CreateActionRow::Buttons(vec![
-        CreateButton::new("music_pause")
-            .label("일시정지")
-            .emoji('⏸')
-            .style(ButtonStyle::Primary)
-            .disabled(true),
-        CreateButton::new("music_skip")
-            .label("스킵")
-            .emoji('⏭')
+        CreateButton::new(format!(
+            "music_queue_prev_{}",
+            page.saturating_sub(1).max(1)
+        ))
+        .label("<< 이전")
+        .style(ButtonStyle::Secondary)
+        .disabled(page <= 1),
+        CreateButton::new("music_queue_page_indicator")
+            .label(format!("{page}/{total_pages}"))
             .style(ButtonStyle::Secondary)
             .disabled(true),
-        CreateButton::new("music_stop")
-            .label("정지")
-            .emoji('⏹')
-            .style(ButtonStyle::Danger)
-            .disabled(true),
+        CreateButton::new(format!("music_queue_next_{}", (page + 1).min(total_pages)))
+            .label("다음 >>")
+            .style(ButtonStyle::Secondary)
+            .disabled(page >= total_pages),
     ])
 }
 
+pub fn parse_queue_page(custom_id: &str) -> Option<usize> {
+    custom_id
+        .strip_prefix("music_queue_prev_")
+        .or_else(|| custom_id.strip_prefix("music_queue_next_"))?
+        .parse()
+        .ok()
+}
+
+/// /equalizer 화면의 프리셋 버튼. 지금 설정과 같은 프리셋은 강조합니다.
+///
+/// Custom ID pattern: `music_eq_preset_{name}`, 초기화는 `music_eq_reset`
+pub fn equalizer_row(bands: &EqBands) -> CreateActionRow {
+    let active = bands.preset_name();
+    let mut buttons: Vec<CreateButton> = eq::PRESETS
+        .iter()
+        .filter(|name| **name != "flat")
+        .map(|name| {
+            CreateButton::new(format!("music_eq_preset_{name}"))
+                .label(*name)
+                .style(if active == Some(name) {
+                    ButtonStyle::Success
+                } else {
+                    ButtonStyle::Secondary
+                })
+        })
+        .collect();
+    buttons.push(
+        CreateButton::new("music_eq_reset")
+            .label("초기화")
+            .emoji('↩')
+            .style(ButtonStyle::Danger)
+            .disabled(bands.is_flat()),
+    );
+    CreateActionRow::Buttons(buttons)
+}
+
+/// `music_eq_preset_{name}`에서 프리셋 이름을 꺼냅니다 (초기화 버튼은 `flat`)
+pub fn parse_eq_preset(custom_id: &str) -> Option<&str> {
+    if custom_id == "music_eq_reset" {
+        return Some("flat");
+    }
+    custom_id.strip_prefix("music_eq_preset_")
+}
+
 fn truncate_str(s: &str, max_chars: usize) -> String {
     if s.chars().count() <= max_chars {
         s.to_string()
@@ -66,7 +224,84 @@ fn truncate_str(s: &str, max_chars: usize) -> String {
     }
 }
 
-fn queue_select_menu(upcoming: &[Song]) -> CreateActionRow {
+/// 대기열 선택 메뉴 옵션 값: `queue_{version}_{index}`
+fn queue_option_value(version: u64, index: usize) -> String {
+    format!("queue_{version}_{index}")
+}
+
+/// `queue_{version}_{index}` 값을 (version, index)로 파싱
+pub fn parse_queue_option(value: &str) -> Option<(u64, usize)> {
+    let rest = value.strip_prefix("queue_")?;
+    let (version, index) = rest.split_once('_')?;
+    Some((version.parse().ok()?, index.parse().ok()?))
+}
+
+/// 대기열에서 선택한 곡에 대한 동작
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QueueAction {
+    Jump,
+    MoveNext,
+    Remove,
+    Details,
+}
+
+impl QueueAction {
+    const ALL: [QueueAction; 4] = [
+        QueueAction::Jump,
+        QueueAction::MoveNext,
+        QueueAction::Remove,
+        QueueAction::Details,
+    ];
+
+    fn id(self) -> &'static str {
+        match self {
+            QueueAction::Jump => "jump",
+            QueueAction::MoveNext => "next",
+            QueueAction::Remove => "remove",
+            QueueAction::Details => "details",
+        }
+    }
+
+    /// Custom ID pattern: `music_qa_{action}_{version}_{index}`
+    pub fn custom_id(self, version: u64, index: usize) -> String {
+        format!("music_qa_{}_{version}_{index}", self.id())
+    }
+
+    /// `music_qa_{action}_{version}_{index}`를 파싱
+    pub fn parse(custom_id: &str) -> Option<(Self, u64, usize)> {
+        let rest = custom_id.strip_prefix("music_qa_")?;
+        let mut parts = rest.splitn(3, '_');
+        let id = parts.next()?;
+        let action = Self::ALL.into_iter().find(|a| a.id() == id)?;
+        let version = parts.next()?.parse().ok()?;
+        let index = parts.next()?.parse().ok()?;
+        Some((action, version, index))
+    }
+}
+
+/// 대기열에서 선택한 곡에 대한 동작 버튼 (ephemeral 메시지용)
+pub fn queue_action_row(version: u64, index: usize) -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new(QueueAction::Jump.custom_id(version, index))
+            .label("바로 재생")
+            .emoji('▶')
+            .style(ButtonStyle::Success),
+        CreateButton::new(QueueAction::MoveNext.custom_id(version, index))
+            .label("다음 곡으로")
+            .emoji('⏫')
+            .style(ButtonStyle::Primary),
+        CreateButton::new(QueueAction::Remove.custom_id(version, index))
+            .label("제거")
+            .emoji('🗑')
+            .style(ButtonStyle::Danger),
+        CreateButton::new(QueueAction::Details.custom_id(version, index))
+            .label("상세 정보")
+            .emoji('ℹ')
+            .style(ButtonStyle::Secondary),
+    ])
+}
+
+fn queue_select_menu(upcoming: &[Song], version: u64) -> CreateActionRow {
     let count = upcoming.len().min(25);
     let options: Vec<CreateSelectMenuOption> = upcoming
         .iter()
@@ -78,7 +313,7 @@ fn queue_select_menu(upcoming: &[Song]) -> CreateActionRow {
                 Some(d) => format!("{}번째 · {d}", i + 1),
                 None => format!("{}번째", i + 1),
             };
-            CreateSelectMenuOption::new(label, format!("queue_{i}"))
+            CreateSelectMenuOption::new(label, queue_option_value(version, i))
                 .description(truncate_str(&desc, 100))
         })
         .collect();
@@ -98,16 +333,20 @@ fn queue_select_menu(upcoming: &[Song]) -> CreateActionRow {
     CreateActionRow::SelectMenu(menu)
 }
 
-pub fn music_components(is_paused: bool, upcoming: &[Song]) -> Vec<CreateActionRow> {
-    let mut rows = vec![music_buttons(is_paused)];
+pub fn music_components(
+    state: &ControllerState,
+    upcoming: &[Song],
+    version: u64,
+) -> Vec<CreateActionRow> {
+    let mut rows = vec![music_buttons(state), music_control_buttons(state)];
     if !upcoming.is_empty() {
-        rows.push(queue_select_menu(upcoming));
+        rows.push(queue_select_menu(upcoming, version));
     }
     rows
 }
 
 pub fn music_components_disabled() -> Vec<CreateActionRow> {
-    vec![music_buttons_disabled()]
+    vec![music_buttons_disabled(), music_control_buttons_disabled()]
 }
 
 // ── Tarkov components (PR #22) ──────────────────────────────────────────────
@@ -220,6 +459,111 @@ pub async fn await_component_interaction(
 mod tests {
     use super::*;
 
+    fn button_count(row: &CreateActionRow) -> usize {
+        match row {
+            CreateActionRow::Buttons(buttons) => buttons.len(),
+            _ => panic!("Expected Buttons action row"),
+        }
+    }
+
+    #[test]
+    fn test_music_components_rows() {
+        let state = ControllerState::default();
+        let rows = music_components(&state, &[], 0);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(button_count(&rows[0]), 5);
+        assert_eq!(button_count(&rows[1]), 5);
+
+        let upcoming = vec![Song::default()];
+        assert_eq!(music_components(&state, &upcoming, 0).len(), 3);
+        assert_eq!(music_components_disabled().len(), 2);
+    }
+
+    fn button_ids(row: &CreateActionRow) -> Vec<String> {
+        let row = serde_json::to_value(row).unwrap();
+        row["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|button| button["custom_id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    fn assert_unique_ids(row: &CreateActionRow) {
+        let mut ids = button_ids(row);
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(
+            ids.len(),
+            count,
+            "duplicate custom_id in {:?}",
+            button_ids(row)
+        );
+    }
+
+    #[test]
+    fn test_queue_page_row_ids() {
+        assert_eq!(button_count(&queue_page_row(1, 3)), 3);
+        assert_eq!(
+            button_ids(&queue_page_row(2, 3)),
+            [
+                "music_queue_prev_1",
+                "music_queue_page_indicator",
+                "music_queue_next_3"
+            ]
+        );
+        assert_eq!(parse_queue_page("music_queue_prev_2"), Some(2));
+        assert_eq!(parse_queue_page("music_queue_next_3"), Some(3));
+        assert_eq!(parse_queue_page("music_queue_page_indicator"), None);
+        assert_eq!(parse_queue_page("music_queue_view"), None);
+    }
+
+    #[test]
+    fn test_queue_page_row_single_page_ids_unique() {
+        assert_unique_ids(&queue_page_row(1, 3));
+        assert_unique_ids(&queue_page_row(1, 1));
+        // Empty queue: still one page
+        assert_unique_ids(&queue_page_row(1, 0));
+    }
+
+    #[test]
+    fn test_equalizer_row_ids() {
+        let rock = EqBands::preset("rock").unwrap();
+        assert_eq!(button_count(&equalizer_row(&rock)), eq::PRESETS.len());
+        assert_eq!(parse_eq_preset("music_eq_preset_rock"), Some("rock"));
+        assert_eq!(parse_eq_preset("music_eq_reset"), Some("flat"));
+        assert_eq!(parse_eq_preset("music_volume_up"), None);
+    }
+
+    #[test]
+    fn test_parse_queue_option() {
+        assert_eq!(parse_queue_option(&queue_option_value(7, 3)), Some((7, 3)));
+        assert_eq!(parse_queue_option("queue_3"), None);
+        assert_eq!(parse_queue_option("queue_x_1"), None);
+        assert_eq!(parse_queue_option("other_1_2"), None);
+    }
+
+    #[test]
+    fn test_queue_action_custom_id_roundtrip() {
+        for action in QueueAction::ALL {
+            let id = action.custom_id(42, 5);
+            assert!(id.starts_with("music_qa_"));
+            assert_eq!(QueueAction::parse(&id), Some((action, 42, 5)));
+        }
+        assert_eq!(QueueAction::parse("music_qa_fly_1_2"), None);
+        assert_eq!(QueueAction::parse("music_qa_jump_1"), None);
+        assert_eq!(QueueAction::parse("music_skip"), None);
+    }
+
+    #[test]
+    fn test_queue_action_row_has_four_buttons() {
+        match &queue_action_row(1, 0) {
+            CreateActionRow::Buttons(buttons) => assert_eq!(buttons.len(), 4),
+            _ => panic!("Expected Buttons action row"),
+        }
+    }
+
     #[test]
     fn test_pagination_row_first_page() {
         let row = pagination_row("ammo", 0, 5);
@@ -1,54 +1,148 @@
 use serenity::builder::CreateEmbed;
+use serenity::model::id::UserId;
 
+use crate::music::eq::{self, EqBands};
+use crate::music::lyrics::{self, Lyrics};
+use crate::music::policy::{self, EnqueuePolicy};
+use crate::music::radio::RadioPreset;
+use crate::music::stats::{RankedEntry, StatsSummary};
 use crate::music::Song;
 
+const LIVE_BADGE: &str = "🔴 LIVE";
+
+/// 숫자를 쉼표로 구분된 문자열로 변환
+fn format_count(n: u64) -> String {
+    let digits = n.to_string();
+    let mut result = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            result.push(',');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// 큐 목록 한 줄에 붙는 길이 표시 (라이브는 배지)
+fn duration_suffix(song: &Song) -> String {
+    if song.is_live {
+        format!(" `{LIVE_BADGE}`")
+    } else {
+        song.duration
+            .as_ref()
+            .map_or(String::new(), |d| format!(" `{d}`"))
+    }
+}
+
+/// 큐 목록 한 줄에 붙는 업로더 표시
+fn uploader_suffix(song: &Song) -> String {
+    song.uploader
+        .as_ref()
+        .map_or(String::new(), |uploader| format!(" · {uploader}"))
+}
+
+/// 0.0 ~ 1.0 볼륨을 `50%` 형식으로 표시
+pub fn format_volume(volume: f32) -> String {
+    format!("{}%", (volume * 100.0).round() as u32)
+}
+
 pub fn now_playing(song: &Song) -> CreateEmbed {
+    let title = if song.is_radio {
+        "📻 라디오 재생 중".to_string()
+    } else if song.is_live {
+        format!("{LIVE_BADGE} · 현재 재생 중")
+    } else {
+        "🎵 현재 재생 중".to_string()
+    };
+
     let mut embed = CreateEmbed::new()
-        .title("🎵 현재 재생 중")
+        .title(title)
         .description(format!("[{}]({})", song.title, song.url))
         .color(0x1DB954);
 
-    if let Some(ref dur) = song.duration {
+    if let Some(ref thumbnail) = song.thumbnail {
+        embed = embed.thumbnail(thumbnail);
+    }
+
+    if let Some(ref stream_title) = song.stream_title {
+        embed = embed.field("방송 중인 곡", stream_title, false);
+    }
+
+    if let Some(ref uploader) = song.uploader {
+        embed = embed.field("업로더", uploader, true);
+    }
+
+    if song.is_live {
+        embed = embed.field("길이", LIVE_BADGE, true);
+    } else if let Some(ref dur) = song.duration {
         embed = embed.field("길이", dur, true);
     }
 
-    embed = embed.field("요청", &song.requester, true);
+    embed = embed.field("요청", song.requester_mention(), true);
+
+    if let Some(views) = song.view_count {
+        embed = embed.field("조회수", format!("{}회", format_count(views)), true);
+    }
+
+    if let Some(ref date) = song.upload_date {
+        embed = embed.field("업로드", date, true);
+    }
+
     embed
 }
 
+/// 대기열에서 선택한 곡의 상세 정보
+pub fn queue_entry(song: &Song, position: usize) -> CreateEmbed {
+    now_playing(song)
+        .title(format!("📋 대기열 #{position}"))
+        .color(0x5865F2)
+}
+
 pub fn added_to_queue(song: &Song, position: usize) -> CreateEmbed {
     let mut embed = CreateEmbed::new()
         .title("✅ 큐에 추가됨")
         .description(format!("[{}]({})", song.title, song.url))
         .color(0x5865F2);
 
-    if let Some(ref dur) = song.duration {
+    if let Some(ref thumbnail) = song.thumbnail {
+        embed = embed.thumbnail(thumbnail);
+    }
+
+    if let Some(ref uploader) = song.uploader {
+        embed = embed.field("업로더", uploader, true);
+    }
+
+    if song.is_live {
+        embed = embed.field("길이", LIVE_BADGE, true);
+    } else if let Some(ref dur) = song.duration {
         embed = embed.field("길이", dur, true);
     }
 
     embed = embed.field("위치", format!("#{position}"), true);
-    embed
+    embed.field("요청", song.requester_mention(), true)
+}
+
+const QUEUE_PAGE_SIZE: usize = 10;
+
+/// 대기열 목록의 전체 페이지 수 (빈 목록도 1페이지)
+pub fn queue_page_count(len: usize) -> usize {
+    len.div_ceil(QUEUE_PAGE_SIZE).max(1)
 }
 
 pub fn queue_list(current: Option<&Song>, songs: &[Song], page: usize) -> CreateEmbed {
-    let per_page = 10;
-    let total_pages = if songs.is_empty() {
-        1
-    } else {
-        songs.len().div_ceil(per_page)
-    };
+    let per_page = QUEUE_PAGE_SIZE;
+    let total_pages = queue_page_count(songs.len());
     let page = page.min(total_pages).max(1);
 
     let mut description = String::new();
 
     if let Some(song) = current {
         description.push_str(&format!(
-            "**현재 재생:** [{}]({}){}\n\n",
+            "**현재 재생:** [{}]({}){}{}\n\n",
             song.title,
             song.url,
-            song.duration
-                .as_ref()
-                .map_or(String::new(), |d| format!(" `{d}`"))
+            duration_suffix(song),
+            uploader_suffix(song)
         ));
     }
 
@@ -60,22 +154,447 @@ pub fn queue_list(current: Option<&Song>, songs: &[Song], page: usize) -> Create
 
         for (i, song) in songs[start..end].iter().enumerate() {
             let num = start + i + 1;
-            let dur = song
-                .duration
-                .as_ref()
-                .map_or(String::new(), |d| format!(" `{d}`"));
-            description.push_str(&format!("**{num}.** [{}]({}){dur}\n", song.title, song.url));
+            let dur = duration_suffix(song);
+            let uploader = uploader_suffix(song);
+            description.push_str(&format!(
+                "**{num}.** [{}]({}){dur}{uploader} · {}\n",
+                song.title,
+                song.url,
+                song.requester_mention()
+            ));
         }
     }
 
-    CreateEmbed::new()
+    let mut embed = CreateEmbed::new()
         .title(format!("📋 재생 목록 ({page}/{total_pages})"))
         .description(description)
         .color(0x5865F2)
         .footer(serenity::builder::CreateEmbedFooter::new(format!(
             "총 {} 곡",
             songs.len()
+        )));
+
+    if let Some(thumbnail) = current.and_then(|song| song.thumbnail.as_ref()) {
+        embed = embed.thumbnail(thumbnail);
+    }
+
+    embed
+}
+
+/// 내가 신청한 대기 곡 (`(대기열 위치, 곡)`)
+pub fn my_queue(songs: &[(usize, Song)]) -> CreateEmbed {
+    let description = if songs.is_empty() {
+        "대기열에 신청한 곡이 없습니다.".to_string()
+    } else {
+        let mut lines: Vec<String> = songs
+            .iter()
+            .take(QUEUE_PAGE_SIZE * 2)
+            .map(|(position, song)| {
+                format!(
+                    "**#{position}** [{}]({}){}",
+                    song.title,
+                    song.url,
+                    duration_suffix(song)
+                )
+            })
+            .collect();
+        if songs.len() > QUEUE_PAGE_SIZE * 2 {
+            lines.push(format!("… 외 {}곡", songs.len() - QUEUE_PAGE_SIZE * 2));
+        }
+        lines.join("\n")
+    };
+
+    CreateEmbed::new()
+        .title("🙋 내 신청곡")
+        .description(description)
+        .color(0x5865F2)
+        .footer(serenity::builder::CreateEmbedFooter::new(format!(
+            "총 {} 곡 · /remove <번호>로 언제든 뺄 수 있습니다",
+            songs.len()
+        )))
+}
+
+/// 사용자의 즐겨찾기 목록 (페이지는 1부터)
+pub fn favorites_list(songs: &[Song], page: usize) -> CreateEmbed {
+    let total_pages = queue_page_count(songs.len());
+    let page = page.min(total_pages).max(1);
+
+    let description = if songs.is_empty() {
+        "즐겨찾기가 비어있습니다. 재생 중인 곡의 ❤️ 버튼으로 추가하세요.".to_string()
+    } else {
+        let start = (page - 1) * QUEUE_PAGE_SIZE;
+        songs
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(QUEUE_PAGE_SIZE)
+            .map(|(i, song)| {
+                format!(
+                    "**{}.** [{}]({}){}",
+                    i + 1,
+                    song.title,
+                    song.url,
+                    duration_suffix(song)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    CreateEmbed::new()
+        .title(format!("❤️ 즐겨찾기 ({page}/{total_pages})"))
+        .description(description)
+        .color(0xED4245)
+        .footer(serenity::builder::CreateEmbedFooter::new(format!(
+            "총 {} 곡",
+            songs.len()
+        )))
+}
+
+/// 퀴즈 점수판 (상위 10명)
+fn quiz_ranking(ranking: &[(UserId, u32)]) -> String {
+    if ranking.is_empty() {
+        return "아직 점수를 얻은 사람이 없습니다.".to_string();
+    }
+    ranking
+        .iter()
+        .take(10)
+        .enumerate()
+        .map(|(i, (user_id, score))| {
+            let place = match i {
+                0 => "🥇".to_string(),
+                1 => "🥈".to_string(),
+                2 => "🥉".to_string(),
+                _ => format!("**{}.**", i + 1),
+            };
+            format!("{place} <@{user_id}> — {score}점")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 한 라운드가 끝난 뒤 정답 공개
+pub fn quiz_reveal(
+    song: &Song,
+    winner: Option<(UserId, u32)>,
+    ranking: &[(UserId, u32)],
+) -> CreateEmbed {
+    let result = match winner {
+        Some((user_id, points)) => format!("✅ <@{user_id}> 정답! **+{points}점**"),
+        None => "⏰ 아무도 맞히지 못했습니다.".to_string(),
+    };
+    let mut embed = CreateEmbed::new()
+        .title("🎶 정답 공개")
+        .description(format!("[{}]({})\n\n{result}", song.title, song.url))
+        .field("점수판", quiz_ranking(ranking), false)
+        .color(if winner.is_some() { 0x57F287 } else { 0xFEE75C });
+    if let Some(ref thumbnail) = song.thumbnail {
+        embed = embed.thumbnail(thumbnail);
+    }
+    embed
+}
+
+/// 게임이 끝난 뒤 최종 순위
+pub fn quiz_final(ranking: &[(UserId, u32)], rounds_played: usize) -> CreateEmbed {
+    let winner = match ranking.first() {
+        Some((user_id, score)) => format!("🏆 우승: <@{user_id}> ({score}점)"),
+        None => "아무도 점수를 얻지 못했습니다.".to_string(),
+    };
+    CreateEmbed::new()
+        .title("🏁 퀴즈 종료")
+        .description(winner)
+        .field("최종 순위", quiz_ranking(ranking), false)
+        .footer(serenity::builder::CreateEmbedFooter::new(format!(
+            "{rounds_played}라운드 진행"
+        )))
+        .color(0x5865F2)
+}
+
+/// 가사 한 페이지
+pub fn lyrics_page(song: &Song, lyrics: &Lyrics, page_text: &str) -> CreateEmbed {
+    let mut footer = format!("출처: {}", lyrics.source);
+    if lyrics.is_synced() {
+        footer.push_str(" · 🎤 싱크 가사 지원");
+    }
+    let mut embed = CreateEmbed::new()
+        .title(format!("📝 {}", song.title))
+        .description(page_text)
+        .color(0x1DB954)
+        .footer(serenity::builder::CreateEmbedFooter::new(footer));
+    // 검색어로 찾은 가사는 링크가 없음
+    if !song.url.is_empty() {
+        embed = embed.url(&song.url);
+    }
+    embed
+}
+
+/// 재생 위치에 맞춰 현재 줄을 강조한 싱크 가사
+pub fn lyrics_synced(song: &Song, lyrics: &Lyrics, position_secs: f64) -> CreateEmbed {
+    let current = lyrics::current_line(&lyrics.synced, position_secs);
+    let window = lyrics::synced_window(lyrics.synced.len(), current, 3, 5);
+
+    let description = lyrics.synced[window.clone()]
+        .iter()
+        .zip(window)
+        .map(|(line, i)| {
+            let text = if line.text.is_empty() {
+                "♪"
+            } else {
+                &line.text
+            };
+            if Some(i) == current {
+                format!("▶ **{text}**")
+            } else {
+                text.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    CreateEmbed::new()
+        .title(format!("🎤 {}", song.title))
+        .url(&song.url)
+        .description(description)
+        .color(0x1DB954)
+        .footer(serenity::builder::CreateEmbedFooter::new(format!(
+            "싱크 모드 · {} · 출처: {}",
+            crate::music::chapters::format_timestamp(position_secs),
+            lyrics.source
+        )))
+}
+
+/// 음악 통계 순위표 한 페이지 크기
+pub const STATS_PAGE_SIZE: usize = 10;
+
+/// 순위표 필드가 1024자를 넘지 않도록 곡 제목을 자르는 길이
+const STATS_LABEL_CHARS: usize = 35;
+
+const WEEKDAYS: [&str; 7] = ["일", "월", "화", "수", "목", "금", "토"];
+
+/// 누적 재생 시간을 `12시간 5분` 형식으로 표시
+pub fn format_listened(listened: std::time::Duration) -> String {
+    let minutes = listened.as_secs() / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{m}분"),
+        (h, 0) => format!("{}시간", format_count(h)),
+        (h, m) => format!("{}시간 {m}분", format_count(h)),
+    }
+}
+
+/// `/stats music` 요약과 순위표 한 페이지
+pub fn music_stats(
+    period: &str,
+    summary: &StatsSummary,
+    board_title: &str,
+    entries: &[RankedEntry],
+    page: usize,
+    total_pages: usize,
+) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title(format!("📊 음악 통계 · {period}"))
+        .color(0x5865F2);
+
+    if summary.plays == 0 {
+        return embed.description("아직 재생 기록이 없습니다.");
+    }
+
+    let skip_rate = summary.skips as f64 / summary.plays as f64 * 100.0;
+    embed = embed
+        .field("재생", format!("{}곡", format_count(summary.plays)), true)
+        .field("재생 시간", format_listened(summary.listened), true)
+        .field("스킵", format!("{skip_rate:.0}%"), true);
+    if let Some((hour, plays)) = summary.busiest_hour {
+        embed = embed.field("가장 많이 듣는 시간", format!("{hour}시 ({plays}곡)"), true);
+    }
+    if let Some((day, plays)) = summary.busiest_weekday {
+        let day = WEEKDAYS.get(day as usize).copied().unwrap_or("?");
+        embed = embed.field(
+            "가장 많이 듣는 요일",
+            format!("{day}요일 ({plays}곡)"),
+            true,
+        );
+    }
+
+    let offset = page * STATS_PAGE_SIZE;
+    let lines: Vec<String> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let mut label: String = entry.label.chars().take(STATS_LABEL_CHARS).collect();
+            if label.len() < entry.label.len() {
+                label.push('…');
+            }
+            let name = match &entry.url {
+                Some(url) => format!("[{label}]({url})"),
+                None => label,
+            };
+            format!("**{}.** {name} — {}회", offset + i + 1, entry.plays)
+        })
+        .collect();
+
+    embed
+        .field(
+            format!("{board_title} ({}/{})", page + 1, total_pages.max(1)),
+            if lines.is_empty() {
+                "기록이 없습니다.".to_string()
+            } else {
+                lines.join("\n")
+            },
+            false,
+        )
+        .footer(serenity::builder::CreateEmbedFooter::new(
+            "시간대는 한국 시간(KST) 기준",
+        ))
+}
+
+/// 게인 한 칸 = 2dB, 가운데 선을 기준으로 양쪽 6칸
+fn eq_bar(gain: f32) -> String {
+    let cells = (gain.abs() / 2.0).round() as usize;
+    let (left, right) = if gain < 0.0 { (cells, 0) } else { (0, cells) };
+    format!(
+        "{}{}┃{}{}",
+        " ".repeat(6 - left),
+        "■".repeat(left),
+        "■".repeat(right),
+        " ".repeat(6 - right)
+    )
+}
+
+/// 밴드별 EQ 설정
+pub fn equalizer(bands: &EqBands) -> CreateEmbed {
+    let rows = eq::BAND_LABELS
+        .iter()
+        .zip(bands.gains)
+        .map(|(label, gain)| format!("{label:>4} {} {gain:+5.1}dB", eq_bar(gain)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let preset = match bands.preset_name() {
+        Some("flat") => "끔 (flat)".to_string(),
+        Some(name) => format!("프리셋: **{name}**"),
+        None => "사용자 설정".to_string(),
+    };
+    CreateEmbed::new()
+        .title("🎚️ 이퀄라이저")
+        .description(format!("{preset}\n```\n{rows}\n```"))
+        .footer(serenity::builder::CreateEmbedFooter::new(format!(
+            "/eq set <밴드> <게인>  ·  게인 범위 ±{}dB",
+            eq::MAX_GAIN_DB
         )))
+        .color(0x5865F2)
+}
+
+/// 목록 가져오기 결과에 실패한 줄을 보여줄 최대 개수
+const IMPORT_FAILURE_LIMIT: usize = 15;
+
+/// 목록 가져오기 진행 상황
+pub fn import_progress(done: usize, total: usize) -> CreateEmbed {
+    let filled = (done * 10).checked_div(total).unwrap_or(10);
+    CreateEmbed::new()
+        .title("📥 노래 목록 가져오는 중")
+        .description(format!(
+            "{}{} {done}/{total}",
+            "■".repeat(filled),
+            "□".repeat(10 - filled)
+        ))
+        .color(0x5865F2)
+}
+
+/// 목록 가져오기 결과. `failed`는 (목록에서의 순번, 검색어, 이유)
+pub fn import_result(
+    added: usize,
+    total: usize,
+    failed: &[(usize, String, String)],
+) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title("📥 노래 목록 가져오기 완료")
+        .description(format!(
+            "{total}줄 중 **{added}곡**을 대기열에 추가했습니다."
+        ))
+        .color(if failed.is_empty() {
+            0x1DB954
+        } else {
+            0xFEE75C
+        });
+
+    if !failed.is_empty() {
+        let mut lines: Vec<String> = failed
+            .iter()
+            .take(IMPORT_FAILURE_LIMIT)
+            .map(|(line, query, reason)| format!("`#{line}` {query} — {reason}"))
+            .collect();
+        if failed.len() > IMPORT_FAILURE_LIMIT {
+            lines.push(format!("외 {}줄", failed.len() - IMPORT_FAILURE_LIMIT));
+        }
+        let mut value = lines.join("\n");
+        if value.chars().count() > 1024 {
+            value = value.chars().take(1021).collect::<String>() + "...";
+        }
+        embed = embed.field(
+            format!("❌ 추가하지 못한 줄 ({})", failed.len()),
+            value,
+            false,
+        );
+    }
+    embed
+}
+
+/// 서버의 라디오 프리셋 목록
+pub fn radio_presets(presets: &[RadioPreset]) -> CreateEmbed {
+    let description = if presets.is_empty() {
+        "저장된 방송국이 없습니다. `/radio add`로 추가해주세요.".to_string()
+    } else {
+        presets
+            .iter()
+            .map(|preset| format!("**{}** — {}", preset.name, preset.url))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    CreateEmbed::new()
+        .title("📻 라디오 방송국")
+        .description(description)
+        .footer(serenity::builder::CreateEmbedFooter::new(
+            "/radio play <이름>  ·  끌 때는 /radio stop",
+        ))
+        .color(0x5865F2)
+}
+
+/// 중복 정책이 경고일 때 추가 안내에 붙이는 필드
+pub fn duplicate_warning(embed: CreateEmbed, position: usize) -> CreateEmbed {
+    let where_ = if position == 0 {
+        "지금 재생 중인 곡".to_string()
+    } else {
+        format!("대기열 #{position}")
+    };
+    embed.field("⚠️ 중복", format!("{where_}과(와) 같은 곡입니다."), false)
+}
+
+/// 서버의 곡 추가 정책
+pub fn enqueue_policy(policy: &EnqueuePolicy) -> CreateEmbed {
+    let max_length = policy
+        .max_duration_secs
+        .map_or("제한 없음".to_string(), policy::format_limit);
+    let live = if policy.allow_live {
+        "허용"
+    } else {
+        "거부"
+    };
+    let blocklist = if policy.blocklist.is_empty() {
+        "없음".to_string()
+    } else {
+        policy
+            .blocklist
+            .iter()
+            .map(|pattern| format!("`{pattern}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    CreateEmbed::new()
+        .title("🛡️ 곡 추가 정책")
+        .field("중복", policy.duplicates.to_string(), true)
+        .field("최대 길이", max_length, true)
+        .field("라이브", live, true)
+        .field("차단 목록", blocklist, false)
+        .color(0x5865F2)
 }
 
 pub fn error(message: &str) -> CreateEmbed {
@@ -89,12 +608,56 @@ pub fn error(message: &str) -> CreateEmbed {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_listened() {
+        use std::time::Duration;
+        assert_eq!(format_listened(Duration::from_secs(59)), "0분");
+        assert_eq!(format_listened(Duration::from_secs(45 * 60)), "45분");
+        assert_eq!(format_listened(Duration::from_secs(2 * 3600)), "2시간");
+        assert_eq!(
+            format_listened(Duration::from_secs(1234 * 3600 + 300)),
+            "1,234시간 5분"
+        );
+    }
+
+    #[test]
+    fn test_eq_bar() {
+        assert_eq!(eq_bar(0.0), "      ┃      ");
+        assert_eq!(eq_bar(4.0), "      ┃■■    ");
+        assert_eq!(eq_bar(-12.0), "■■■■■■┃      ");
+    }
+
+    #[test]
+    fn test_import_embeds() {
+        let _ = import_progress(0, 0);
+        let _ = import_progress(3, 7);
+        let failed: Vec<_> = (1..=20)
+            .map(|i| {
+                (
+                    i,
+                    format!("query {i}"),
+                    "노래를 찾을 수 없습니다".to_string(),
+                )
+            })
+            .collect();
+        let _ = import_result(5, 25, &failed);
+        // CreateEmbed is opaque; just verify creation doesn't panic
+    }
+
+    #[test]
+    fn test_format_volume() {
+        assert_eq!(format_volume(0.5), "50%");
+        assert_eq!(format_volume(0.299), "30%");
+        assert_eq!(format_volume(0.0), "0%");
+    }
+
     fn test_song(title: &str) -> crate::music::Song {
         crate::music::Song {
             title: title.to_string(),
             url: format!("https://example.com/{title}"),
             duration: Some("3:00".to_string()),
             requester: "tester".to_string(),
+            ..Default::default()
         }
     }
 
@@ -127,6 +690,15 @@ mod tests {
         let _embed = queue_list(Some(&current), &songs, 1);
     }
 
+    #[test]
+    fn test_my_queue_creates_embed() {
+        let _embed = my_queue(&[]);
+        let songs: Vec<_> = (1..=25)
+            .map(|i| (i * 2, test_song(&format!("Mine {i}"))))
+            .collect();
+        let _embed = my_queue(&songs);
+    }
+
     #[test]
     fn test_queue_list_pagination() {
         let songs: Vec<crate::music::Song> =
@@ -135,6 +707,56 @@ mod tests {
         let _embed = queue_list(None, &songs, 2);
     }
 
+    #[test]
+    fn test_now_playing_with_metadata() {
+        let song = crate::music::Song {
+            thumbnail: Some("https://i.ytimg.com/vi/abc/hq.jpg".to_string()),
+            uploader: Some("Artist".to_string()),
+            upload_date: Some("2024-01-31".to_string()),
+            view_count: Some(1_234_567),
+            ..test_song("Rich Song")
+        };
+        let _embed = now_playing(&song);
+        let _embed = added_to_queue(&song, 1);
+        let _embed = queue_entry(&song, 2);
+        let _embed = queue_list(Some(&song), std::slice::from_ref(&song), 1);
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1000), "1,000");
+        assert_eq!(format_count(1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn test_duration_suffix_live_badge() {
+        let live = crate::music::Song {
+            is_live: true,
+            ..test_song("Live")
+        };
+        assert_eq!(duration_suffix(&live), " `🔴 LIVE`");
+        assert_eq!(duration_suffix(&test_song("Normal")), " `3:00`");
+    }
+
+    #[test]
+    fn test_uploader_suffix() {
+        let song = crate::music::Song {
+            uploader: Some("Artist".to_string()),
+            ..test_song("With Uploader")
+        };
+        assert_eq!(uploader_suffix(&song), " · Artist");
+        assert_eq!(uploader_suffix(&test_song("Anonymous")), "");
+    }
+
+    #[test]
+    fn test_queue_page_count() {
+        assert_eq!(queue_page_count(0), 1);
+        assert_eq!(queue_page_count(10), 1);
+        assert_eq!(queue_page_count(11), 2);
+    }
+
     #[test]
     fn test_error_embed_creates() {
         let _embed = error("something went wrong");
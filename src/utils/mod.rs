@@ -1,2 +1,4 @@
 pub mod components;
 pub mod embed;
+pub mod ratelimit;
+pub mod voice;
@@ -0,0 +1,384 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serenity::model::id::{GuildId, UserId};
+
+/// AI 채팅(멘션/이미지) 경로의 버킷 이름
+pub const AI_BUCKET: &str = "ai";
+/// 만료된 기록을 정리하는 간격
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `per` 동안 최대 `max`회 (max가 1이면 단순 쿨다운)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Limit {
+    pub max: u32,
+    pub per: Duration,
+}
+
+impl Limit {
+    pub fn new(max: u32, per: Duration) -> Self {
+        Self { max, per }
+    }
+
+    /// `3/30` 형식 (30초에 3회)
+    fn parse(input: &str) -> Option<Self> {
+        let (max, secs) = input.trim().split_once('/')?;
+        let max: u32 = max.trim().parse().ok().filter(|&m| m > 0)?;
+        let secs: u64 = secs.trim().trim_end_matches('s').parse().ok()?;
+        Some(Self::new(max, Duration::from_secs(secs)))
+    }
+}
+
+/// 한 버킷의 사용자별/서버별 제한
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RateLimit {
+    pub per_user: Option<Limit>,
+    pub per_guild: Option<Limit>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Scope {
+    User(UserId),
+    Guild(GuildId),
+}
+
+/// 범위별 최근 사용 시각
+struct Hits {
+    windows: HashMap<(String, Scope), VecDeque<Instant>>,
+    /// 마지막으로 만료된 기록을 정리한 시각
+    pruned_at: Instant,
+}
+
+/// 명령어별로 설정할 수 있는 사용자/서버 단위 쿨다운
+///
+/// 여러 명령어(예: `/play`와 `/p`)가 한 버킷을 공유할 수 있습니다.
+pub struct RateLimiter {
+    buckets: HashMap<String, RateLimit>,
+    commands: HashMap<String, String>,
+    hits: Mutex<Hits>,
+}
+
+impl Default for RateLimiter {
+    /// 외부 프로세스나 API를 호출하는 명령어의 기본 제한
+    fn default() -> Self {
+        Self::new()
+            .bucket(
+                "play",
+                &["play", "p"],
+                RateLimit {
+                    per_user: Some(Limit::new(3, Duration::from_secs(15))),
+                    per_guild: Some(Limit::new(10, Duration::from_secs(30))),
+                },
+            )
+            .bucket(
+                AI_BUCKET,
+                &[],
+                RateLimit {
+                    per_user: Some(Limit::new(1, Duration::from_secs(10))),
+                    per_guild: Some(Limit::new(10, Duration::from_secs(60))),
+                },
+            )
+    }
+}
+
+impl RateLimiter {
+    /// 제한이 없는 빈 설정
+    pub fn new() -> Self {
+        Self {
+            buckets: HashMap::new(),
+            commands: HashMap::new(),
+            hits: Mutex::new(Hits {
+                windows: HashMap::new(),
+                pruned_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// 버킷을 추가하거나 바꿉니다. `commands`의 명령어들은 이 버킷을 함께 사용합니다.
+    pub fn bucket(mut self, name: &str, commands: &[&str], limit: RateLimit) -> Self {
+        for command in commands {
+            self.commands.insert(command.to_string(), name.to_string());
+        }
+        self.buckets.insert(name.to_string(), limit);
+        self
+    }
+
+    /// `play:user=3/15,guild=10/30;ai:user=1/10` 형식의 설정으로 기존 버킷을 덮어씁니다.
+    ///
+    /// 잘못된 항목은 무시하고 경고를 남깁니다. `0`이나 `off`는 해당 제한을 끕니다.
+    pub fn with_overrides(mut self, spec: &str) -> Self {
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let Some((name, limits)) = entry.split_once(':') else {
+                tracing::warn!("쿨다운 설정 무시: {entry}");
+                continue;
+            };
+            let name = name.trim();
+            let mut limit = self.buckets.get(name).copied().unwrap_or_default();
+
+            for part in limits.split(',') {
+                let Some((scope, value)) = part.split_once('=') else {
+                    tracing::warn!("쿨다운 설정 무시: {part}");
+                    continue;
+                };
+                let value = match value.trim() {
+                    "0" | "off" => None,
+                    v => match Limit::parse(v) {
+                        Some(l) => Some(l),
+                        None => {
+                            tracing::warn!("쿨다운 설정 무시: {part}");
+                            continue;
+                        }
+                    },
+                };
+                match scope.trim() {
+                    "user" => limit.per_user = value,
+                    "guild" => limit.per_guild = value,
+                    other => tracing::warn!("알 수 없는 쿨다운 범위: {other}"),
+                }
+            }
+
+            if !self.commands.values().any(|b| b == name) && name != AI_BUCKET {
+                // 버킷 이름과 같은 명령어에 바로 적용
+                self.commands.insert(name.to_string(), name.to_string());
+            }
+            self.buckets.insert(name.to_string(), limit);
+        }
+        self
+    }
+
+    /// 명령어가 속한 버킷 이름
+    pub fn bucket_for(&self, command: &str) -> Option<&str> {
+        self.commands.get(command).map(String::as_str)
+    }
+
+    /// 사용을 기록합니다. 제한에 걸리면 기록하지 않고 남은 대기 시간을 반환합니다.
+    pub fn check(
+        &self,
+        bucket: &str,
+        user_id: UserId,
+        guild_id: Option<GuildId>,
+    ) -> Result<(), Duration> {
+        self.check_at(bucket, user_id, guild_id, Instant::now())
+    }
+
+    pub(crate) fn check_at(
+        &self,
+        bucket: &str,
+        user_id: UserId,
+        guild_id: Option<GuildId>,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let Some(limit) = self.buckets.get(bucket) else {
+            return Ok(());
+        };
+
+        let mut scopes = Vec::with_capacity(2);
+        if let Some(l) = limit.per_user {
+            scopes.push((Scope::User(user_id), l));
+        }
+        if let (Some(l), Some(guild_id)) = (limit.per_guild, guild_id) {
+            scopes.push((Scope::Guild(guild_id), l));
+        }
+
+        let mut guard = self.hits.lock().unwrap();
+        if now.saturating_duration_since(guard.pruned_at) >= PRUNE_INTERVAL {
+            self.prune(&mut guard.windows, now);
+            guard.pruned_at = now;
+        }
+        let hits = &mut guard.windows;
+
+        // 모든 범위를 먼저 확인해 한쪽에서 막히면 어느 쪽에도 기록하지 않음
+        let mut wait = Duration::ZERO;
+        for (scope, l) in &scopes {
+            let window = hits.entry((bucket.to_string(), *scope)).or_default();
+            while window
+                .front()
+                .is_some_and(|&t| now.duration_since(t) >= l.per)
+            {
+                window.pop_front();
+            }
+            if window.len() as u32 >= l.max {
+                let oldest = window[window.len() - l.max as usize];
+                wait = wait.max(l.per - now.duration_since(oldest));
+            }
+        }
+        if !wait.is_zero() {
+            return Err(wait);
+        }
+
+        for (scope, _) in &scopes {
+            hits.entry((bucket.to_string(), *scope))
+                .or_default()
+                .push_back(now);
+        }
+        Ok(())
+    }
+
+    /// 제한 시간이 모두 지나 더 이상 쓸모없는 기록을 지웁니다 (한 번 쓰고 떠난 사용자 등)
+    fn prune(&self, windows: &mut HashMap<(String, Scope), VecDeque<Instant>>, now: Instant) {
+        windows.retain(|(bucket, scope), window| {
+            let limit = self.buckets.get(bucket).and_then(|l| match scope {
+                Scope::User(_) => l.per_user,
+                Scope::Guild(_) => l.per_guild,
+            });
+            limit.is_some_and(|l| {
+                window
+                    .back()
+                    .is_some_and(|&t| now.saturating_duration_since(t) < l.per)
+            })
+        });
+    }
+}
+
+/// 사용자에게 보여줄 남은 대기 시간
+pub fn cooldown_message(remaining: Duration) -> String {
+    let secs = remaining.as_secs_f64().ceil().max(1.0) as u64;
+    format!("⏳ 너무 자주 사용했습니다. **{secs}초** 후에 다시 시도해주세요.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER: UserId = UserId::new(1);
+    const OTHER: UserId = UserId::new(2);
+    const GUILD: GuildId = GuildId::new(10);
+
+    fn limiter(user: Option<Limit>, guild: Option<Limit>) -> RateLimiter {
+        RateLimiter::new().bucket(
+            "play",
+            &["play", "p"],
+            RateLimit {
+                per_user: user,
+                per_guild: guild,
+            },
+        )
+    }
+
+    #[test]
+    fn test_user_cooldown_and_remaining() {
+        let rl = limiter(Some(Limit::new(1, Duration::from_secs(10))), None);
+        let t0 = Instant::now();
+
+        assert!(rl.check_at("play", USER, Some(GUILD), t0).is_ok());
+        let wait = rl
+            .check_at("play", USER, Some(GUILD), t0 + Duration::from_secs(4))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(6));
+
+        // 다른 사용자는 영향 없음, 시간이 지나면 다시 허용
+        assert!(rl.check_at("play", OTHER, Some(GUILD), t0).is_ok());
+        assert!(rl
+            .check_at("play", USER, Some(GUILD), t0 + Duration::from_secs(10))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_sliding_window_allows_burst() {
+        let rl = limiter(Some(Limit::new(3, Duration::from_secs(30))), None);
+        let t0 = Instant::now();
+        for i in 0..3 {
+            assert!(rl
+                .check_at("play", USER, None, t0 + Duration::from_secs(i))
+                .is_ok());
+        }
+        let wait = rl
+            .check_at("play", USER, None, t0 + Duration::from_secs(5))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(25));
+    }
+
+    #[test]
+    fn test_guild_limit_shared_between_users() {
+        let rl = limiter(None, Some(Limit::new(2, Duration::from_secs(60))));
+        let t0 = Instant::now();
+        assert!(rl.check_at("play", USER, Some(GUILD), t0).is_ok());
+        assert!(rl.check_at("play", OTHER, Some(GUILD), t0).is_ok());
+        assert!(rl
+            .check_at("play", UserId::new(3), Some(GUILD), t0)
+            .is_err());
+        // DM 등 서버 밖에서는 서버 제한을 적용하지 않음
+        assert!(rl.check_at("play", UserId::new(3), None, t0).is_ok());
+    }
+
+    #[test]
+    fn test_blocked_call_is_not_recorded() {
+        let rl = limiter(
+            Some(Limit::new(5, Duration::from_secs(60))),
+            Some(Limit::new(1, Duration::from_secs(60))),
+        );
+        let t0 = Instant::now();
+        assert!(rl.check_at("play", OTHER, Some(GUILD), t0).is_ok());
+        // 서버 제한에 막힌 호출이 사용자 기록을 소모하지 않음
+        for _ in 0..10 {
+            assert!(rl.check_at("play", USER, Some(GUILD), t0).is_err());
+        }
+        assert!(rl.check_at("play", USER, None, t0).is_ok());
+    }
+
+    #[test]
+    fn test_aliases_share_bucket() {
+        let rl = limiter(None, None);
+        assert_eq!(rl.bucket_for("play"), Some("play"));
+        assert_eq!(rl.bucket_for("p"), Some("play"));
+        assert_eq!(rl.bucket_for("skip"), None);
+        // 설정되지 않은 버킷은 항상 허용
+        assert!(rl.check("unknown", USER, None).is_ok());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let rl = RateLimiter::default()
+            .with_overrides("play:user=5/60,guild=off; ai:user=2/20s; item:user=1/3; bad");
+        let t0 = Instant::now();
+
+        for _ in 0..5 {
+            assert!(rl.check_at("play", USER, Some(GUILD), t0).is_ok());
+        }
+        assert!(rl.check_at("play", USER, Some(GUILD), t0).is_err());
+        // 기존 별칭 유지
+        assert_eq!(rl.bucket_for("p"), Some("play"));
+
+        assert!(rl.check_at(AI_BUCKET, USER, None, t0).is_ok());
+        assert!(rl.check_at(AI_BUCKET, USER, None, t0).is_ok());
+        assert!(rl.check_at(AI_BUCKET, USER, None, t0).is_err());
+
+        assert_eq!(rl.bucket_for("item"), Some("item"));
+        assert!(rl.check_at("item", USER, None, t0).is_ok());
+        assert!(rl.check_at("item", USER, None, t0).is_err());
+    }
+
+    #[test]
+    fn test_expired_entries_are_pruned() {
+        let rl = limiter(
+            Some(Limit::new(1, Duration::from_secs(10))),
+            Some(Limit::new(5, Duration::from_secs(120))),
+        );
+        let t0 = Instant::now();
+        for user in 1..=50 {
+            assert!(rl.check_at("play", UserId::new(user), None, t0).is_ok());
+        }
+        assert!(rl.check_at("play", USER, Some(GUILD), t0).is_err());
+        assert!(rl.check_at("play", OTHER, Some(GUILD), t0).is_err());
+        assert!(rl
+            .check_at("play", UserId::new(51), Some(GUILD), t0)
+            .is_ok());
+        assert_eq!(rl.hits.lock().unwrap().windows.len(), 52);
+
+        // 사용자 기록은 만료되어 지워지고, 아직 유효한 서버 기록은 남음
+        let later = t0 + PRUNE_INTERVAL;
+        assert!(rl.check_at("play", UserId::new(99), None, later).is_ok());
+        let hits = rl.hits.lock().unwrap();
+        assert_eq!(hits.windows.len(), 2);
+        assert!(hits
+            .windows
+            .contains_key(&("play".to_string(), Scope::Guild(GUILD))));
+    }
+
+    #[test]
+    fn test_cooldown_message_rounds_up() {
+        assert!(cooldown_message(Duration::from_millis(2100)).contains("3초"));
+        assert!(cooldown_message(Duration::from_millis(10)).contains("1초"));
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use poise::serenity_prelude as serenity;
+use serenity::builder::EditVoiceState;
+use serenity::model::channel::{ChannelType, GuildChannel};
+use serenity::model::id::{ChannelId, GuildId, UserId};
+use songbird::Call;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::music::stage::{StageSpeak, StageTopic};
+use crate::music::{queue, QueueManager};
+use crate::Error;
+
+/// 사용자가 현재 접속해 있는 음성 채널 (캐시 기준)
+pub fn user_channel(
+    ctx: &serenity::Context,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Option<ChannelId> {
+    let guild = ctx.cache.guild(guild_id)?;
+    guild
+        .voice_states
+        .get(&user_id)
+        .and_then(|vs| vs.channel_id)
+}
+
+/// 권한이 없어 스테이지 채널에서 재생할 수 없을 때의 안내
+pub const STAGE_PERMISSION_ERROR: &str =
+    "스테이지 채널에서 말할 권한이 없습니다. 봇에 `멤버 음소거` 또는 `발언권 요청` 권한을 주세요.";
+
+/// 채널이 스테이지면 봇이 말할 수 있는 방법을 확인합니다 (일반 음성 채널이면 `None`)
+async fn stage_speak(
+    ctx: &serenity::Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<Option<(StageSpeak, GuildChannel)>, Error> {
+    let is_stage = ctx
+        .cache
+        .guild(guild_id)
+        .and_then(|g| g.channels.get(&channel_id).map(|c| c.kind))
+        == Some(ChannelType::Stage);
+    if !is_stage {
+        return Ok(None);
+    }
+
+    let bot_id = ctx.cache.current_user().id;
+    let member = guild_id.member(ctx, bot_id).await?;
+    let (channel, permissions) = {
+        let guild = ctx
+            .cache
+            .guild(guild_id)
+            .ok_or("서버 정보를 가져올 수 없습니다")?;
+        let channel = guild
+            .channels
+            .get(&channel_id)
+            .ok_or("채널 정보를 가져올 수 없습니다")?;
+        (channel.clone(), guild.user_permissions_in(channel, &member))
+    };
+
+    match StageSpeak::from_permissions(permissions) {
+        Some(speak) => Ok(Some((speak, channel))),
+        None => Err(STAGE_PERMISSION_ERROR.into()),
+    }
+}
+
+/// 음성 채널에 접속(또는 이동)하고 서버 큐의 채널 정보를 갱신합니다.
+///
+/// 이미 다른 채널에 있으면 연결을 유지한 채 옮기므로 재생 중인 곡은 계속 재생됩니다.
+/// 스테이지 채널이면 청중 상태를 해제(또는 발언권 요청)하고, 곡이 바뀔 때 주제를 갱신하도록 합니다.
+pub async fn join(
+    ctx: &serenity::Context,
+    queue_manager: &QueueManager,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> Result<Arc<Mutex<Call>>, Error> {
+    // 권한이 없으면 들어가도 아무도 들을 수 없으므로 접속 전에 확인
+    let speak = stage_speak(ctx, guild_id, channel_id).await?;
+
+    let manager = songbird::get(ctx).await.expect("Songbird 미등록");
+    let call = manager.join(guild_id, channel_id).await?;
+    queue::set_voice_channel(queue_manager, guild_id, Some(channel_id)).await;
+
+    let stage_topic = match speak {
+        Some((speak, channel)) => {
+            let builder = match speak {
+                StageSpeak::Unsuppress => EditVoiceState::new().suppress(false),
+                StageSpeak::RequestToSpeak => EditVoiceState::new().request_to_speak(true),
+            };
+            if let Err(e) = channel.edit_own_voice_state(ctx, builder).await {
+                warn!("스테이지 발언 설정 실패: {e}");
+            }
+            Some(StageTopic::new(ctx.http.clone(), channel_id))
+        }
+        None => None,
+    };
+    queue::set_stage_topic(queue_manager, guild_id, stage_topic).await;
+
+    Ok(call)
+}
+
+/// 사용자가 봇과 같은 음성 채널에 있는지 확인합니다.
+///
+/// 재생 제어 슬래시 커맨드와 컨트롤러 버튼이 같은 조건을 쓰도록 공유합니다.
+/// 실패 시 사용자에게 보여줄 메시지를 반환합니다.
+pub async fn check_same_channel(
+    ctx: &serenity::Context,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<(), &'static str> {
+    let manager = songbird::get(ctx).await.expect("Songbird 미등록");
+
+    let bot_channel = {
+        let handler_lock = manager.get(guild_id).ok_or("봇이 음성 채널에 없습니다.")?;
+        let handler = handler_lock.lock().await;
+        handler.current_channel()
+    };
+
+    let guild = ctx
+        .cache
+        .guild(guild_id)
+        .ok_or("서버 정보를 가져올 수 없습니다")?;
+
+    let user_in_bot_channel = match bot_channel {
+        Some(bot_ch) => guild
+            .voice_states
+            .get(&user_id)
+            .and_then(|vs| vs.channel_id)
+            .is_some_and(|ch| ch.get() == bot_ch.0.get()),
+        None => false,
+    };
+
+    if user_in_bot_channel {
+        Ok(())
+    } else {
+        Err("봇과 같은 음성 채널에 있어야 합니다.")
+    }
+}
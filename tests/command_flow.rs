@@ -1,5 +1,5 @@
 use every_discord_bot::music::{self, queue, LoopMode, Song};
-use serenity::model::id::GuildId;
+use serenity::model::id::{ChannelId, GuildId};
 
 fn test_song(n: u32) -> Song {
     Song {
@@ -7,6 +7,7 @@ fn test_song(n: u32) -> Song {
         url: format!("https://youtube.com/watch?v=test{n}"),
         duration: Some(format!("{n}:00")),
         requester: "user".to_string(),
+        ..Default::default()
     }
 }
 
@@ -21,9 +22,9 @@ async fn test_play_queue_skip_flow() {
     let gid = GuildId::new(1);
 
     // User plays 3 songs
-    queue::add_song(&qm, gid, test_song(1)).await;
-    queue::add_song(&qm, gid, test_song(2)).await;
-    queue::add_song(&qm, gid, test_song(3)).await;
+    queue::add_song(&qm, gid, test_song(1)).await.unwrap();
+    queue::add_song(&qm, gid, test_song(2)).await.unwrap();
+    queue::add_song(&qm, gid, test_song(3)).await.unwrap();
 
     // First song starts (get_next_song simulates play_next)
     let current = queue::get_next_song(&qm, gid, false).await;
@@ -59,8 +60,8 @@ async fn test_loop_song_flow() {
     let qm = music::new_queue_manager();
     let gid = GuildId::new(2);
 
-    queue::add_song(&qm, gid, test_song(1)).await;
-    queue::add_song(&qm, gid, test_song(2)).await;
+    queue::add_song(&qm, gid, test_song(1)).await.unwrap();
+    queue::add_song(&qm, gid, test_song(2)).await.unwrap();
 
     let current = queue::get_next_song(&qm, gid, false).await;
     assert_eq!(current.unwrap().title, "Song 1");
@@ -84,8 +85,8 @@ async fn test_loop_queue_flow() {
     let qm = music::new_queue_manager();
     let gid = GuildId::new(3);
 
-    queue::add_song(&qm, gid, test_song(1)).await;
-    queue::add_song(&qm, gid, test_song(2)).await;
+    queue::add_song(&qm, gid, test_song(1)).await.unwrap();
+    queue::add_song(&qm, gid, test_song(2)).await.unwrap();
 
     queue::set_loop_mode(&qm, gid, LoopMode::Queue).await;
 
@@ -107,7 +108,7 @@ async fn test_shuffle_remove_flow() {
     let gid = GuildId::new(4);
 
     for i in 1..=5 {
-        queue::add_song(&qm, gid, test_song(i)).await;
+        queue::add_song(&qm, gid, test_song(i)).await.unwrap();
     }
 
     // Start playing (pops first song)
@@ -148,8 +149,8 @@ async fn test_stop_clears_everything() {
     let qm = music::new_queue_manager();
     let gid = GuildId::new(6);
 
-    queue::add_song(&qm, gid, test_song(1)).await;
-    queue::add_song(&qm, gid, test_song(2)).await;
+    queue::add_song(&qm, gid, test_song(1)).await.unwrap();
+    queue::add_song(&qm, gid, test_song(2)).await.unwrap();
     queue::get_next_song(&qm, gid, false).await;
 
     // /stop
@@ -166,8 +167,8 @@ async fn test_multiple_guilds_isolated() {
     let g1 = GuildId::new(100);
     let g2 = GuildId::new(200);
 
-    queue::add_song(&qm, g1, test_song(1)).await;
-    queue::add_song(&qm, g2, test_song(2)).await;
+    queue::add_song(&qm, g1, test_song(1)).await.unwrap();
+    queue::add_song(&qm, g2, test_song(2)).await.unwrap();
 
     let s1 = queue::get_next_song(&qm, g1, false).await;
     assert_eq!(s1.unwrap().title, "Song 1");
@@ -189,3 +190,114 @@ async fn test_multiple_guilds_isolated() {
     assert!(queue::is_empty(&qm, g1).await);
     assert!(!queue::is_empty(&qm, g2).await);
 }
+
+#[tokio::test]
+async fn test_shuffle_unshuffle_flow() {
+    // /play 5 songs, /shuffle, /play song6, /unshuffle → original order
+    let qm = music::new_queue_manager();
+    let gid = GuildId::new(7);
+
+    for i in 1..=5 {
+        queue::add_song(&qm, gid, test_song(i)).await.unwrap();
+    }
+    queue::get_next_song(&qm, gid, false).await;
+
+    // /shuffle
+    assert_eq!(queue::shuffle(&qm, gid).await, 4);
+
+    // Song 3 is moved to the front and finishes while shuffled, then a new song is requested
+    let (_, songs) = queue::get_queue_list(&qm, gid).await;
+    let position = songs.iter().position(|s| s.title == "Song 3").unwrap() + 1;
+    queue::move_to(&qm, gid, position, 1).await;
+    assert_eq!(
+        queue::get_next_song(&qm, gid, false).await.unwrap().title,
+        "Song 3"
+    );
+    queue::add_song(&qm, gid, test_song(6)).await.unwrap();
+
+    // /unshuffle
+    assert!(queue::unshuffle(&qm, gid).await);
+
+    let (_, songs) = queue::get_queue_list(&qm, gid).await;
+    let titles: Vec<&str> = songs.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(titles, ["Song 2", "Song 4", "Song 5", "Song 6"]);
+}
+
+#[tokio::test]
+async fn test_queue_select_stale_version_flow() {
+    // Dropdown is rendered, then someone else changes the queue before the click
+    let qm = music::new_queue_manager();
+    let gid = GuildId::new(8);
+
+    for i in 1..=3 {
+        queue::add_song(&qm, gid, test_song(i)).await.unwrap();
+    }
+    let (_, rendered_version) = queue::get_upcoming(&qm, gid).await;
+
+    // Another user removes #1 → the rendered index now points elsewhere
+    queue::remove_at(&qm, gid, 1).await;
+    assert_ne!(queue::get_version(&qm, gid).await, rendered_version);
+
+    // A fresh render sees the new state and "move to next" works
+    let (upcoming, version) = queue::get_upcoming(&qm, gid).await;
+    assert_eq!(queue::get_version(&qm, gid).await, version);
+    assert_eq!(upcoming[1].title, "Song 3");
+    queue::move_to(&qm, gid, 2, 1).await;
+
+    let (_, songs) = queue::get_queue_list(&qm, gid).await;
+    assert_eq!(songs[0].title, "Song 3");
+}
+
+#[tokio::test]
+async fn test_previous_button_flow() {
+    // Song 1 → skip → Song 2, then ⏮️ brings Song 1 back and Song 2 is next again
+    let qm = music::new_queue_manager();
+    let gid = GuildId::new(9);
+
+    for i in 1..=3 {
+        queue::add_song(&qm, gid, test_song(i)).await.unwrap();
+    }
+    queue::get_next_song(&qm, gid, false).await;
+    assert!(!queue::has_previous(&qm, gid).await);
+
+    queue::get_next_song(&qm, gid, true).await;
+    assert!(queue::has_previous(&qm, gid).await);
+
+    let prev = queue::previous(&qm, gid).await.unwrap();
+    assert_eq!(prev.title, "Song 1");
+    assert_eq!(queue::get_current(&qm, gid).await.unwrap().title, "Song 1");
+
+    let (_, songs) = queue::get_queue_list(&qm, gid).await;
+    let titles: Vec<&str> = songs.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(titles, vec!["Song 2", "Song 3"]);
+    assert!(queue::previous(&qm, gid).await.is_none());
+}
+
+#[tokio::test]
+async fn test_leave_and_rejoin_flow() {
+    // /play 3 songs → /leave keeps the queue → /join resumes from the interrupted song
+    let qm = music::new_queue_manager();
+    let gid = GuildId::new(10);
+    let channel = ChannelId::new(100);
+
+    queue::set_voice_channel(&qm, gid, Some(channel)).await;
+    for i in 1..=3 {
+        queue::add_song(&qm, gid, test_song(i)).await.unwrap();
+    }
+    queue::get_next_song(&qm, gid, false).await;
+
+    // /leave
+    queue::detach(&qm, gid).await;
+    assert!(queue::get_voice_channel(&qm, gid).await.is_none());
+    assert!(queue::get_current(&qm, gid).await.is_none());
+    assert!(!queue::is_empty(&qm, gid).await);
+
+    // /join (play_next with skip flag so loop-song mode doesn't block the restart)
+    queue::set_voice_channel(&qm, gid, Some(channel)).await;
+    queue::set_loop_mode(&qm, gid, LoopMode::Song).await;
+    let resumed = queue::get_next_song(&qm, gid, true).await.unwrap();
+    assert_eq!(resumed.title, "Song 1");
+
+    let (_, songs) = queue::get_queue_list(&qm, gid).await;
+    assert_eq!(songs.len(), 2);
+}
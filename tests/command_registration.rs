@@ -7,8 +7,8 @@ fn test_all_commands_returns_correct_count() {
     let cmds = commands::all();
     assert_eq!(
         cmds.len(),
-        43,
-        "Expected 43 commands (1 help + 11 music + 11 aliases + 10 tarkov + 10 aliases), got {}",
+        76,
+        "Expected 76 commands (1 help + 1 stats + 27 music + 27 aliases + 10 tarkov + 10 aliases), got {}",
         cmds.len()
     );
 }
@@ -40,8 +40,41 @@ fn test_all_commands_contain_expected_names() {
         "sh",
         "remove",
         "rm",
+        "myqueue",
+        "myq",
         "volume",
         "v",
+        "equalizer",
+        "eq",
+        "chapter",
+        "ch",
+        "sponsorskip",
+        "sb",
+        "unshuffle",
+        "ush",
+        "undo",
+        "u",
+        "previous",
+        "prev",
+        "join",
+        "j",
+        "leave",
+        "dc",
+        "summon",
+        "sm",
+        "247",
+        "stay",
+        "queuepolicy",
+        "qp",
+        "favorites",
+        "fav",
+        "radio",
+        "rd",
+        "lyrics",
+        "ly",
+        "musicquiz",
+        "mq",
+        "stats",
         // Tarkov commands (Phase 3)
         "item",
         "아이템",
@@ -0,0 +1,270 @@
+//! End-to-end playback through `player` with a fake yt-dlp and an offline voice driver
+
+mod support;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use every_discord_bot::music::player::Services;
+use every_discord_bot::music::stats::PlayStats;
+use every_discord_bot::music::{self, player, queue, quiz, LoopMode, QueueManager, Song};
+use serenity::model::id::{ChannelId, GuildId};
+use support::{FakeYtDlp, OfflineDriver, Playback};
+use tokio::sync::{mpsc, Mutex};
+
+const WAIT: Duration = Duration::from_secs(10);
+
+struct Harness {
+    gid: GuildId,
+    qm: QueueManager,
+    services: Services,
+    call: Arc<Mutex<OfflineDriver>>,
+    events: mpsc::UnboundedReceiver<Playback>,
+}
+
+impl Harness {
+    /// Each test uses its own guild and song ids so they can share one fake yt-dlp
+    async fn new(guild: u64) -> Self {
+        let gid = GuildId::new(guild);
+        let qm = music::new_queue_manager();
+        // The end-of-track handler only advances while the bot is in a voice channel
+        queue::set_voice_channel(&qm, gid, Some(ChannelId::new(1))).await;
+        let (driver, events) = OfflineDriver::new(gid, qm.clone());
+        Self {
+            gid,
+            qm,
+            services: Services {
+                http_client: reqwest::Client::new(),
+                ytdlp: Arc::new(FakeYtDlp::global().runner(Duration::from_secs(10))),
+                stats: None,
+                quiz_games: Default::default(),
+            },
+            call: Arc::new(Mutex::new(driver)),
+            events,
+        }
+    }
+
+    /// Registers the song with the fake and enqueues it like /play does
+    async fn enqueue(&self, id: &str, title: &str, secs: f32) {
+        let url = FakeYtDlp::global().add_song(id, title, secs);
+        let mut song: Song = self.services.ytdlp.song_info(&url).await.unwrap();
+        song.requester = "tester".to_string();
+        queue::add_song(&self.qm, self.gid, song).await.unwrap();
+    }
+
+    async fn play_next(&self, skipped: bool) {
+        player::play_next(self.gid, &self.qm, &self.services, &self.call, skipped)
+            .await
+            .unwrap();
+    }
+
+    async fn next_event(&mut self) -> Playback {
+        tokio::time::timeout(WAIT, self.events.recv())
+            .await
+            .expect("timed out waiting for playback")
+            .expect("driver dropped")
+    }
+
+    /// Waits until the guild has nothing playing
+    async fn wait_idle(&self) {
+        tokio::time::timeout(WAIT, async {
+            while queue::get_track_handle(&self.qm, self.gid).await.is_some() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("queue never went idle");
+    }
+}
+
+fn started(title: &str) -> Playback {
+    Playback::Started(title.to_string())
+}
+
+fn finished(title: &str) -> Playback {
+    Playback::Finished(title.to_string())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_plays_queue_in_order_until_empty() {
+    let mut h = Harness::new(1).await;
+    h.enqueue("order-a", "Order A", 0.2).await;
+    h.enqueue("order-b", "Order B", 0.2).await;
+
+    h.play_next(false).await;
+
+    // The end handler advances to B on its own, then stops at the end of the queue
+    assert_eq!(h.next_event().await, started("Order A"));
+    assert_eq!(h.next_event().await, finished("Order A"));
+    assert_eq!(h.next_event().await, started("Order B"));
+    assert_eq!(h.next_event().await, finished("Order B"));
+    h.wait_idle().await;
+
+    assert!(queue::get_current(&h.qm, h.gid).await.is_none());
+    let history: Vec<_> = queue::get_history(&h.qm, h.gid)
+        .await
+        .into_iter()
+        .map(|s| s.title)
+        .collect();
+    assert_eq!(history, ["Order A", "Order B"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_skip_replaces_track_without_double_advance() {
+    let mut h = Harness::new(2).await;
+    h.enqueue("skip-a", "Skip A", 3.0).await;
+    h.enqueue("skip-b", "Skip B", 0.2).await;
+    h.enqueue("skip-c", "Skip C", 0.2).await;
+
+    h.play_next(false).await;
+    assert_eq!(h.next_event().await, started("Skip A"));
+
+    // /skip: the replaced track must not fire its end handler and jump over B
+    h.play_next(true).await;
+    let mut events = Vec::new();
+    for _ in 0..5 {
+        events.push(h.next_event().await);
+    }
+    h.wait_idle().await;
+
+    assert!(events.contains(&Playback::Replaced("Skip A".to_string())));
+    assert!(!events.contains(&finished("Skip A")));
+    let order: Vec<_> = events
+        .into_iter()
+        .filter(|e| !matches!(e, Playback::Replaced(_)))
+        .collect();
+    assert_eq!(
+        order,
+        [
+            started("Skip B"),
+            finished("Skip B"),
+            started("Skip C"),
+            finished("Skip C")
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_loop_song_repeats_until_turned_off() {
+    let mut h = Harness::new(3).await;
+    h.enqueue("loop-a", "Loop A", 0.2).await;
+    h.enqueue("loop-b", "Loop B", 0.2).await;
+    queue::set_loop_mode(&h.qm, h.gid, LoopMode::Song).await;
+
+    h.play_next(false).await;
+    for _ in 0..3 {
+        assert_eq!(h.next_event().await, started("Loop A"));
+        assert_eq!(h.next_event().await, finished("Loop A"));
+    }
+
+    // Turning the loop off lets the current repeat finish, then moves on
+    queue::set_loop_mode(&h.qm, h.gid, LoopMode::Off).await;
+    let mut titles = Vec::new();
+    loop {
+        match h.next_event().await {
+            Playback::Started(title) => titles.push(title),
+            Playback::Finished(title) if title == "Loop B" => break,
+            Playback::Finished(_) => {}
+            other => panic!("unexpected playback event: {other:?}"),
+        }
+    }
+    h.wait_idle().await;
+
+    assert_eq!(titles.last().map(String::as_str), Some("Loop B"));
+    assert!(titles[..titles.len() - 1].iter().all(|t| t == "Loop A"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_leave_stops_without_advancing() {
+    let mut h = Harness::new(4).await;
+    h.enqueue("leave-a", "Leave A", 0.3).await;
+    h.enqueue("leave-b", "Leave B", 0.2).await;
+
+    h.play_next(false).await;
+    assert_eq!(h.next_event().await, started("Leave A"));
+
+    // /leave clears the voice channel before the track ends
+    queue::set_voice_channel(&h.qm, h.gid, None).await;
+    assert_eq!(h.next_event().await, finished("Leave A"));
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(h.events.try_recv().is_err(), "Leave B should not start");
+    assert_eq!(queue::get_queue_list(&h.qm, h.gid).await.1.len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_skip_records_skipped_play() {
+    let mut h = Harness::new(5).await;
+    let stats = Arc::new(PlayStats::new(":memory:").unwrap());
+    h.services.stats = Some(stats.clone());
+    h.enqueue("stats-a", "Stats A", 3.0).await;
+    h.enqueue("stats-b", "Stats B", 0.2).await;
+
+    h.play_next(false).await;
+    assert_eq!(h.next_event().await, started("Stats A"));
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // The replaced track never fires its end handler, so play_next records the skip itself
+    h.play_next(true).await;
+    h.wait_idle().await;
+
+    let summary = stats.summary(h.gid, 0);
+    assert_eq!(summary.plays, 2);
+    assert_eq!(summary.skips, 1);
+    let top = stats.top_songs(h.gid, 0, 10, 0);
+    assert!(top.iter().any(|e| e.label == "Stats A"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_quiz_holds_the_queue() {
+    let mut h = Harness::new(6).await;
+    h.enqueue("quiz-a", "Quiz A", 0.3).await;
+    h.enqueue("quiz-b", "Quiz B", 0.2).await;
+
+    h.play_next(false).await;
+    assert_eq!(h.next_event().await, started("Quiz A"));
+    h.services.quiz_games.start(h.gid).unwrap();
+
+    // The end handler must not replace the quiz excerpt with the next song
+    assert_eq!(h.next_event().await, finished("Quiz A"));
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(
+        h.events.try_recv().is_err(),
+        "Quiz B should wait for the quiz"
+    );
+
+    // Skipping is refused without advancing the queue
+    let err = player::play_next(h.gid, &h.qm, &h.services, &h.call, true)
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), quiz::MUSIC_ON_HOLD);
+    let current = queue::get_current(&h.qm, h.gid).await.unwrap();
+    assert_eq!(current.title, "Quiz A");
+
+    // Once the quiz is over the queue moves on again
+    h.services.quiz_games.finish(h.gid);
+    h.play_next(false).await;
+    assert_eq!(h.next_event().await, started("Quiz B"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_stop_records_interrupted_play() {
+    let mut h = Harness::new(7).await;
+    let stats = Arc::new(PlayStats::new(":memory:").unwrap());
+    h.services.stats = Some(stats.clone());
+    h.enqueue("stop-a", "Stop A", 3.0).await;
+
+    h.play_next(false).await;
+    assert_eq!(h.next_event().await, started("Stop A"));
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // /stop records the song before clearing the queue and stopping the track
+    let handle = queue::get_track_handle(&h.qm, h.gid).await.unwrap();
+    player::record_skipped(h.gid, &h.qm, &h.services).await;
+    queue::clear(&h.qm, h.gid).await;
+    let _ = handle.stop();
+
+    let summary = stats.summary(h.gid, 0);
+    assert_eq!(summary.plays, 1);
+    assert_eq!(summary.skips, 1);
+}
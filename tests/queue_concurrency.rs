@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use every_discord_bot::music::{self, queue, Song};
+use serenity::model::id::GuildId;
+
+const GUILDS: u64 = 64;
+const SONGS_PER_GUILD: u32 = 50;
+
+fn guild_song(guild: u64, n: u32) -> Song {
+    Song {
+        title: format!("Guild {guild} Song {n}"),
+        url: format!("https://youtube.com/watch?v=g{guild}s{n}"),
+        requester: "user".to_string(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_many_guilds_in_parallel() {
+    // Each guild enqueues, reorders and plays through its own queue concurrently
+    let qm = music::new_queue_manager();
+
+    let tasks: Vec<_> = (1..=GUILDS)
+        .map(|g| {
+            let qm = qm.clone();
+            tokio::spawn(async move {
+                let gid = GuildId::new(g);
+                for n in 1..=SONGS_PER_GUILD {
+                    queue::add_song(&qm, gid, guild_song(g, n)).await.unwrap();
+                    tokio::task::yield_now().await;
+                }
+                if g % 2 == 0 {
+                    queue::shuffle(&qm, gid).await;
+                }
+                queue::move_to(&qm, gid, SONGS_PER_GUILD as usize, 1).await;
+                queue::set_volume(&qm, gid, g as f32 / 100.0).await;
+
+                let mut played = Vec::new();
+                while let Some(song) = queue::get_next_song(&qm, gid, false).await {
+                    played.push(song.url);
+                    tokio::task::yield_now().await;
+                }
+                played
+            })
+        })
+        .collect();
+
+    for (g, task) in (1..=GUILDS).zip(tasks) {
+        let mut played = task.await.unwrap();
+        assert_eq!(played.len(), SONGS_PER_GUILD as usize, "guild {g}");
+
+        // Every song belongs to this guild and none was lost or duplicated
+        played.sort();
+        played.dedup();
+        assert_eq!(played.len(), SONGS_PER_GUILD as usize, "guild {g}");
+        let prefix = format!("https://youtube.com/watch?v=g{g}s");
+        assert!(played.iter().all(|url| url.starts_with(&prefix)));
+
+        let gid = GuildId::new(g);
+        assert!(queue::is_empty(&qm, gid).await);
+        let volume = queue::get_volume(&qm, gid).await;
+        assert!((volume - g as f32 / 100.0).abs() < f32::EPSILON);
+    }
+    assert_eq!(qm.len(), GUILDS as usize);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_writers_same_guild() {
+    // Many users in one guild add songs at the same time while another task keeps advancing
+    // the queue, as the track-end handler does between songs
+    let qm = music::new_queue_manager();
+    let gid = GuildId::new(1);
+    let adding = Arc::new(AtomicBool::new(true));
+
+    let advancer = {
+        let qm = qm.clone();
+        let adding = adding.clone();
+        tokio::spawn(async move {
+            let mut played = Vec::new();
+            while adding.load(Ordering::Acquire) {
+                if let Some(song) = queue::get_next_song(&qm, gid, false).await {
+                    played.push(song.title);
+                }
+                tokio::task::yield_now().await;
+            }
+            played
+        })
+    };
+
+    let adders: Vec<_> = (0..8u64)
+        .map(|user| {
+            let qm = qm.clone();
+            tokio::spawn(async move {
+                for n in 0..25 {
+                    queue::add_song(&qm, gid, guild_song(user, n))
+                        .await
+                        .unwrap();
+                    tokio::task::yield_now().await;
+                }
+            })
+        })
+        .collect();
+    for adder in adders {
+        adder.await.unwrap();
+    }
+    adding.store(false, Ordering::Release);
+    let mut played = advancer.await.unwrap();
+
+    while let Some(song) = queue::get_next_song(&qm, gid, true).await {
+        played.push(song.title);
+    }
+
+    // Every song was played exactly once, whichever side took it
+    assert_eq!(played.len(), 8 * 25);
+    played.sort();
+    played.dedup();
+    assert_eq!(played.len(), 8 * 25);
+}
+
+#[tokio::test]
+async fn test_locked_guild_does_not_block_others() {
+    let qm = music::new_queue_manager();
+    let busy = GuildId::new(1);
+    let other = GuildId::new(2);
+
+    // Hold the busy guild's lock for the whole test
+    let _guard = qm.write(busy).await;
+
+    let result = tokio::time::timeout(Duration::from_secs(1), async {
+        queue::add_song(&qm, other, guild_song(2, 1)).await.unwrap();
+        queue::get_next_song(&qm, other, false).await
+    })
+    .await;
+
+    let song = result.expect("other guild was blocked by an unrelated lock");
+    assert_eq!(song.unwrap().title, "Guild 2 Song 1");
+}
@@ -0,0 +1,362 @@
+//! Offline stand-ins for yt-dlp and the Discord voice connection.
+//!
+//! `FakeYtDlp` is a shell script that answers `-j` queries for `https://fake.test/<id>`
+//! with canned JSON whose stream URL points at a local HTTP server serving generated WAV
+//! files. `OfflineDriver` implements `VoiceDriver` by decoding those streams in real time
+//! and firing the end handlers `player` registered, the way songbird would.
+#![allow(dead_code)]
+
+use std::f32::consts::PI;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use every_discord_bot::music::driver::VoiceDriver;
+use every_discord_bot::music::source::{YtDlp, YtDlpConfig};
+use every_discord_bot::music::{queue, QueueManager};
+use serenity::model::id::GuildId;
+use songbird::error::ControlError;
+use songbird::events::{EventContext, EventHandler};
+use songbird::input::{Input, LiveInput};
+use songbird::tracks::{PlayMode, Track, TrackHandle, TrackState};
+use songbird::{Config, Driver};
+use symphonia::core::errors::Error as SymphoniaError;
+use tokio::sync::mpsc;
+
+const SAMPLE_RATE: u32 = 48_000;
+const FAKE_HOST: &str = "https://fake.test/";
+
+const SCRIPT: &str = r#"#!/bin/sh
+# Fake yt-dlp: print the canned JSON for https://fake.test/<id> or ytsearch1:<id>
+for arg in "$@"; do
+  case "$arg" in
+    https://fake.test/*) id="${arg#https://fake.test/}" ;;
+    ytsearch*:*) id=$(printf '%s' "${arg#*:}" | tr ' ' '-') ;;
+    *) continue ;;
+  esac
+  if [ -f "__DIR__/$id.sleep" ]; then sleep "$(cat "__DIR__/$id.sleep")"; fi
+  if [ -f "__DIR__/$id.json" ]; then cat "__DIR__/$id.json"; exit 0; fi
+  echo "ERROR: [fake] $id: Video unavailable" >&2
+  exit 1
+done
+echo "ERROR: no URL given" >&2
+exit 1
+"#;
+
+/// Scripted yt-dlp plus the HTTP server that serves its audio
+pub struct FakeYtDlp {
+    dir: PathBuf,
+    binary: PathBuf,
+    base_url: String,
+}
+
+impl FakeYtDlp {
+    /// Creates the script in a fresh temp directory and starts the audio server
+    pub fn start() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "everybot-fake-ytdlp-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let binary = dir.join("yt-dlp");
+        std::fs::write(&binary, SCRIPT.replace("__DIR__", dir.to_str().unwrap())).unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let base_url = serve_dir(dir.clone());
+        Self {
+            dir,
+            binary,
+            base_url,
+        }
+    }
+
+    /// Process-wide instance shared by tests that only need distinct song ids
+    pub fn global() -> &'static FakeYtDlp {
+        static FAKE: OnceLock<FakeYtDlp> = OnceLock::new();
+        FAKE.get_or_init(FakeYtDlp::start)
+    }
+
+    /// A runner that uses this script instead of the real binary
+    pub fn runner(&self, timeout: Duration) -> YtDlp {
+        YtDlp::new(YtDlpConfig {
+            binary: self.binary.to_str().unwrap().to_string(),
+            timeout,
+            ..Default::default()
+        })
+    }
+
+    /// Registers a song of `secs` seconds and returns its page URL
+    pub fn add_song(&self, id: &str, title: &str, secs: f32) -> String {
+        std::fs::write(self.dir.join(format!("{id}.wav")), sine_wav(secs)).unwrap();
+        let page_url = Self::url(id);
+        let info = serde_json::json!({
+            "title": title,
+            "webpage_url": page_url,
+            "duration": secs,
+            "uploader": "Fake Uploader",
+            "is_live": false,
+            "url": format!("{}/{id}.wav", self.base_url),
+        });
+        std::fs::write(self.dir.join(format!("{id}.json")), info.to_string()).unwrap();
+        page_url
+    }
+
+    /// Makes every lookup of `id` hang for `secs` seconds first
+    pub fn delay(&self, id: &str, secs: u32) {
+        std::fs::write(self.dir.join(format!("{id}.sleep")), secs.to_string()).unwrap();
+    }
+
+    pub fn url(id: &str) -> String {
+        format!("{FAKE_HOST}{id}")
+    }
+}
+
+impl Drop for FakeYtDlp {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// 16-bit mono PCM WAV with a 440Hz tone
+fn sine_wav(secs: f32) -> Vec<u8> {
+    let frames = (secs * SAMPLE_RATE as f32) as u32;
+    let data_len = frames * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for i in 0..frames {
+        let t = i as f32 / SAMPLE_RATE as f32;
+        let sample = ((2.0 * PI * 440.0 * t).sin() * 8_000.0) as i16;
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+/// Serves files from `dir` over plain HTTP on a background thread and returns the base URL
+fn serve_dir(dir: PathBuf) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let dir = dir.clone();
+            std::thread::spawn(move || {
+                let mut reader = BufReader::new(&stream);
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).is_err() {
+                    return;
+                }
+                // Drain the headers; the body is always sent whole
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    line.clear();
+                }
+
+                let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+                let mut stream = &stream;
+                match std::fs::read(dir.join(path.trim_start_matches('/'))) {
+                    Ok(body) => {
+                        let _ = write!(
+                            stream,
+                            "HTTP/1.1 200 OK\r\nContent-Type: audio/wav\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            body.len()
+                        );
+                        let _ = stream.write_all(&body);
+                    }
+                    Err(_) => {
+                        let _ = write!(
+                            stream,
+                            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        );
+                    }
+                }
+            });
+        }
+    });
+    base_url
+}
+
+/// What the offline driver did, in order
+#[derive(Debug, Clone, PartialEq)]
+pub enum Playback {
+    /// A track started; carries the queue's current song title at that moment
+    Started(String),
+    /// A track reached its natural end
+    Finished(String),
+    /// A track was replaced by `play_only` before finishing
+    Replaced(String),
+    /// The input could not be opened or decoded
+    Failed(String),
+}
+
+type Handlers = Arc<Mutex<Vec<(TrackHandle, Box<dyn EventHandler>)>>>;
+
+/// Plays tracks by decoding them at real-time speed, without a Discord connection
+pub struct OfflineDriver {
+    /// Only used to mint track handles; an unconnected driver never mixes anything
+    inner: Driver,
+    guild_id: GuildId,
+    queue_manager: QueueManager,
+    generation: Arc<AtomicU64>,
+    handlers: Handlers,
+    events: mpsc::UnboundedSender<Playback>,
+}
+
+impl OfflineDriver {
+    pub fn new(
+        guild_id: GuildId,
+        queue_manager: QueueManager,
+    ) -> (Self, mpsc::UnboundedReceiver<Playback>) {
+        let (events, rx) = mpsc::unbounded_channel();
+        let driver = Self {
+            inner: Driver::new(Config::default()),
+            guild_id,
+            queue_manager,
+            generation: Arc::new(AtomicU64::new(0)),
+            handlers: Arc::default(),
+            events,
+        };
+        (driver, rx)
+    }
+}
+
+impl VoiceDriver for OfflineDriver {
+    fn play_only(&mut self, mut track: Track) -> TrackHandle {
+        // Keep the real input for ourselves and hand songbird an inert placeholder
+        let input = std::mem::replace(
+            &mut track.input,
+            Input::from(songbird::input::File::new("/dev/null")),
+        );
+        let handle = self.inner.play_only(track);
+
+        // Like songbird, a replaced track stops silently and its handlers never run
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.handlers.lock().unwrap().clear();
+
+        let current = self.generation.clone();
+        let handlers = self.handlers.clone();
+        let events = self.events.clone();
+        let queue_manager = self.queue_manager.clone();
+        let guild_id = self.guild_id;
+        let handle_for_task = handle.clone();
+        tokio::spawn(async move {
+            let title = queue::get_current(&queue_manager, guild_id)
+                .await
+                .map(|song| song.title)
+                .unwrap_or_default();
+            let _ = events.send(Playback::Started(title.clone()));
+
+            let played = match decode_realtime(input, current.clone(), generation).await {
+                Ok(Some(played)) => played,
+                Ok(None) => {
+                    let _ = events.send(Playback::Replaced(title));
+                    return;
+                }
+                Err(e) => {
+                    let _ = events.send(Playback::Failed(format!("{title}: {e}")));
+                    return;
+                }
+            };
+            let _ = events.send(Playback::Finished(title));
+
+            let ended = {
+                let mut handlers = handlers.lock().unwrap();
+                if current.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+                let (ended, rest) = std::mem::take(&mut *handlers)
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|(track, _)| track.uuid() == handle_for_task.uuid());
+                *handlers = rest;
+                ended
+            };
+            let state = TrackState {
+                playing: PlayMode::End,
+                volume: 1.0,
+                position: played,
+                play_time: played,
+                ..Default::default()
+            };
+            for (_, handler) in ended {
+                handler
+                    .act(&EventContext::Track(&[(&state, &handle_for_task)]))
+                    .await;
+            }
+        });
+
+        handle
+    }
+
+    fn add_end_event<H: EventHandler + 'static>(
+        &mut self,
+        track: &TrackHandle,
+        handler: H,
+    ) -> Result<(), ControlError> {
+        self.handlers
+            .lock()
+            .unwrap()
+            .push((track.clone(), Box::new(handler)));
+        Ok(())
+    }
+}
+
+/// Decodes `input` to the end, sleeping as long as the audio lasts.
+/// Returns `None` if a newer track replaced it first.
+async fn decode_realtime(
+    input: Input,
+    current: Arc<AtomicU64>,
+    generation: u64,
+) -> Result<Option<Duration>, Box<dyn std::error::Error + Send + Sync>> {
+    let input = input
+        .make_playable_async(
+            &songbird::input::codecs::CODEC_REGISTRY,
+            &songbird::input::codecs::PROBE,
+        )
+        .await?;
+    let Input::Live(LiveInput::Parsed(mut parsed), _) = input else {
+        return Err("input was not parsed".into());
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let mut played = Duration::ZERO;
+        loop {
+            if current.load(Ordering::SeqCst) != generation {
+                return Ok(None);
+            }
+            let packet = match parsed.format.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(e))
+                    if e.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    return Ok(Some(played));
+                }
+                Err(e) => return Err(e.into()),
+            };
+            if packet.track_id() != parsed.track_id {
+                continue;
+            }
+            let decoded = parsed.decoder.decode(&packet)?;
+            let rate = decoded.spec().rate;
+            let chunk = Duration::from_secs_f64(decoded.frames() as f64 / rate as f64);
+            std::thread::sleep(chunk);
+            played += chunk;
+        }
+    })
+    .await?
+}
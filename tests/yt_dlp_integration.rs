@@ -1,11 +1,18 @@
-use every_discord_bot::music::source;
+mod support;
+
+use std::time::Duration;
+
+use every_discord_bot::music::source::{YtDlp, YtDlpConfig};
+use support::FakeYtDlp;
 
 #[tokio::test]
 #[ignore] // Requires yt-dlp installed and network access
-async fn test_get_song_info_with_url() {
+async fn test_song_info_with_url() {
     // Use a well-known, stable YouTube video (Rick Astley - Never Gonna Give You Up)
-    let result = source::get_song_info("https://www.youtube.com/watch?v=dQw4w9WgXcQ").await;
-    assert!(result.is_ok(), "get_song_info failed: {:?}", result.err());
+    let result = YtDlp::new(YtDlpConfig::default())
+        .song_info("https://www.youtube.com/watch?v=dQw4w9WgXcQ")
+        .await;
+    assert!(result.is_ok(), "song_info failed: {:?}", result.err());
     let song = result.unwrap();
     assert!(!song.title.is_empty());
     assert!(song.url.contains("youtube.com") || song.url.contains("youtu.be"));
@@ -14,10 +21,83 @@ async fn test_get_song_info_with_url() {
 
 #[tokio::test]
 #[ignore] // Requires yt-dlp installed and network access
-async fn test_get_song_info_with_search() {
-    let result = source::get_song_info("never gonna give you up rick astley").await;
+async fn test_song_info_with_search() {
+    let result = YtDlp::new(YtDlpConfig::default())
+        .song_info("never gonna give you up rick astley")
+        .await;
     assert!(result.is_ok(), "search failed: {:?}", result.err());
     let song = result.unwrap();
     assert!(!song.title.is_empty());
     assert!(song.duration.is_some());
 }
+
+// The tests below run the same code paths against the scripted fake, so they need no network
+
+#[tokio::test]
+async fn test_fake_song_info_with_url() {
+    let fake = FakeYtDlp::start();
+    let url = fake.add_song("info", "Fake Song", 1.5);
+
+    let song = fake
+        .runner(Duration::from_secs(10))
+        .song_info(&url)
+        .await
+        .unwrap();
+    assert_eq!(song.title, "Fake Song");
+    assert_eq!(song.url, url);
+    assert_eq!(song.duration.as_deref(), Some("0:01"));
+    assert_eq!(song.uploader.as_deref(), Some("Fake Uploader"));
+    assert!(!song.is_live);
+}
+
+#[tokio::test]
+async fn test_fake_song_info_with_search() {
+    let fake = FakeYtDlp::start();
+    fake.add_song("fake-search-song", "Searched Song", 1.0);
+
+    let song = fake
+        .runner(Duration::from_secs(10))
+        .song_info("fake search song")
+        .await
+        .unwrap();
+    assert_eq!(song.title, "Searched Song");
+}
+
+#[tokio::test]
+async fn test_fake_stream_source() {
+    let fake = FakeYtDlp::start();
+    let url = fake.add_song("stream", "Stream Song", 1.0);
+
+    let stream = fake
+        .runner(Duration::from_secs(10))
+        .stream_source(&url)
+        .await
+        .unwrap();
+    assert!(stream.url().ends_with("/stream.wav"), "{}", stream.url());
+}
+
+#[tokio::test]
+async fn test_fake_reports_yt_dlp_error() {
+    let fake = FakeYtDlp::start();
+
+    let err = fake
+        .runner(Duration::from_secs(10))
+        .song_info(&FakeYtDlp::url("missing"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Video unavailable"), "{err}");
+}
+
+#[tokio::test]
+async fn test_fake_times_out() {
+    let fake = FakeYtDlp::start();
+    let url = fake.add_song("slow", "Slow Song", 1.0);
+    fake.delay("slow", 5);
+
+    let err = fake
+        .runner(Duration::from_secs(1))
+        .song_info(&url)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("시간 초과"), "{err}");
+}